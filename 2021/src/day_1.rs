@@ -1,18 +1,31 @@
-use std::{
-    collections::VecDeque,
-    io::{self},
+use std::collections::VecDeque;
+use utils::{
+    error::{AocResult, LineContext},
+    solution::{Answer, Solution},
 };
-use utils::file_utils::read_lines;
 
-pub fn day_1() -> io::Result<i32> {
-    fetch_increase_measurements("./inputs/day-1-input.txt")
-}
-pub fn day_1_part_2() -> io::Result<i32> {
-    calc_measurement_window_increase("./inputs/day-1-input.txt")
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        fetch_increase_measurements(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        calc_measurement_window_increase(input).map(Answer::from)
+    }
 }
 
-fn fetch_increase_measurements(filename: &str) -> io::Result<i32> {
-    let measurements = fetch_measurements(filename)?;
+fn fetch_increase_measurements(input: &str) -> AocResult<i32> {
+    let measurements = fetch_measurements(input)?;
 
     let mut increases = 0;
     let mut prev_measurement_opt = Option::None;
@@ -27,8 +40,8 @@ fn fetch_increase_measurements(filename: &str) -> io::Result<i32> {
     Ok(increases)
 }
 
-fn calc_measurement_window_increase(filename: &str) -> io::Result<i32> {
-    let measurements = fetch_measurements(filename)?;
+fn calc_measurement_window_increase(input: &str) -> AocResult<i32> {
+    let measurements = fetch_measurements(input)?;
 
     let mut increases = 0;
     let mut window = VecDeque::new();
@@ -53,15 +66,14 @@ fn calc_measurement_window_increase(filename: &str) -> io::Result<i32> {
     Ok(increases)
 }
 
-fn fetch_measurements(filename: &str) -> io::Result<Vec<i32>> {
+fn fetch_measurements(input: &str) -> AocResult<Vec<i32>> {
     let mut measurements_vec = Vec::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
-        let line_measurement_opt = line.parse::<i32>();
-        if let Ok(line_measurement) = line_measurement_opt {
-            measurements_vec.push(line_measurement);
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            continue;
         }
+        measurements_vec.push(line.parse::<i32>().on_line(i)?);
     }
     Ok(measurements_vec)
 }
@@ -74,7 +86,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            fetch_increase_measurements("./inputs/day-1-input-test.txt").unwrap(),
+            fetch_increase_measurements(include_str!("../inputs/day-1-input-test.txt")).unwrap(),
             7
         );
     }
@@ -82,7 +94,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            fetch_increase_measurements("./inputs/day-1-input.txt").unwrap(),
+            fetch_increase_measurements(include_str!("../inputs/day-1-input.txt")).unwrap(),
             1466
         );
     }
@@ -90,7 +102,8 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            calc_measurement_window_increase("./inputs/day-1-input-test.txt").unwrap(),
+            calc_measurement_window_increase(include_str!("../inputs/day-1-input-test.txt"))
+                .unwrap(),
             5
         );
     }
@@ -98,7 +111,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            calc_measurement_window_increase("./inputs/day-1-input.txt").unwrap(),
+            calc_measurement_window_increase(include_str!("../inputs/day-1-input.txt")).unwrap(),
             1491
         );
     }
@@ -1,15 +1,45 @@
-use std::{
-    collections::VecDeque,
-    io::{self},
+use std::collections::VecDeque;
+
+use utils::{
+    error::{AocError, AocResult},
+    solution::{Answer, Solution},
 };
 
-use utils::file_utils::read_lines;
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        find_syntax_errors(input).map(Answer::from)
+    }
 
-pub fn day_10() -> io::Result<i32> {
-    find_syntax_errors("./inputs/day-10-input.txt")
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        solve_syntax_errors(input).map(Answer::from)
+    }
 }
-pub fn day_10_part_2() -> io::Result<i64> {
-    solve_syntax_errors("./inputs/day-10-input.txt")
+
+/**
+ * Rejects lines containing anything other than braces.
+ */
+fn check_line(index: usize, line: &str) -> AocResult<()> {
+    match line
+        .char_indices()
+        .find(|(_, c)| !c.is_whitespace() && !"({<[)}>]".contains(*c))
+    {
+        Some((column, c)) => Err(AocError::parse_at(
+            index,
+            column,
+            format!("unknown character '{c}'"),
+        )),
+        None => Ok(()),
+    }
 }
 
 fn char_is_open(c: &char) -> bool {
@@ -57,14 +87,14 @@ fn solve_brace_score(c: &char) -> i32 {
     }
 }
 
-fn find_syntax_errors(filename: &str) -> io::Result<i32> {
+fn find_syntax_errors(input: &str) -> AocResult<i32> {
     let mut score = 0;
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
+        check_line(i, line)?;
 
         let mut stack = VecDeque::new();
 
@@ -88,14 +118,14 @@ fn find_syntax_errors(filename: &str) -> io::Result<i32> {
     Ok(score)
 }
 
-fn solve_syntax_errors(filename: &str) -> io::Result<i64> {
+fn solve_syntax_errors(input: &str) -> AocResult<i64> {
     let mut scores = Vec::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
+        check_line(i, line)?;
 
         let mut stack = VecDeque::new();
 
@@ -130,7 +160,10 @@ fn solve_syntax_errors(filename: &str) -> io::Result<i64> {
     }
 
     scores.sort();
-    let score = scores.get(scores.len() / 2).unwrap().to_owned();
+    let score = scores
+        .get(scores.len() / 2)
+        .ok_or_else(|| AocError::invalid_state("every line is corrupted"))?
+        .to_owned();
 
     Ok(score)
 }
@@ -143,7 +176,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            find_syntax_errors("./inputs/day-10-input-test.txt").unwrap(),
+            find_syntax_errors(include_str!("../inputs/day-10-input-test.txt")).unwrap(),
             26397
         );
     }
@@ -151,7 +184,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            find_syntax_errors("./inputs/day-10-input.txt").unwrap(),
+            find_syntax_errors(include_str!("../inputs/day-10-input.txt")).unwrap(),
             366027
         );
     }
@@ -159,7 +192,7 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            solve_syntax_errors("./inputs/day-10-input-test.txt").unwrap(),
+            solve_syntax_errors(include_str!("../inputs/day-10-input-test.txt")).unwrap(),
             288957
         );
     }
@@ -167,7 +200,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            solve_syntax_errors("./inputs/day-10-input.txt").unwrap(),
+            solve_syntax_errors(include_str!("../inputs/day-10-input.txt")).unwrap(),
             1118645287
         );
     }
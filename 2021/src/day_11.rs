@@ -1,106 +1,186 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    io::{self},
+use itertools::Itertools;
+
+use utils::{
+    automaton::{Automaton, Dense, Neighbourhood},
+    error::{AocError, AocResult},
+    grid::Grid,
+    simulation::Simulation,
+    solution::{Answer, Solution},
 };
 
-use utils::file_utils::read_lines;
-
 const ENERGY_LIMIT: i32 = 10;
 
-pub fn day_11() -> io::Result<i32> {
-    run_steps("./inputs/day-11-input.txt", 100)
-}
-pub fn day_11_part_2() -> io::Result<i32> {
-    find_first_synced_flash("./inputs/day-11-input.txt")
+/**
+ * An octopus's energy level, or `None` once it has flashed during the current step.
+ */
+type Energy = Option<i32>;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        11
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        run_steps(input, 100).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        find_first_synced_flash(input).map(Answer::from)
+    }
+
+    fn simulate(&self, part: u32, input: &str) -> AocResult<Box<dyn Simulation>> {
+        Ok(Box::new(Octopuses {
+            grid: load_grid(input)?,
+            part,
+            steps: 0,
+            step_flashes: 0,
+            flashes: 0,
+        }))
+    }
 }
 
-fn load_map(filename: &str) -> io::Result<HashMap<(i32, i32), i32>> {
-    let mut map = HashMap::new();
+/**
+ * The octopuses stepping towards the answer of one part: 100 steps for part 1, or until they
+ * all flash together for part 2.
+ */
+struct Octopuses {
+    grid: Grid<Energy>,
+    part: u32,
+    steps: i32,
+    step_flashes: i32,
+    flashes: i32,
+}
 
-    let lines = read_lines(filename)?;
-    let mut y = 0;
-    for line in lines.flatten() {
-        if line.is_empty() {
-            continue;
+impl Simulation for Octopuses {
+    fn step(&mut self) -> bool {
+        let finished = match self.part {
+            1 => self.steps >= 100,
+            _ => self.step_flashes == octopus_count(&self.grid),
+        };
+        if finished {
+            return false;
         }
+        // flashes always stop spreading within a step, so this can't fail in practice
+        let Ok((grid, step_flashes)) = perform_step(self.grid.clone()) else {
+            return false;
+        };
+        self.grid = grid;
+        self.steps += 1;
+        self.step_flashes = step_flashes;
+        self.flashes += step_flashes;
+        true
+    }
 
-        for (x, c) in line.trim().chars().enumerate() {
-            let energy = format!("{c}").parse::<i32>().unwrap();
-            map.insert((x as i32, y), energy);
-        }
+    fn render(&self) -> Vec<String> {
+        self.grid
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .map(|energy| match energy {
+                        Some(0) | None => '*',
+                        Some(energy) => char::from_digit(*energy as u32, 10).unwrap_or('?'),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
 
-        y += 1;
+    fn metrics(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("Step", self.steps.to_string()),
+            ("Flashes this step", self.step_flashes.to_string()),
+            ("Total flashes", self.flashes.to_string()),
+        ]
     }
-    Ok(map)
 }
 
-fn perform_step(mut map: HashMap<(i32, i32), i32>) -> (HashMap<(i32, i32), i32>, i32) {
-    let mut to_flash = VecDeque::new();
-    let mut flashed = HashSet::new();
-    for ((x, y), energy) in map.iter_mut() {
-        *energy += 1;
-        if *energy >= ENERGY_LIMIT {
-            to_flash.push_back((*x, *y));
-        }
+fn load_grid(input: &str) -> AocResult<Grid<Energy>> {
+    let grid = Grid::parse(input, |c| {
+        c.to_digit(10)
+            .map(|energy| Some(energy as i32))
+            .ok_or_else(|| format!("unknown energy level '{c}'"))
+    })?;
+    if grid.width() == 0 {
+        return Err(AocError::parse(0, "there are no octopuses"));
     }
+    Ok(grid)
+}
 
-    while let Some((x, y)) = to_flash.pop_front() {
-        if flashed.contains(&(x, y)) {
-            continue;
-        }
-        flashed.insert((x, y));
-
-        let adjacent: Vec<(i32, i32)> = vec![
-            (x - 1, y - 1),
-            (x, y - 1),
-            (x + 1, y - 1),
-            (x - 1, y),
-            (x + 1, y),
-            (x - 1, y + 1),
-            (x, y + 1),
-            (x + 1, y + 1),
-        ];
-
-        for (adj_x, adj_y) in adjacent {
-            if let Some(energy) = map.get_mut(&(adj_x, adj_y)) {
-                *energy += 1;
-                if *energy >= ENERGY_LIMIT {
-                    to_flash.push_back((adj_x, adj_y));
-                }
-            }
+fn octopus_count(grid: &Grid<Energy>) -> i32 {
+    (grid.width() * grid.height()) as i32
+}
+
+/**
+ * Spreads flashes one generation further: octopuses over the limit flash, and every other
+ * octopus gains a level for each neighbour flashing.
+ */
+fn flash(energy: &Energy, neighbours: &[&Energy]) -> Energy {
+    match energy {
+        Some(energy) if *energy < ENERGY_LIMIT => {
+            let flashing = neighbours
+                .iter()
+                .filter(|neighbour| neighbour.is_some_and(|n| n >= ENERGY_LIMIT))
+                .count();
+            Some(energy + flashing as i32)
         }
+        _ => None,
     }
+}
 
-    for (x, y) in flashed.iter() {
-        if let Some(energy) = map.get_mut(&(*x, *y)) {
-            *energy = 0;
-        }
+fn perform_step(mut grid: Grid<Energy>) -> AocResult<(Grid<Energy>, i32)> {
+    let coordinates = grid.coordinates().collect_vec();
+    for &coordinate in &coordinates {
+        grid[coordinate] = grid[coordinate].map(|energy| energy + 1);
     }
 
-    (map, flashed.len() as i32)
+    // every generation that changes anything flashes at least one more octopus
+    let limit = coordinates.len() + 1;
+    let mut octopuses = Dense::new(grid, Neighbourhood::Surrounding, flash);
+    octopuses
+        .run_until_stable(limit, |_| {})
+        .ok_or_else(|| AocError::invalid_state("the flashes never stop spreading"))?;
+
+    let mut grid = octopuses.into_grid();
+    let mut flashes = 0;
+    for &coordinate in &coordinates {
+        if grid[coordinate].is_none() {
+            grid[coordinate] = Some(0);
+            flashes += 1;
+        }
+    }
+    Ok((grid, flashes))
 }
 
-fn run_steps(filepath: &str, steps: usize) -> io::Result<i32> {
-    let mut map = load_map(filepath)?;
+fn run_steps(input: &str, steps: usize) -> AocResult<i32> {
+    let mut grid = load_grid(input)?;
     let mut flashes = 0;
     for _ in 0..steps {
-        let (step_map, step_flashes) = perform_step(map);
-        map = step_map;
+        let (step_grid, step_flashes) = perform_step(grid)?;
+        grid = step_grid;
         flashes += step_flashes;
     }
     Ok(flashes)
 }
 
-fn find_first_synced_flash(filepath: &str) -> io::Result<i32> {
-    let mut map = load_map(filepath)?;
+fn find_first_synced_flash(input: &str) -> AocResult<i32> {
+    let mut grid = load_grid(input)?;
+    let octopuses = octopus_count(&grid);
     for i in 0..1_000_000 {
-        let (step_map, step_flashes) = perform_step(map);
-        map = step_map;
-        if step_flashes == 100 {
+        let (step_grid, step_flashes) = perform_step(grid)?;
+        grid = step_grid;
+        if step_flashes == octopuses {
             return Ok(i + 1);
         }
     }
-    panic!("Failed to converge.");
+    Err(AocError::invalid_state(
+        "the octopuses never flash together",
+    ))
 }
 
 #[cfg(test)]
@@ -111,28 +191,50 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            run_steps("./inputs/day-11-input-test.txt", 100).unwrap(),
+            run_steps(include_str!("../inputs/day-11-input-test.txt"), 100).unwrap(),
             1656
         );
     }
 
     #[test]
     fn test() {
-        assert_eq!(run_steps("./inputs/day-11-input.txt", 100).unwrap(), 1608);
+        assert_eq!(
+            run_steps(include_str!("../inputs/day-11-input.txt"), 100).unwrap(),
+            1608
+        );
     }
 
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            find_first_synced_flash("./inputs/day-11-input-test.txt").unwrap(),
+            find_first_synced_flash(include_str!("../inputs/day-11-input-test.txt")).unwrap(),
             195
         );
     }
 
+    #[test]
+    fn simulate_test() {
+        let input = include_str!("../inputs/day-11-input-test.txt");
+        let mut simulation = Day11.simulate(2, input).unwrap();
+        let mut steps = 0;
+        while simulation.step() {
+            steps += 1;
+        }
+        assert_eq!(steps, 195);
+        assert!(simulation.render().iter().all(|row| row == "**********"));
+
+        let mut simulation = Day11.simulate(1, input).unwrap();
+        while simulation.step() {}
+        assert_eq!(
+            simulation.metrics()[2],
+            ("Total flashes", "1656".to_string())
+        );
+    }
+
     #[test]
     fn part_2_test() {
         assert_eq!(
-            find_first_synced_flash("./inputs/day-11-input.txt").unwrap(),
+            find_first_synced_flash(include_str!("../inputs/day-11-input.txt")).unwrap(),
             214
         );
     }
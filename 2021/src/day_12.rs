@@ -1,12 +1,14 @@
 use std::{
     collections::{HashMap, VecDeque},
-    io::{self},
     rc::Rc,
 };
 
 use itertools::Itertools;
 
-use utils::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult},
+    solution::{Answer, Solution},
+};
 
 const START: &str = "start";
 const END: &str = "end";
@@ -37,27 +39,44 @@ fn fetch_has_repeated(node: Rc<Node>) -> bool {
     false
 }
 
-pub fn day_12() -> io::Result<i32> {
-    find_paths("./inputs/day-12-input.txt")
-}
-pub fn day_12_part_2() -> io::Result<i32> {
-    find_paths_with_repeat("./inputs/day-12-input.txt")
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        12
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        find_paths(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        find_paths_with_repeat(input).map(Answer::from)
+    }
 }
 
 fn is_small(name: String) -> bool {
     name.chars().next().unwrap().is_lowercase()
 }
 
-fn load_map(filename: &str) -> io::Result<HashMap<String, Vec<String>>> {
+fn load_map(input: &str) -> AocResult<HashMap<String, Vec<String>>> {
     let mut map: HashMap<String, Vec<String>> = HashMap::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
-        let (a, b) = line.split('-').map(|s| s.trim()).collect_tuple().unwrap();
+        let (a, b) = line
+            .split('-')
+            .map(|s| s.trim())
+            .collect_tuple()
+            .filter(|(a, b)| !a.is_empty() && !b.is_empty())
+            .ok_or_else(|| AocError::parse(i, "expected two caves separated by '-'"))?;
 
         if b != START {
             if let Some(a_vec) = map.get_mut(a) {
@@ -78,8 +97,14 @@ fn load_map(filename: &str) -> io::Result<HashMap<String, Vec<String>>> {
     Ok(map)
 }
 
-fn find_paths(filename: &str) -> io::Result<i32> {
-    let map = load_map(filename)?;
+fn tunnels_from<'a>(map: &'a HashMap<String, Vec<String>>, name: &str) -> AocResult<&'a [String]> {
+    map.get(name)
+        .map(Vec::as_slice)
+        .ok_or_else(|| AocError::invalid_state(format!("there are no tunnels from cave '{name}'")))
+}
+
+fn find_paths(input: &str) -> AocResult<i32> {
+    let map = load_map(input)?;
     let mut current = VecDeque::new();
     let mut finished = Vec::new();
 
@@ -97,7 +122,7 @@ fn find_paths(filename: &str) -> io::Result<i32> {
             continue;
         }
 
-        let children = map.get(&node.name).unwrap();
+        let children = tunnels_from(&map, &node.name)?;
         for child in children {
             // check if small cave has already been visited
             if is_small(child.to_string()) && has_visited(node.clone(), child) {
@@ -115,8 +140,8 @@ fn find_paths(filename: &str) -> io::Result<i32> {
     Ok(finished.len() as i32)
 }
 
-fn find_paths_with_repeat(filename: &str) -> io::Result<i32> {
-    let map = load_map(filename)?;
+fn find_paths_with_repeat(input: &str) -> AocResult<i32> {
+    let map = load_map(input)?;
     let mut current = VecDeque::new();
     let mut finished = Vec::new();
 
@@ -134,7 +159,7 @@ fn find_paths_with_repeat(filename: &str) -> io::Result<i32> {
             continue;
         }
 
-        let children = map.get(&node.name).unwrap();
+        let children = tunnels_from(&map, &node.name)?;
         let has_repeated = fetch_has_repeated(node.clone());
         for child in children {
             let mut repeat = false;
@@ -165,18 +190,24 @@ mod tests {
 
     #[test]
     fn small_test() {
-        assert_eq!(find_paths("./inputs/day-12-input-test.txt").unwrap(), 226);
+        assert_eq!(
+            find_paths(include_str!("../inputs/day-12-input-test.txt")).unwrap(),
+            226
+        );
     }
 
     #[test]
     fn test() {
-        assert_eq!(find_paths("./inputs/day-12-input.txt").unwrap(), 5228);
+        assert_eq!(
+            find_paths(include_str!("../inputs/day-12-input.txt")).unwrap(),
+            5228
+        );
     }
 
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            find_paths_with_repeat("./inputs/day-12-input-test.txt").unwrap(),
+            find_paths_with_repeat(include_str!("../inputs/day-12-input-test.txt")).unwrap(),
             3509
         );
     }
@@ -184,7 +215,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            find_paths_with_repeat("./inputs/day-12-input.txt").unwrap(),
+            find_paths_with_repeat(include_str!("../inputs/day-12-input.txt")).unwrap(),
             131228
         );
     }
@@ -1,45 +1,64 @@
-use std::{
-    collections::HashSet,
-    io::{self},
-};
-
-use itertools::Itertools;
+use std::collections::HashSet;
 
-use utils::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    ocr,
+    parse::{field, list},
+    solution::{Answer, Solution},
+};
 
 struct Fold {
     axis: char,
     position: i32,
 }
 
-pub fn day_13() -> io::Result<i32> {
-    fold_first("./inputs/day-13-input.txt")
-}
-pub fn day_13_part_2() -> io::Result<String> {
-    calc_plot("./inputs/day-13-input.txt")
+pub struct Day13;
+
+impl Solution for Day13 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        13
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        fold_first(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        read_plot(input).map(Answer::Text)
+    }
 }
 
 type FoldingPlot = (HashSet<(i32, i32)>, Vec<Fold>);
 
-fn load_plot(filename: &str) -> io::Result<FoldingPlot> {
+fn load_plot(input: &str) -> AocResult<FoldingPlot> {
     let mut plot = HashSet::new();
     let mut folds = Vec::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
         if line.contains(',') {
-            let (x, y) = line
-                .split(',')
-                .map(|p| p.parse::<i32>().unwrap())
-                .collect_tuple()
-                .unwrap();
+            let [x, y] = list::<i32>(line, ",", i)?[..] else {
+                return Err(AocError::parse(i, "expected a dot position 'x,y'"));
+            };
             plot.insert((x, y));
         } else {
-            let (axis, position) = sscanf::sscanf!(line, "fold along {}={}", char, i32).unwrap();
+            let fold = field(line, "fold along ", i)?;
+            let (axis, position) = fold.split_once('=').ok_or_else(|| {
+                AocError::parse(i, format!("expected a fold like 'x=5', found '{fold}'"))
+            })?;
+            let axis = match axis {
+                "x" => 'x',
+                "y" => 'y',
+                _ => return Err(AocError::parse(i, format!("unknown fold axis '{axis}'"))),
+            };
+            let position = position.parse::<i32>().on_line(i)?;
             folds.push(Fold { axis, position });
         }
     }
@@ -68,14 +87,17 @@ fn perform_fold(plot: HashSet<(i32, i32)>, fold: &Fold) -> HashSet<(i32, i32)> {
     new_plot
 }
 
-fn fold_first(filepath: &str) -> io::Result<i32> {
-    let (plot, folds) = load_plot(filepath)?;
-    let new_plot = perform_fold(plot, folds.get(0).unwrap());
+fn fold_first(input: &str) -> AocResult<i32> {
+    let (plot, folds) = load_plot(input)?;
+    let fold = folds
+        .first()
+        .ok_or_else(|| AocError::invalid_state("there are no folds"))?;
+    let new_plot = perform_fold(plot, fold);
     Ok(new_plot.len() as i32)
 }
 
-fn performs_folds(filepath: &str) -> io::Result<HashSet<(i32, i32)>> {
-    let (plot, folds) = load_plot(filepath)?;
+fn performs_folds(input: &str) -> AocResult<HashSet<(i32, i32)>> {
+    let (plot, folds) = load_plot(input)?;
     let mut new_plot = plot;
     for fold in folds {
         new_plot = perform_fold(new_plot, &fold);
@@ -83,9 +105,13 @@ fn performs_folds(filepath: &str) -> io::Result<HashSet<(i32, i32)>> {
     Ok(new_plot)
 }
 
-fn print_plot(plot: &HashSet<(i32, i32)>) -> String {
-    let width = plot.iter().map(|(x, _)| x).max().unwrap();
-    let height = plot.iter().map(|(_, y)| y).max().unwrap();
+fn print_plot(plot: &HashSet<(i32, i32)>) -> AocResult<String> {
+    let (Some(width), Some(height)) = (
+        plot.iter().map(|(x, _)| x).max(),
+        plot.iter().map(|(_, y)| y).max(),
+    ) else {
+        return Err(AocError::invalid_state("there are no dots to plot"));
+    };
 
     let mut output: String = String::new();
 
@@ -101,12 +127,16 @@ fn print_plot(plot: &HashSet<(i32, i32)>) -> String {
             output.push('\n');
         }
     }
-    output
+    Ok(output)
 }
 
-fn calc_plot(filepath: &str) -> io::Result<String> {
-    let plot = performs_folds(filepath)?;
-    Ok(print_plot(&plot))
+fn calc_plot(input: &str) -> AocResult<String> {
+    let plot = performs_folds(input)?;
+    print_plot(&plot)
+}
+
+fn read_plot(input: &str) -> AocResult<String> {
+    ocr::recognise(&calc_plot(input)?)
 }
 
 #[cfg(test)]
@@ -116,18 +146,24 @@ mod tests {
 
     #[test]
     fn small_test() {
-        assert_eq!(fold_first("./inputs/day-13-input-test.txt").unwrap(), 17);
+        assert_eq!(
+            fold_first(include_str!("../inputs/day-13-input-test.txt")).unwrap(),
+            17
+        );
     }
 
     #[test]
     fn test() {
-        assert_eq!(fold_first("./inputs/day-13-input.txt").unwrap(), 814);
+        assert_eq!(
+            fold_first(include_str!("../inputs/day-13-input.txt")).unwrap(),
+            814
+        );
     }
 
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            calc_plot("./inputs/day-13-input-test.txt").unwrap(),
+            calc_plot(include_str!("../inputs/day-13-input-test.txt")).unwrap(),
             r#"#####
 #...#
 #...#
@@ -139,7 +175,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            calc_plot("./inputs/day-13-input.txt").unwrap(),
+            calc_plot(include_str!("../inputs/day-13-input.txt")).unwrap(),
             r#"###..####.####.#..#.###...##..####.###.
 #..#....#.#....#..#.#..#.#..#.#....#..#
 #..#...#..###..####.#..#.#..#.###..#..#
@@ -147,5 +183,9 @@ mod tests {
 #....#....#....#..#.#.#..#..#.#....#.#.
 #....####.####.#..#.#..#.#..#.####.#..#"#
         );
+        assert_eq!(
+            read_plot(include_str!("../inputs/day-13-input.txt")).unwrap(),
+            "PZEHRAER"
+        );
     }
 }
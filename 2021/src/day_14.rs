@@ -1,14 +1,31 @@
-use std::{collections::HashMap, io};
+use std::collections::HashMap;
 
 use itertools::Itertools;
 
-use utils::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    parse::list,
+    solution::{Answer, Solution},
+};
 
-pub fn day_14() -> io::Result<i64> {
-    count_polymer_parts(10, "./inputs/day-14-input.txt")
-}
-pub fn day_14_part_2() -> io::Result<i64> {
-    count_polymer_parts(40, "./inputs/day-14-input.txt")
+pub struct Day14;
+
+impl Solution for Day14 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        14
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        count_polymer_parts(10, input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        count_polymer_parts(40, input).map(Answer::from)
+    }
 }
 
 struct Polymer {
@@ -16,36 +33,39 @@ struct Polymer {
     insertions: HashMap<String, char>,
 }
 
-fn load_polymers(filename: &str) -> io::Result<Polymer> {
+fn load_polymers(input: &str) -> AocResult<Polymer> {
     let mut insertions = HashMap::new();
     let mut template = Vec::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
         if line.contains('>') {
-            let (pattern, insertion) = sscanf::sscanf!(line, "{} -> {}", String, char).unwrap();
-            insertions.insert(pattern, insertion);
+            let [pattern, insertion] = &list::<String>(line, "->", i)?[..] else {
+                return Err(AocError::parse(i, "expected an insertion like 'AB -> C'"));
+            };
+            insertions.insert(pattern.clone(), insertion.parse::<char>().on_line(i)?);
         } else {
             template = line.chars().collect_vec();
         }
     }
+    if template.is_empty() {
+        return Err(AocError::invalid_state("there is no polymer template"));
+    }
     Ok(Polymer {
         template,
         insertions,
     })
 }
 
-fn count_polymer_parts(steps: i32, filename: &str) -> io::Result<i64> {
-    let polymer = load_polymers(filename)?;
+fn count_polymer_parts(steps: i32, input: &str) -> AocResult<i64> {
+    let polymer = load_polymers(input)?;
 
     let mut pairs = HashMap::new();
-    for i in 0..polymer.template.len() - 1 {
-        let pair = polymer.template.get(i).unwrap().to_string()
-            + &polymer.template.get(i + 1).unwrap().to_string();
+    for (a, b) in polymer.template.iter().tuple_windows() {
+        let pair = format!("{a}{b}");
 
         pairs.entry(pair).and_modify(|x| *x += 1).or_insert(1_i64);
     }
@@ -74,8 +94,11 @@ fn count_polymer_parts(steps: i32, filename: &str) -> io::Result<i64> {
         pairs = new_pairs;
     }
 
+    // Every element starts a pair apart from the last one in the template.
     let mut counts = HashMap::new();
-    counts.insert(polymer.template.last().unwrap().to_owned(), 1_i64);
+    if let Some(&last) = polymer.template.last() {
+        counts.insert(last, 1_i64);
+    }
     for (pair, freq) in pairs {
         counts
             .entry(pair.chars().next().unwrap())
@@ -83,8 +106,11 @@ fn count_polymer_parts(steps: i32, filename: &str) -> io::Result<i64> {
             .or_insert(freq);
     }
 
-    let min = counts.values().min().unwrap();
-    let max = counts.values().max().unwrap();
+    let (min, max) = counts
+        .values()
+        .minmax()
+        .into_option()
+        .ok_or_else(|| AocError::invalid_state("the polymer has no elements"))?;
     Ok(max - min)
 }
 
@@ -96,7 +122,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            count_polymer_parts(10, "./inputs/day-14-input-test.txt").unwrap(),
+            count_polymer_parts(10, include_str!("../inputs/day-14-input-test.txt")).unwrap(),
             1588
         );
     }
@@ -104,7 +130,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            count_polymer_parts(10, "./inputs/day-14-input.txt").unwrap(),
+            count_polymer_parts(10, include_str!("../inputs/day-14-input.txt")).unwrap(),
             2587
         );
     }
@@ -112,7 +138,7 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            count_polymer_parts(40, "./inputs/day-14-input-test.txt").unwrap(),
+            count_polymer_parts(40, include_str!("../inputs/day-14-input-test.txt")).unwrap(),
             2188189693529
         );
     }
@@ -120,7 +146,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            count_polymer_parts(40, "./inputs/day-14-input.txt").unwrap(),
+            count_polymer_parts(40, include_str!("../inputs/day-14-input.txt")).unwrap(),
             3318837563123
         );
     }
@@ -1,29 +1,42 @@
-use std::io::{self};
-
 use utils::{
     coordinate::{Coordinate, CoordinateMap},
-    dijkstra::calc_route_cost,
-    file_utils::read_lines,
+    dijkstra::calc_route,
+    error::{AocError, AocResult},
+    solution::{Answer, Solution},
 };
 
-pub fn day_15() -> io::Result<i32> {
-    find_low_risk("./inputs/day-15-input.txt")
-}
-pub fn day_15_part_2() -> io::Result<i32> {
-    find_big_map_low_risk("./inputs/day-15-input.txt")
+pub struct Day15;
+
+impl Solution for Day15 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        15
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        find_low_risk(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        find_big_map_low_risk(input).map(Answer::from)
+    }
 }
 
-fn load_map(filename: &str) -> io::Result<CoordinateMap<i32>> {
+fn load_map(input: &str) -> AocResult<CoordinateMap<i32>> {
     let mut map = CoordinateMap::new();
 
-    let lines = read_lines(filename)?;
     let mut y = 0;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
         for (x, c) in line.trim().chars().enumerate() {
-            let risk = format!("{c}").parse::<i32>().unwrap();
+            let risk = c.to_digit(10).filter(|&risk| risk > 0).ok_or_else(|| {
+                AocError::parse_at(i, x, format!("expected a risk from 1 to 9, found '{c}'"))
+            })? as i32;
 
             map.insert(Coordinate::new(x as i32, y), risk);
         }
@@ -32,16 +45,14 @@ fn load_map(filename: &str) -> io::Result<CoordinateMap<i32>> {
     Ok(map)
 }
 
-fn load_big_map(filename: &str) -> io::Result<CoordinateMap<i32>> {
-    let tile = load_map(filename)?;
+fn load_big_map(input: &str) -> AocResult<CoordinateMap<i32>> {
+    let tile = load_map(input)?;
 
     let mut map = CoordinateMap::new();
 
-    let width = tile.keys().map(|c| c.x).max().unwrap().to_owned() + 1;
-    let height = tile.keys().map(|c| c.y).max().unwrap().to_owned() + 1;
-
-    debug_assert_eq!(0, width % 10);
-    debug_assert_eq!(0, height % 10);
+    let corner = bottom_right(&tile)?;
+    let width = corner.x + 1;
+    let height = corner.y + 1;
 
     for i in 0..5 {
         for j in 0..5 {
@@ -71,23 +82,31 @@ fn load_big_map(filename: &str) -> io::Result<CoordinateMap<i32>> {
     Ok(map)
 }
 
-fn find_low_risk(filepath: &str) -> io::Result<i32> {
-    let map = load_map(filepath)?;
-    Ok(find_low_risk_of_map(&map))
+fn find_low_risk(input: &str) -> AocResult<i32> {
+    let map = load_map(input)?;
+    find_low_risk_of_map(&map)
 }
 
-fn find_big_map_low_risk(filepath: &str) -> io::Result<i32> {
-    let map = load_big_map(filepath)?;
-    Ok(find_low_risk_of_map(&map))
+fn find_big_map_low_risk(input: &str) -> AocResult<i32> {
+    let map = load_big_map(input)?;
+    find_low_risk_of_map(&map)
 }
 
-fn find_low_risk_of_map(map: &CoordinateMap<i32>) -> i32 {
+fn find_low_risk_of_map(map: &CoordinateMap<i32>) -> AocResult<i32> {
     let start = Coordinate::origin();
-    let end_x = map.keys().map(|c| c.x).max().unwrap().to_owned();
-    let end_y = map.keys().map(|c| c.y).max().unwrap().to_owned();
-    let end = Coordinate::new(end_x, end_y);
+    let end = bottom_right(map)?;
+
+    let (risk, _) = calc_route(map, &start, &end)
+        .ok_or_else(|| AocError::invalid_state("there is no route to the bottom right"))?;
+
+    Ok(risk)
+}
 
-    calc_route_cost(map, &start, &end)
+fn bottom_right(map: &CoordinateMap<i32>) -> AocResult<Coordinate> {
+    let (Some(x), Some(y)) = (map.keys().map(|c| c.x).max(), map.keys().map(|c| c.y).max()) else {
+        return Err(AocError::invalid_state("the map is empty"));
+    };
+    Ok(Coordinate::new(x, y))
 }
 
 #[cfg(test)]
@@ -97,18 +116,24 @@ mod tests {
 
     #[test]
     fn small_test() {
-        assert_eq!(find_low_risk("./inputs/day-15-input-test.txt").unwrap(), 40);
+        assert_eq!(
+            find_low_risk(include_str!("../inputs/day-15-input-test.txt")).unwrap(),
+            40
+        );
     }
 
     #[test]
     fn test() {
-        assert_eq!(find_low_risk("./inputs/day-15-input.txt").unwrap(), 673);
+        assert_eq!(
+            find_low_risk(include_str!("../inputs/day-15-input.txt")).unwrap(),
+            673
+        );
     }
 
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            find_big_map_low_risk("./inputs/day-15-input-test.txt").unwrap(),
+            find_big_map_low_risk(include_str!("../inputs/day-15-input-test.txt")).unwrap(),
             315
         );
     }
@@ -116,7 +141,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            find_big_map_low_risk("./inputs/day-15-input.txt").unwrap(),
+            find_big_map_low_risk(include_str!("../inputs/day-15-input.txt")).unwrap(),
             2893
         );
     }
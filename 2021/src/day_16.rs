@@ -1,9 +1,9 @@
-use std::{
-    collections::VecDeque,
-    io::{self},
-};
+use std::collections::VecDeque;
 
-use utils::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult},
+    solution::{Answer, Solution},
+};
 
 struct BitTransmission {
     version: i32,
@@ -23,182 +23,177 @@ impl BitTransmission {
                 .sum::<i32>()
     }
 
-    fn evaluate(&self) -> i64 {
+    fn evaluate(&self) -> AocResult<i64> {
+        let values = self
+            .sub_packets
+            .iter()
+            .map(|b| b.evaluate())
+            .collect::<AocResult<Vec<_>>>()?;
+        let no_sub_packets = || AocError::invalid_state("operator packet has no sub packets");
+        let compare = |comparison: fn(&i64, &i64) -> bool| match values[..] {
+            [a, b] => Ok(comparison(&a, &b) as i64),
+            _ => Err(AocError::invalid_state(format!(
+                "comparison packets must have exactly 2 subs. Found {}.",
+                values.len()
+            ))),
+        };
         match self.type_id {
             // sum
-            0 => self.sub_packets.iter().map(|b| b.evaluate()).sum(),
+            0 => Ok(values.iter().sum()),
             // product
-            1 => self.sub_packets.iter().map(|b| b.evaluate()).product(),
+            1 => Ok(values.iter().product()),
             // minimum
-            2 => self.sub_packets.iter().map(|b| b.evaluate()).min().unwrap(),
+            2 => values.iter().copied().min().ok_or_else(no_sub_packets),
             // maximum
-            3 => self.sub_packets.iter().map(|b| b.evaluate()).max().unwrap(),
+            3 => values.iter().copied().max().ok_or_else(no_sub_packets),
             // literal
-            4 => self.literal.unwrap(),
+            4 => self
+                .literal
+                .ok_or_else(|| AocError::invalid_state("literal packet has no value")),
             // greater than
-            5 => {
-                debug_assert_eq!(
-                    2,
-                    self.sub_packets.len(),
-                    "Greater than operator must have exactly 2 subs. Found {}.",
-                    self.sub_packets.len()
-                );
-                if self.sub_packets.get(0).unwrap().evaluate()
-                    > self.sub_packets.get(1).unwrap().evaluate()
-                {
-                    1
-                } else {
-                    0
-                }
-            }
+            5 => compare(i64::gt),
             // less than
-            6 => {
-                debug_assert_eq!(
-                    2,
-                    self.sub_packets.len(),
-                    "Less than operator must have exactly 2 subs. Found {}.",
-                    self.sub_packets.len()
-                );
-                if self.sub_packets.get(0).unwrap().evaluate()
-                    < self.sub_packets.get(1).unwrap().evaluate()
-                {
-                    1
-                } else {
-                    0
-                }
-            }
+            6 => compare(i64::lt),
             // equality
-            7 => {
-                debug_assert_eq!(
-                    2,
-                    self.sub_packets.len(),
-                    "Less than operator must have exactly 2 subs. Found {}.",
-                    self.sub_packets.len()
-                );
-                if self.sub_packets.get(0).unwrap().evaluate()
-                    == self.sub_packets.get(1).unwrap().evaluate()
-                {
-                    1
-                } else {
-                    0
-                }
-            }
-            x => panic!("Unknown packet type: {}", x),
+            7 => compare(i64::eq),
+            x => Err(AocError::invalid_state(format!("unknown packet type {x}"))),
         }
     }
 }
 
-pub fn day_16() -> io::Result<i32> {
-    sum_transmissions_version("./inputs/day-16-input.txt")
-}
-pub fn day_16_part_2() -> io::Result<i64> {
-    evalutate_transmission("./inputs/day-16-input.txt")
+pub struct Day16;
+
+impl Solution for Day16 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        16
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        sum_transmissions_version(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        evalutate_transmission(input).map(Answer::from)
+    }
 }
 
-fn sum_transmissions_version(filename: &str) -> io::Result<i32> {
-    let transmission = load_transmissions(filename)?;
+fn sum_transmissions_version(input: &str) -> AocResult<i32> {
+    let transmission = load_transmissions(input)?;
 
     Ok(transmission.sum_version())
 }
 
-fn evalutate_transmission(filename: &str) -> io::Result<i64> {
-    let transmission = load_transmissions(filename)?;
+fn evalutate_transmission(input: &str) -> AocResult<i64> {
+    let transmission = load_transmissions(input)?;
 
-    Ok(transmission.evaluate())
+    transmission.evaluate()
 }
 
-fn load_transmissions(filename: &str) -> io::Result<BitTransmission> {
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+fn load_transmissions(input: &str) -> AocResult<BitTransmission> {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
-        return Ok(bit_transmission_from_string(line.trim().to_string()));
+        if let Some((column, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+            return Err(AocError::parse_at(
+                i,
+                column,
+                format!("expected a hex digit, found '{c}'"),
+            ));
+        }
+        return bit_transmission_from_string(line.trim().to_string());
     }
-    unreachable!()
+    Err(AocError::invalid_state("there is no transmission"))
 }
 
-fn bit_transmission_from_string(s: String) -> BitTransmission {
-    let mut bits = extract_bits_from_hex(s.as_str());
+fn bit_transmission_from_string(s: String) -> AocResult<BitTransmission> {
+    let mut bits = extract_bits_from_hex(s.as_str())?;
     bit_transmission_from_bits(&mut bits)
 }
 
-fn bit_transmission_from_bits(bits: &mut VecDeque<bool>) -> BitTransmission {
+fn bit_transmission_from_bits(bits: &mut VecDeque<bool>) -> AocResult<BitTransmission> {
     let mut literal = None;
     let mut sub_packets = Vec::new();
-    let version = read_number_from_bits(bits, 3);
-    let type_id = read_number_from_bits(bits, 3);
+    let version = read_number_from_bits(bits, 3)?;
+    let type_id = read_number_from_bits(bits, 3)?;
 
     if type_id == 4 {
         let mut value = 0_i64;
         loop {
-            let end = !read_bool_from_bits(bits);
-            value = value << 4 | read_number_from_bits(bits, 4) as i64;
+            let end = !read_bool_from_bits(bits)?;
+            value = value << 4 | read_number_from_bits(bits, 4)? as i64;
             if end {
                 break;
             }
         }
         literal = Some(value);
     } else {
-        let length_bit = read_bool_from_bits(bits);
+        let length_bit = read_bool_from_bits(bits)?;
         let digit_length = if length_bit { 11 } else { 15 };
 
-        let length_value = read_number_from_bits(bits, digit_length);
+        let length_value = read_number_from_bits(bits, digit_length)?;
 
         // length bit 0 means size of all sub packets
         if !length_bit {
             let mut sub_packet_bits = VecDeque::new();
 
             for _ in 0..length_value {
-                sub_packet_bits.push_back(bits.pop_front().unwrap());
+                sub_packet_bits.push_back(read_bool_from_bits(bits)?);
             }
 
             // expect subpacket(s) to exactly consume sub packet bits.
             while !sub_packet_bits.is_empty() {
-                sub_packets.push(bit_transmission_from_bits(&mut sub_packet_bits));
+                sub_packets.push(bit_transmission_from_bits(&mut sub_packet_bits)?);
             }
         }
         // digit length 1 means multiple packets, seemingly of unknown length
         else {
             for _ in 0..length_value {
-                sub_packets.push(bit_transmission_from_bits(bits));
+                sub_packets.push(bit_transmission_from_bits(bits)?);
             }
         }
     }
-    BitTransmission {
+    Ok(BitTransmission {
         version: version as i32,
         type_id: type_id as i32,
         literal,
         sub_packets,
-    }
+    })
 }
 
-fn extract_bits_from_hex(s: &str) -> VecDeque<bool> {
+fn extract_bits_from_hex(s: &str) -> AocResult<VecDeque<bool>> {
     let mut bits = VecDeque::new();
     for i in s.chars() {
-        let byte = u8::from_str_radix(&i.to_string(), 16).unwrap();
+        let byte = i
+            .to_digit(16)
+            .ok_or_else(|| AocError::invalid_state(format!("expected a hex digit, found '{i}'")))?;
         bits.push_back(byte & 0b00001000 > 0);
         bits.push_back(byte & 0b00000100 > 0);
         bits.push_back(byte & 0b00000010 > 0);
         bits.push_back(byte & 0b00000001 > 0);
     }
-    bits
+    Ok(bits)
 }
 
-fn read_number_from_bits(bits: &mut VecDeque<bool>, length: usize) -> usize {
+fn read_number_from_bits(bits: &mut VecDeque<bool>, length: usize) -> AocResult<usize> {
     let mut value = 0;
     for _ in 0..length {
-        let bit = bits.pop_front().unwrap();
+        let bit = read_bool_from_bits(bits)?;
         value <<= 1;
         if bit {
             value += 1;
         }
     }
-    value
+    Ok(value)
 }
 
-fn read_bool_from_bits(bits: &mut VecDeque<bool>) -> bool {
-    bits.pop_front().unwrap()
+fn read_bool_from_bits(bits: &mut VecDeque<bool>) -> AocResult<bool> {
+    bits.pop_front()
+        .ok_or_else(|| AocError::invalid_state("the transmission ends part way through a packet"))
 }
 
 #[cfg(test)]
@@ -210,19 +205,22 @@ mod tests {
     fn literal_test() {
         assert_eq!(
             bit_transmission_from_string("D2FE28".to_string())
+                .unwrap()
                 .literal
                 .unwrap(),
             2021
         );
 
         assert_eq!(
-            bit_transmission_from_string("38006F45291200".to_string()).version,
+            bit_transmission_from_string("38006F45291200".to_string())
+                .unwrap()
+                .version,
             1
         );
 
-        let c = bit_transmission_from_string("EE00D40C823060".to_string());
+        let c = bit_transmission_from_string("EE00D40C823060".to_string()).unwrap();
         assert_eq!(c.sub_packets.len(), 3);
-        let c_1 = c.sub_packets.get(0).unwrap();
+        let c_1 = c.sub_packets.first().unwrap();
         let c_2 = c.sub_packets.get(1).unwrap();
         let c_3 = c.sub_packets.get(2).unwrap();
         assert_eq!(c.version, 7);
@@ -232,19 +230,19 @@ mod tests {
         assert_eq!(c_2.literal, Some(2));
         assert_eq!(c_3.literal, Some(3));
 
-        let d = bit_transmission_from_string("8A004A801A8002F478".to_string());
+        let d = bit_transmission_from_string("8A004A801A8002F478".to_string()).unwrap();
         let d_sum = d.sum_version();
         assert_eq!(16, d_sum);
 
-        let e = bit_transmission_from_string("620080001611562C8802118E34".to_string());
+        let e = bit_transmission_from_string("620080001611562C8802118E34".to_string()).unwrap();
         let e_sum = e.sum_version();
         assert_eq!(12, e_sum);
 
-        let f = bit_transmission_from_string("C0015000016115A2E0802F182340".to_string());
+        let f = bit_transmission_from_string("C0015000016115A2E0802F182340".to_string()).unwrap();
         let f_sum = f.sum_version();
         assert_eq!(23, f_sum);
 
-        let g = bit_transmission_from_string("A0016C880162017C3686B18A3D4780".to_string());
+        let g = bit_transmission_from_string("A0016C880162017C3686B18A3D4780".to_string()).unwrap();
         let g_sum = g.sum_version();
         assert_eq!(31, g_sum);
     }
@@ -252,7 +250,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            sum_transmissions_version("./inputs/day-16-input-test.txt").unwrap(),
+            sum_transmissions_version(include_str!("../inputs/day-16-input-test.txt")).unwrap(),
             31
         );
     }
@@ -260,7 +258,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            sum_transmissions_version("./inputs/day-16-input.txt").unwrap(),
+            sum_transmissions_version(include_str!("../inputs/day-16-input.txt")).unwrap(),
             986
         );
     }
@@ -268,35 +266,59 @@ mod tests {
     #[test]
     fn expression_test() {
         assert_eq!(
-            bit_transmission_from_string("C200B40A82".to_string()).evaluate(),
+            bit_transmission_from_string("C200B40A82".to_string())
+                .unwrap()
+                .evaluate()
+                .unwrap(),
             3
         );
         assert_eq!(
-            bit_transmission_from_string("04005AC33890".to_string()).evaluate(),
+            bit_transmission_from_string("04005AC33890".to_string())
+                .unwrap()
+                .evaluate()
+                .unwrap(),
             54
         );
         assert_eq!(
-            bit_transmission_from_string("880086C3E88112".to_string()).evaluate(),
+            bit_transmission_from_string("880086C3E88112".to_string())
+                .unwrap()
+                .evaluate()
+                .unwrap(),
             7
         );
         assert_eq!(
-            bit_transmission_from_string("CE00C43D881120".to_string()).evaluate(),
+            bit_transmission_from_string("CE00C43D881120".to_string())
+                .unwrap()
+                .evaluate()
+                .unwrap(),
             9
         );
         assert_eq!(
-            bit_transmission_from_string("D8005AC2A8F0".to_string()).evaluate(),
+            bit_transmission_from_string("D8005AC2A8F0".to_string())
+                .unwrap()
+                .evaluate()
+                .unwrap(),
             1
         );
         assert_eq!(
-            bit_transmission_from_string("F600BC2D8F".to_string()).evaluate(),
+            bit_transmission_from_string("F600BC2D8F".to_string())
+                .unwrap()
+                .evaluate()
+                .unwrap(),
             0
         );
         assert_eq!(
-            bit_transmission_from_string("9C005AC2F8F0".to_string()).evaluate(),
+            bit_transmission_from_string("9C005AC2F8F0".to_string())
+                .unwrap()
+                .evaluate()
+                .unwrap(),
             0
         );
         assert_eq!(
-            bit_transmission_from_string("9C0141080250320F1802104A08".to_string()).evaluate(),
+            bit_transmission_from_string("9C0141080250320F1802104A08".to_string())
+                .unwrap()
+                .evaluate()
+                .unwrap(),
             1
         );
     }
@@ -304,8 +326,18 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            evalutate_transmission("./inputs/day-16-input.txt").unwrap(),
+            evalutate_transmission(include_str!("../inputs/day-16-input.txt")).unwrap(),
             18234816469452
         );
     }
+
+    #[test]
+    fn truncated_test() {
+        assert!(bit_transmission_from_string("D2FE".to_string()).is_err());
+        assert!(bit_transmission_from_string("38006F45291200".to_string())
+            .unwrap()
+            .evaluate()
+            .is_ok());
+        assert!(bit_transmission_from_string("E000".to_string()).is_err());
+    }
 }
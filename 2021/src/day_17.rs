@@ -1,12 +1,30 @@
-use std::{collections::HashSet, io};
+use std::collections::HashSet;
 
-use utils::{coordinate::Coordinate, file_utils::read_lines};
+use utils::{
+    coordinate::Coordinate,
+    error::{AocError, AocResult},
+    parse::{field, signed_ints},
+    solution::{Answer, Solution},
+};
 
-pub fn day_17() -> io::Result<i32> {
-    find_highest_shot_from_file("./inputs/day-17-input.txt")
-}
-pub fn day_17_part_2() -> io::Result<i32> {
-    find_working_vectors_from_file("./inputs/day-17-input.txt")
+pub struct Day17;
+
+impl Solution for Day17 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        17
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        find_highest_shot_from_file(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        find_working_vectors_from_file(input).map(Answer::from)
+    }
 }
 
 struct TargetArea {
@@ -22,18 +40,19 @@ struct ProbeState {
     vector: Coordinate,
 }
 
-fn load_target_area(filename: &str) -> io::Result<TargetArea> {
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+fn load_target_area(input: &str) -> AocResult<TargetArea> {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
-        let (x_1, x_2, y_2, y_1) =
-            sscanf::sscanf!(line, "target area: x={}..{}, y={}..{}", i32, i32, i32, i32).unwrap();
+        let area = field(line, "target area: ", i)?;
+        let [x_1, x_2, y_2, y_1] = signed_ints(area, i)?[..] else {
+            return Err(AocError::parse(i, "expected 'x=a..b, y=c..d'"));
+        };
         return Ok(TargetArea { x_1, y_1, x_2, y_2 });
     }
-    panic!("Failed to find target area.")
+    Err(AocError::invalid_state("there is no target area"))
 }
 
 fn process_probe_state(probe_state: &ProbeState) -> ProbeState {
@@ -52,14 +71,14 @@ fn process_probe_state(probe_state: &ProbeState) -> ProbeState {
     }
 }
 
-fn find_highest_shot_from_file(filename: &str) -> io::Result<i32> {
-    let target_area = load_target_area(filename)?;
+fn find_highest_shot_from_file(input: &str) -> AocResult<i32> {
+    let target_area = load_target_area(input)?;
     let (highest_shot, _) = find_best_shot(&target_area);
     Ok(highest_shot)
 }
 
-fn find_working_vectors_from_file(filename: &str) -> io::Result<i32> {
-    let target_area = load_target_area(filename)?;
+fn find_working_vectors_from_file(input: &str) -> AocResult<i32> {
+    let target_area = load_target_area(input)?;
     let (_, vectors) = find_best_shot(&target_area);
     Ok(vectors)
 }
@@ -137,7 +156,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            find_highest_shot_from_file("./inputs/day-17-input-test.txt").unwrap(),
+            find_highest_shot_from_file(include_str!("../inputs/day-17-input-test.txt")).unwrap(),
             45
         );
     }
@@ -145,7 +164,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            find_highest_shot_from_file("./inputs/day-17-input.txt").unwrap(),
+            find_highest_shot_from_file(include_str!("../inputs/day-17-input.txt")).unwrap(),
             10011
         );
     }
@@ -153,7 +172,8 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            find_working_vectors_from_file("./inputs/day-17-input-test.txt").unwrap(),
+            find_working_vectors_from_file(include_str!("../inputs/day-17-input-test.txt"))
+                .unwrap(),
             112
         );
     }
@@ -161,7 +181,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            find_working_vectors_from_file("./inputs/day-17-input.txt").unwrap(),
+            find_working_vectors_from_file(include_str!("../inputs/day-17-input.txt")).unwrap(),
             2994
         );
     }
@@ -1,16 +1,32 @@
-use std::{collections::VecDeque, io};
+use std::collections::VecDeque;
 
-use utils::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    solution::{Answer, Solution},
+};
 
-pub fn day_18() -> io::Result<i64> {
-    add_numbers_from_file("./inputs/day-18-input.txt")
-}
-pub fn day_18_part_2() -> io::Result<i64> {
-    find_largest_magnitude_from_file("./inputs/day-18-input.txt")
+pub struct Day18;
+
+impl Solution for Day18 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        18
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        add_numbers_from_file(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        find_largest_magnitude_from_file(input).map(Answer::from)
+    }
 }
 
-fn find_largest_magnitude_from_file(filename: &str) -> io::Result<i64> {
-    let numbers = load_snail_numbers(filename)?;
+fn find_largest_magnitude_from_file(input: &str) -> AocResult<i64> {
+    let numbers = load_snail_numbers(input)?;
 
     let mut magnitudes = Vec::new();
 
@@ -19,28 +35,32 @@ fn find_largest_magnitude_from_file(filename: &str) -> io::Result<i64> {
             if a_i == b_i {
                 continue;
             }
-            let added = add(a.clone(), b.clone());
+            let added = add(a.clone(), b.clone())?;
             let mag = added.magnitude();
             magnitudes.push(mag);
         }
     }
-    Ok(*magnitudes.iter().max().unwrap())
+    magnitudes
+        .iter()
+        .max()
+        .copied()
+        .ok_or_else(|| AocError::invalid_state("at least two numbers are needed"))
 }
 
-fn add_numbers_from_file(filename: &str) -> io::Result<i64> {
-    let numbers = load_snail_numbers(filename)?;
-    Ok(add_numbers_from_vec(numbers))
+fn add_numbers_from_file(input: &str) -> AocResult<i64> {
+    let numbers = load_snail_numbers(input)?;
+    add_numbers_from_vec(numbers)
 }
 
-fn add_numbers_from_vec(numbers: Vec<SnailNumber>) -> i64 {
+fn add_numbers_from_vec(numbers: Vec<SnailNumber>) -> AocResult<i64> {
     if let Some((first, elements)) = numbers.split_first() {
         let mut accumulator: SnailNumber = first.clone();
         for num in elements {
-            accumulator = add(accumulator, num.clone());
+            accumulator = add(accumulator, num.clone())?;
         }
-        return accumulator.magnitude();
+        return Ok(accumulator.magnitude());
     }
-    panic!("Could not load numbers")
+    Err(AocError::invalid_state("there are no numbers to add"))
 }
 
 #[derive(Clone)]
@@ -50,57 +70,31 @@ struct SnailNumber {
 }
 
 impl SnailNumber {
-    fn from_str(str: &str) -> Self {
+    fn parse(str: &str) -> Result<Self, String> {
         let mut chars = VecDeque::new();
         chars.extend(str.chars());
 
-        let result = Self::from_chars(&mut chars);
-        assert_eq!(chars.len(), 0, "Failed to consume all chars.");
-        result
-    }
-
-    fn from_chars(chars: &mut VecDeque<char>) -> Self {
-        if let Some(first) = chars.pop_front() {
-            if first != '[' {
-                panic!("Expected '[' but got '{}'", first);
-            }
-        }
-        let value_a = if let Some(c) = chars.front() {
-            match c {
-                '[' => SnailValue::from_pair(SnailNumber::from_chars(chars)),
-                token if token.is_ascii_digit() => SnailValue::from_chars(chars),
-                _ => panic!("Expected '0'..'9' or '[' but got '{}'", c),
-            }
-        } else {
-            panic!("Unexpected end of input.");
-        };
-
-        if let Some(comma) = chars.pop_front() {
-            if comma != ',' {
-                panic!("Expected comma but got'{}'", comma);
-            }
+        let result = Self::from_chars(&mut chars)?;
+        if !chars.is_empty() {
+            return Err(format!(
+                "unexpected trailing characters '{}'",
+                chars.iter().collect::<String>()
+            ));
         }
+        Ok(result)
+    }
 
-        let value_b = if let Some(c) = chars.front() {
-            match c {
-                '[' => SnailValue::from_pair(SnailNumber::from_chars(chars)),
-                token if token.is_ascii_digit() => SnailValue::from_chars(chars),
-                _ => panic!("Expected '0'..'9' or '[' but got '{}'", c),
-            }
-        } else {
-            panic!("Unexpected end of input.");
-        };
-
-        if let Some(last) = chars.pop_front() {
-            if last != ']' {
-                panic!("Expected ']' but got '{}'", last);
-            }
-        }
+    fn from_chars(chars: &mut VecDeque<char>) -> Result<Self, String> {
+        expect_char(chars, '[')?;
+        let value_a = SnailValue::from_chars(chars)?;
+        expect_char(chars, ',')?;
+        let value_b = SnailValue::from_chars(chars)?;
+        expect_char(chars, ']')?;
 
-        SnailNumber {
+        Ok(SnailNumber {
             a: value_a,
             b: value_b,
-        }
+        })
     }
 
     fn magnitude(&self) -> i64 {
@@ -111,7 +105,7 @@ impl SnailNumber {
         format!("[{a},{b}]", a = self.a.to_str(), b = self.b.to_str())
     }
 
-    fn explode(&self) -> Option<SnailNumber> {
+    fn explode(&self) -> AocResult<Option<SnailNumber>> {
         let org_str = self.to_str();
 
         let mut chars = VecDeque::with_capacity(org_str.len());
@@ -120,40 +114,29 @@ impl SnailNumber {
         let mut depth = 0;
 
         let mut index = 0;
-        let mut explode_str = None;
-        let mut explode_index_opt = None;
-
-        let mut left_num_opt = None;
-        let mut right_num_opt = None;
-
-        let mut left_num_index_opt = None;
-        let mut right_num_index_opt = None;
+        // the exploding pair, as its index, its text and its left and right numbers
+        let mut explosion = None;
+        // the first regular number right of the explosion, as its index, new value and old length
+        let mut right_opt = None;
 
-        let mut old_left_num_length_opt = None;
-        let mut old_right_num_length_opt = None;
-
-        while !chars.is_empty() {
-            let c = chars.pop_front().unwrap();
+        while let Some(c) = chars.pop_front() {
             index += 1;
 
             if c == '[' {
                 depth += 1;
-                if depth >= 5 && chars.front().unwrap() != &'[' && explode_str.is_none() {
+                if depth >= 5 && chars.front() != Some(&'[') && explosion.is_none() {
                     // check if we're in a regular number pair
                     let num_1 = read_number_from_chars(&chars, 0);
-                    let c2 = chars.get(num_1.1).unwrap();
                     let num_2 = read_number_from_chars(&chars, num_1.1 + 1);
 
-                    if num_1.1 > 0 && c2 == &',' && num_2.1 > 0 {
-                        explode_str = Some(format!("[{a},{b}]", a = num_1.0, b = num_2.0));
-                        explode_index_opt = Some(index - 1);
+                    if num_1.1 > 0 && chars.get(num_1.1) == Some(&',') && num_2.1 > 0 {
+                        let explode_str = format!("[{a},{b}]", a = num_1.0, b = num_2.0);
+                        explosion = Some((index - 1, explode_str, num_1.0, num_2.0));
 
                         for _ in 0..(num_1.1 + num_2.1 + 1) {
                             chars.pop_front();
                         }
                         index += num_1.1 + num_2.1 + 1;
-                        left_num_opt = Some(num_1.0);
-                        right_num_opt = Some(num_2.0);
                     }
                 }
             }
@@ -163,14 +146,13 @@ impl SnailNumber {
 
             if c.is_ascii_digit() {
                 // find right digit and index
-                if let Some(right_num) = right_num_opt {
+                if let Some((_, _, _, right_num)) = explosion {
                     chars.push_front(c);
                     let (num, right_num_length) = read_number_from_chars(&chars, 0);
 
                     if right_num_length > 0 {
-                        right_num_index_opt = Some(index - 1);
-                        right_num_opt = Some(right_num + num);
-                        old_right_num_length_opt = Some(right_num_length);
+                        right_opt =
+                            Some((index - 1, add_regular(right_num, num)?, right_num_length));
                         break;
                     }
                     chars.pop_front();
@@ -178,14 +160,11 @@ impl SnailNumber {
             }
         }
 
-        explode_index_opt?;
-
-        let mut left_num = left_num_opt.unwrap();
-        let right_num = right_num_opt.unwrap();
-
-        let explode_index = explode_index_opt.unwrap();
+        let Some((explode_index, explode_str, left_num, _)) = explosion else {
+            return Ok(None);
+        };
 
-        let mut last_left_num = 0;
+        let mut left_opt = None;
 
         let mut left_haystack = VecDeque::with_capacity(org_str.len());
         left_haystack.extend(org_str.chars());
@@ -202,103 +181,72 @@ impl SnailNumber {
             }
 
             if c.is_ascii_digit() {
-                left_num_index_opt = Some(index);
                 let (num, indexes) = read_number_from_chars(&left_haystack, index);
-                last_left_num = num;
-                old_left_num_length_opt = Some(indexes);
+                left_opt = Some((index, num, indexes));
                 skip_indexes = indexes;
             }
         }
 
-        left_num += last_left_num;
-
         let mut res = org_str;
 
         // and now to rebuild the whole thing
-        if let Some(right_num_index) = right_num_index_opt {
-            let (a, b) = res.split_at_mut(right_num_index + old_right_num_length_opt.unwrap());
-            let mut first = a.to_string();
-            for _ in 0..old_right_num_length_opt.unwrap() {
-                first.pop();
-            }
-            res = format!("{first}{right_num}{b}");
+        if let Some((right_num_index, right_num, old_length)) = right_opt {
+            let (a, b) = res.split_at(right_num_index + old_length);
+            res = format!("{first}{right_num}{b}", first = &a[..right_num_index]);
         }
 
         // and replace explosion
-        let (a, b) = res.split_at_mut(explode_index);
-        let replace = b.replacen(explode_str.unwrap().as_str(), "0", 1);
+        let (a, b) = res.split_at(explode_index);
+        let replace = b.replacen(explode_str.as_str(), "0", 1);
         res = format!("{a}{replace}");
 
-        if let Some(left_num_index) = left_num_index_opt {
-            let (a, b) = res.split_at_mut(left_num_index + old_left_num_length_opt.unwrap());
-            let mut first = a.to_string();
-            for _ in 0..old_left_num_length_opt.unwrap() {
-                first.pop();
-            }
-            res = format!("{first}{left_num}{b}");
+        if let Some((left_num_index, last_left_num, old_length)) = left_opt {
+            let left_num = add_regular(left_num, last_left_num)?;
+            let (a, b) = res.split_at(left_num_index + old_length);
+            res = format!("{first}{left_num}{b}", first = &a[..left_num_index]);
         }
 
-        Some(SnailNumber::from_str(res.as_str()))
+        SnailNumber::parse(res.as_str())
+            .map(Some)
+            .map_err(AocError::invalid_state)
     }
 
     fn split(&mut self) -> bool {
-        if let Some(a) = split_number(self.a.number) {
-            self.a = a;
-            return true;
-        }
-        if let Some(a_pair) = self.a.pair.as_mut() {
-            if a_pair.split() {
-                return true;
-            }
-        }
-
-        if let Some(b) = split_number(self.b.number) {
-            self.b = b;
-            return true;
-        }
-        if let Some(b_pair) = self.b.pair.as_mut() {
-            if b_pair.split() {
-                return true;
-            }
-        }
+        self.a.split() || self.b.split()
+    }
+}
 
-        false
+fn expect_char(chars: &mut VecDeque<char>, expected: char) -> Result<(), String> {
+    match chars.pop_front() {
+        Some(c) if c == expected => Ok(()),
+        Some(c) => Err(format!("expected '{expected}' but got '{c}'")),
+        None => Err(format!("expected '{expected}' but got the end of the line")),
     }
 }
 
-fn split_number(num_opt: Option<i32>) -> Option<SnailValue> {
-    if let Some(num) = num_opt {
-        if num >= 10 {
-            let new_a = num / 2;
-            let mut new_b = new_a;
+fn add_regular(a: i32, b: i32) -> AocResult<i32> {
+    a.checked_add(b)
+        .ok_or_else(|| AocError::invalid_state(format!("adding {a} and {b} overflows")))
+}
 
-            if num % 2 == 1 {
-                new_b += 1;
-            }
-            debug_assert_eq!(new_a + new_b, num);
-            debug_assert!(new_a <= new_b);
-            let number = SnailNumber {
-                a: SnailValue {
-                    number: Some(new_a),
-                    pair: None,
-                },
-                b: SnailValue {
-                    number: Some(new_b),
-                    pair: None,
-                },
-            };
-            return Some(SnailValue {
-                number: None,
-                pair: Some(Box::new(number)),
-            });
-        }
+fn split_number(num: i32) -> SnailValue {
+    let new_a = num / 2;
+    let mut new_b = new_a;
+
+    if num % 2 == 1 {
+        new_b += 1;
     }
-    None
+    debug_assert_eq!(new_a + new_b, num);
+    debug_assert!(new_a <= new_b);
+    SnailValue::Pair(Box::new(SnailNumber {
+        a: SnailValue::Number(new_a),
+        b: SnailValue::Number(new_b),
+    }))
 }
 
-fn reduce_number(mut snail_number: SnailNumber) -> SnailNumber {
+fn reduce_number(mut snail_number: SnailNumber) -> AocResult<SnailNumber> {
     loop {
-        if let Some(exploded_number) = snail_number.explode() {
+        if let Some(exploded_number) = snail_number.explode()? {
             snail_number = exploded_number;
             continue;
         }
@@ -308,13 +256,13 @@ fn reduce_number(mut snail_number: SnailNumber) -> SnailNumber {
         }
         break;
     }
-    snail_number
+    Ok(snail_number)
 }
 
 #[derive(Clone)]
-struct SnailValue {
-    number: Option<i32>,
-    pair: Option<Box<SnailNumber>>,
+enum SnailValue {
+    Number(i32),
+    Pair(Box<SnailNumber>),
 }
 
 fn read_number_from_chars(chars: &VecDeque<char>, skip: usize) -> (i32, usize) {
@@ -333,69 +281,71 @@ fn read_number_from_chars(chars: &VecDeque<char>, skip: usize) -> (i32, usize) {
 }
 
 impl SnailValue {
-    fn from_pair(pair: SnailNumber) -> Self {
-        let pair = Box::new(Some(Box::new(pair)));
-        SnailValue {
-            number: None,
-            pair: *pair,
-        }
-    }
-
-    fn from_chars(chars: &mut VecDeque<char>) -> Self {
-        let mut value = 0;
-        while let Some(c) = chars.pop_front() {
-            if c.is_ascii_digit() {
-                value = (value * 10) + c.to_string().parse::<i32>().unwrap();
-            } else {
-                chars.push_front(c);
-                break;
+    fn from_chars(chars: &mut VecDeque<char>) -> Result<Self, String> {
+        match chars.front() {
+            Some('[') => Ok(SnailValue::Pair(Box::new(SnailNumber::from_chars(chars)?))),
+            Some(c) if c.is_ascii_digit() => {
+                let mut value: i32 = 0;
+                while let Some(digit) = chars.front().and_then(|c| c.to_digit(10)) {
+                    chars.pop_front();
+                    value = value
+                        .checked_mul(10)
+                        .and_then(|v| v.checked_add(digit as i32))
+                        .ok_or_else(|| "regular number is too large".to_string())?;
+                }
+                Ok(SnailValue::Number(value))
             }
-        }
-        SnailValue {
-            number: Some(value),
-            pair: None,
+            Some(c) => Err(format!("expected '0'..'9' or '[' but got '{c}'")),
+            None => Err("unexpected end of input".to_string()),
         }
     }
 
     fn to_str(&self) -> String {
-        if let Some(value) = self.number {
-            return value.to_string();
+        match self {
+            SnailValue::Number(value) => value.to_string(),
+            SnailValue::Pair(pair) => pair.to_str(),
         }
-        if let Some(value) = &self.pair {
-            return value.to_str();
-        }
-        unreachable!()
     }
 
     fn magnitude(&self) -> i64 {
-        if let Some(num) = self.number {
-            return num as i64;
+        match self {
+            SnailValue::Number(num) => *num as i64,
+            SnailValue::Pair(pair) => pair.magnitude(),
         }
-        if let Some(pair) = &self.pair {
-            return pair.magnitude();
+    }
+
+    /**
+     * Splits the leftmost regular number of ten or more, returning whether one was found.
+     */
+    fn split(&mut self) -> bool {
+        match self {
+            SnailValue::Number(num) if *num >= 10 => {
+                *self = split_number(*num);
+                true
+            }
+            SnailValue::Number(_) => false,
+            SnailValue::Pair(pair) => pair.split(),
         }
-        unreachable!()
     }
 }
 
-fn load_snail_numbers(filename: &str) -> io::Result<Vec<SnailNumber>> {
+fn load_snail_numbers(input: &str) -> AocResult<Vec<SnailNumber>> {
     let mut snail_numbers = Vec::new();
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
-        let snail_number = SnailNumber::from_str(line.as_str());
+        let snail_number = SnailNumber::parse(line).on_line(i)?;
         snail_numbers.push(snail_number);
     }
     Ok(snail_numbers)
 }
 
-fn add(a: SnailNumber, b: SnailNumber) -> SnailNumber {
+fn add(a: SnailNumber, b: SnailNumber) -> AocResult<SnailNumber> {
     let result = SnailNumber {
-        a: SnailValue::from_pair(a),
-        b: SnailValue::from_pair(b),
+        a: SnailValue::Pair(Box::new(a)),
+        b: SnailValue::Pair(Box::new(b)),
     };
 
     reduce_number(result)
@@ -406,24 +356,25 @@ mod tests {
 
     use super::*;
 
+    fn snail(str: &str) -> SnailNumber {
+        SnailNumber::parse(str).unwrap()
+    }
+
     #[test]
     fn read_str_test() {
-        let a = SnailNumber::from_str("[1,2]");
-        assert_eq!(a.a.number, Some(1));
-        assert_eq!(a.b.number, Some(2));
-        assert!(a.a.pair.is_none());
-        assert!(a.b.pair.is_none());
-
-        let b = SnailNumber::from_str("[[1,2],3]");
-        assert_eq!(b.a.number, None);
-        assert_eq!(b.b.number, Some(3));
-        assert!(b.a.pair.is_some());
-        assert_eq!(b.a.pair.as_ref().unwrap().a.number, Some(1));
-        assert_eq!(b.a.pair.as_ref().unwrap().b.number, Some(2));
-        assert!(b.b.pair.is_none());
+        let a = snail("[1,2]");
+        assert!(matches!(a.a, SnailValue::Number(1)));
+        assert!(matches!(a.b, SnailValue::Number(2)));
+
+        let b = snail("[[1,2],3]");
+        assert!(matches!(b.b, SnailValue::Number(3)));
+        let SnailValue::Pair(pair) = &b.a else {
+            panic!("expected a pair");
+        };
+        assert!(matches!(pair.a, SnailValue::Number(1)));
+        assert!(matches!(pair.b, SnailValue::Number(2)));
 
-        let _c =
-            SnailNumber::from_str("[[[[1,3],[5,3]],[[1,3],[8,7]]],[[[4,9],[6,9]],[[8,2],[7,3]]]]");
+        let _c = snail("[[[[1,3],[5,3]],[[1,3],[8,7]]],[[[4,9],[6,9]],[[8,2],[7,3]]]]");
     }
 
     #[test]
@@ -436,7 +387,7 @@ mod tests {
         ];
 
         for number in numbers {
-            let snail_number = SnailNumber::from_str(number);
+            let snail_number = snail(number);
             let res_number = snail_number.to_str();
             assert_eq!(res_number, number);
         }
@@ -445,37 +396,42 @@ mod tests {
     #[test]
     fn explode_test() {
         assert_eq!(
-            SnailNumber::from_str("[[[[[9,8],1],2],3],4]")
+            snail("[[[[[9,8],1],2],3],4]")
                 .explode()
                 .unwrap()
+                .unwrap()
                 .to_str(),
             "[[[[0,9],2],3],4]"
         );
         assert_eq!(
-            SnailNumber::from_str("[7,[6,[5,[4,[3,2]]]]]")
+            snail("[7,[6,[5,[4,[3,2]]]]]")
                 .explode()
                 .unwrap()
+                .unwrap()
                 .to_str(),
             "[7,[6,[5,[7,0]]]]"
         );
         assert_eq!(
-            SnailNumber::from_str("[[6,[5,[4,[3,2]]]],1]")
+            snail("[[6,[5,[4,[3,2]]]],1]")
                 .explode()
                 .unwrap()
+                .unwrap()
                 .to_str(),
             "[[6,[5,[7,0]]],3]"
         );
         assert_eq!(
-            SnailNumber::from_str("[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]")
+            snail("[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]")
                 .explode()
                 .unwrap()
+                .unwrap()
                 .to_str(),
             "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]"
         );
         assert_eq!(
-            SnailNumber::from_str("[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]")
+            snail("[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]")
                 .explode()
                 .unwrap()
+                .unwrap()
                 .to_str(),
             "[[3,[2,[8,0]]],[9,[5,[7,0]]]]"
         );
@@ -484,16 +440,18 @@ mod tests {
     #[test]
     fn mixed_width_explode() {
         assert_eq!(
-            SnailNumber::from_str("[[[[[9,80],1],2],3],4]")
+            snail("[[[[[9,80],1],2],3],4]")
                 .explode()
                 .unwrap()
+                .unwrap()
                 .to_str(),
             "[[[[0,81],2],3],4]"
         );
         assert_eq!(
-            SnailNumber::from_str("[[3,[2,[8,0]]],[9,[5,[412,[3,2]]]]]")
+            snail("[[3,[2,[8,0]]],[9,[5,[412,[3,2]]]]]")
                 .explode()
                 .unwrap()
+                .unwrap()
                 .to_str(),
             "[[3,[2,[8,0]]],[9,[5,[415,0]]]]"
         );
@@ -501,7 +459,7 @@ mod tests {
 
     #[test]
     fn split_test() {
-        let mut a = SnailNumber::from_str("[[[[0,7],4],[15,[0,13]]],[1,1]]");
+        let mut a = snail("[[[[0,7],4],[15,[0,13]]],[1,1]]");
         let res = a.split();
         assert!(res);
         assert_eq!(a.to_str(), "[[[[0,7],4],[[7,8],[0,13]]],[1,1]]");
@@ -509,16 +467,15 @@ mod tests {
 
     #[test]
     fn reduce_test() {
-        let init = SnailNumber::from_str("[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]");
-        let reduce = reduce_number(init);
+        let init = snail("[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]");
+        let reduce = reduce_number(init).unwrap();
         assert_eq!(reduce.to_str(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
     }
 
     #[test]
     fn magnitude_test() {
         assert_eq!(
-            SnailNumber::from_str("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]")
-                .magnitude(),
+            snail("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]").magnitude(),
             3488
         );
     }
@@ -526,7 +483,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            add_numbers_from_file("./inputs/day-18-input-test.txt").unwrap(),
+            add_numbers_from_file(include_str!("../inputs/day-18-input-test.txt")).unwrap(),
             4140
         );
     }
@@ -534,7 +491,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            add_numbers_from_file("./inputs/day-18-input.txt").unwrap(),
+            add_numbers_from_file(include_str!("../inputs/day-18-input.txt")).unwrap(),
             4124
         );
     }
@@ -542,7 +499,8 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            find_largest_magnitude_from_file("./inputs/day-18-input-test.txt").unwrap(),
+            find_largest_magnitude_from_file(include_str!("../inputs/day-18-input-test.txt"))
+                .unwrap(),
             3993
         );
     }
@@ -550,8 +508,20 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            find_largest_magnitude_from_file("./inputs/day-18-input.txt").unwrap(),
+            find_largest_magnitude_from_file(include_str!("../inputs/day-18-input.txt")).unwrap(),
             4673
         );
     }
+
+    #[test]
+    fn invalid_test() {
+        let error = add_numbers_from_file("[1,2]\n[[1,2],3\n").unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: 2, .. }), "{error}");
+        let error = add_numbers_from_file("[1,x]\n").unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: 1, .. }), "{error}");
+        let error = add_numbers_from_file("[99999999999,1]\n").unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: 1, .. }), "{error}");
+        let error = add_numbers_from_file("[[[[1,[2147483647,1]],1],1],1]\n[1,1]\n").unwrap_err();
+        assert!(matches!(error, AocError::InvalidState(_)), "{error}");
+    }
 }
@@ -1,57 +1,63 @@
-use std::io::{self};
-
 use itertools::Itertools;
 
-use utils::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    solution::{Answer, Solution},
+};
 
-pub fn day_2() -> io::Result<i32> {
-    calc_position("./inputs/day-2-input.txt")
-}
-pub fn day_2_part_2() -> io::Result<i32> {
-    calc_position_with_aim("./inputs/day-2-input.txt")
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        calc_position(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        calc_position_with_aim(input).map(Answer::from)
+    }
 }
 
-fn calc_position(filename: &str) -> io::Result<i32> {
+fn calc_position(input: &str) -> AocResult<i32> {
     let mut x = 0;
     let mut y = 0;
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if let Some((direction, distance_str)) = line.split(' ').collect_tuple() {
-            if let Ok(distance) = distance_str.parse::<i32>() {
-                match direction {
-                    "forward" => x += distance,
-                    "down" => y += distance,
-                    "up" => y -= distance,
-                    x => panic!("Unknown direction '{x}'"),
-                };
-            } else {
-                panic!("Could not parse distance. {dist}", dist = distance_str);
-            }
+            let distance = distance_str.parse::<i32>().on_line(i)?;
+            match direction {
+                "forward" => x += distance,
+                "down" => y += distance,
+                "up" => y -= distance,
+                other => return Err(AocError::parse(i, format!("unknown direction '{other}'"))),
+            };
         }
     }
     Ok(x * y)
 }
 
-fn calc_position_with_aim(filename: &str) -> io::Result<i32> {
+fn calc_position_with_aim(input: &str) -> AocResult<i32> {
     let mut x = 0;
     let mut y = 0;
     let mut aim = 0;
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if let Some((direction, distance_str)) = line.split(' ').collect_tuple() {
-            if let Ok(distance) = distance_str.parse::<i32>() {
-                match direction {
-                    "forward" => {
-                        x += distance;
-                        y += aim * distance
-                    }
-                    "down" => aim += distance,
-                    "up" => aim -= distance,
-                    x => panic!("Unknown direction '{x}'"),
-                };
-            } else {
-                panic!("Could not parse distance. {dist}", dist = distance_str);
-            }
+            let distance = distance_str.parse::<i32>().on_line(i)?;
+            match direction {
+                "forward" => {
+                    x += distance;
+                    y += aim * distance
+                }
+                "down" => aim += distance,
+                "up" => aim -= distance,
+                other => return Err(AocError::parse(i, format!("unknown direction '{other}'"))),
+            };
         }
     }
     Ok(x * y)
@@ -64,18 +70,24 @@ mod tests {
 
     #[test]
     fn small_test() {
-        assert_eq!(calc_position("./inputs/day-2-input-test.txt").unwrap(), 150);
+        assert_eq!(
+            calc_position(include_str!("../inputs/day-2-input-test.txt")).unwrap(),
+            150
+        );
     }
 
     #[test]
     fn test() {
-        assert_eq!(calc_position("./inputs/day-2-input.txt").unwrap(), 2102357);
+        assert_eq!(
+            calc_position(include_str!("../inputs/day-2-input.txt")).unwrap(),
+            2102357
+        );
     }
 
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            calc_position_with_aim("./inputs/day-2-input-test.txt").unwrap(),
+            calc_position_with_aim(include_str!("../inputs/day-2-input-test.txt")).unwrap(),
             900
         );
     }
@@ -83,7 +95,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            calc_position_with_aim("./inputs/day-2-input.txt").unwrap(),
+            calc_position_with_aim(include_str!("../inputs/day-2-input.txt")).unwrap(),
             2101031224
         );
     }
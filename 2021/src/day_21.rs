@@ -1,13 +1,28 @@
-use std::{collections::HashMap, io};
+use utils::{
+    error::{AocError, AocResult},
+    memo::Memo,
+    parse::{field, signed_ints},
+    solution::{Answer, Solution},
+};
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    fn year(&self) -> u32 {
+        2021
+    }
 
+    fn day(&self) -> u32 {
+        21
+    }
 
-use utils::file_utils::read_lines;
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        eval_game_from_file(input).map(Answer::from)
+    }
 
-pub fn day_21() -> io::Result<i32> {
-    eval_game_from_file("./inputs/day-21-input.txt")
-}
-pub fn day_21_part_2() -> io::Result<u128> {
-    eval_dirac_game_from_file("./inputs/day-21-input.txt")
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        eval_dirac_game_from_file(input).map(Answer::from)
+    }
 }
 
 #[derive(Debug)]
@@ -59,12 +74,15 @@ impl GameState {
     }
 }
 
-fn eval_game_from_file(filename: &str) -> io::Result<i32> {
-    let players = load_players(filename)?;
-    Ok(eval_game(players))
+fn eval_game_from_file(input: &str) -> AocResult<i32> {
+    let players = load_players(input)?;
+    eval_game(players)
 }
 
-fn eval_game(mut players: Vec<Player>) -> i32 {
+fn eval_game(mut players: Vec<Player>) -> AocResult<i32> {
+    if players.is_empty() {
+        return Err(AocError::invalid_state("there are no players"));
+    }
     let mut dice = DeterministicDice::new();
 
     let player_count = players.len();
@@ -73,11 +91,11 @@ fn eval_game(mut players: Vec<Player>) -> i32 {
 
     let winning_score = 1000;
 
-    while players.iter().map(|p| p.score).max().unwrap() < winning_score {
+    while players.iter().all(|p| p.score < winning_score) {
         // roll three times
         let roll = dice.roll() + dice.roll() + dice.roll();
 
-        let player = players.get_mut(player_index).unwrap();
+        let player = &mut players[player_index];
         let new_pos = ((roll + (player.position - 1)) % 10) + 1;
         debug_assert!((1..=10).contains(&new_pos));
 
@@ -87,31 +105,35 @@ fn eval_game(mut players: Vec<Player>) -> i32 {
         player_index += 1;
         player_index %= player_count;
     }
-    players.iter().map(|p| p.score).min().unwrap() * dice.roll_count
+    let losing_score = players.iter().map(|p| p.score).min().unwrap_or_default();
+    Ok(losing_score * dice.roll_count)
 }
 
-fn load_players(filename: &str) -> io::Result<Vec<Player>> {
+fn load_players(input: &str) -> AocResult<Vec<Player>> {
     let mut players = Vec::new();
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
-        let (_, position) =
-            sscanf::sscanf!(line, "Player {} starting position: {}", i32, i32).unwrap();
+        let [_, position] = signed_ints(field(line, "Player ", i)?, i)?[..] else {
+            return Err(AocError::parse(
+                i,
+                "expected 'Player n starting position: p'",
+            ));
+        };
         players.push(Player { position, score: 0 });
     }
     Ok(players)
 }
 
-fn eval_dirac_game_from_file(filename: &str) -> io::Result<u128> {
-    let players = load_players(filename)?;
+fn eval_dirac_game_from_file(input: &str) -> AocResult<u128> {
+    let players = load_players(input)?;
     if players.len() != 2 {
-        panic!(
-            "Irregular number of players: {}. Expected 2.",
+        return Err(AocError::invalid_state(format!(
+            "expected 2 players, found {}",
             players.len()
-        );
+        )));
     }
 
     let player_1_position = players.first().unwrap().position;
@@ -120,80 +142,47 @@ fn eval_dirac_game_from_file(filename: &str) -> io::Result<u128> {
     Ok(eval_dirac_game(player_1_position, player_2_position))
 }
 
+/**
+ * The totals of three rolls of the Dirac dice, with the number of universes that roll them.
+ */
+const DIRAC_ROLLS: [(i32, u128); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
 fn eval_dirac_game(player_1_position: i32, player_2_position: i32) -> u128 {
-    let mut combos: HashMap<i32, u128> = HashMap::new();
-    // pre calc all the combos of 3 dice
-    for a in 1..=3 {
-        for b in 1..=3 {
-            for c in 1..=3 {
-                let total = a + b + c;
-                combos.entry(total).and_modify(|e| *e += 1).or_insert(1);
-            }
-        }
-    }
+    let state = GameState::from_positions(player_1_position, player_2_position);
+    let (p_1_wins, p_2_wins) = count_wins(&mut Memo::named("count_wins"), state);
+    p_1_wins.max(p_2_wins)
+}
 
-    let mut game_states = HashMap::new();
-    game_states.insert(
-        GameState::from_positions(player_1_position, player_2_position),
-        1_u128,
-    );
-
-    let mut p_1_wins = 0_u128;
-    let mut p_2_wins = 0_u128;
-
-    while !game_states.is_empty() {
-        // process player 1
-        let mut new_game_states = HashMap::new();
-        for (state, count) in &game_states {
-            for (roll, roll_count) in &combos {
-                let mut new_state = *state;
-
-                let new_pos = ((roll + (state.player_1.position - 1)) % 10) + 1;
-                debug_assert!((1..=10).contains(&new_pos));
-
-                new_state.player_1.position = new_pos;
-                new_state.player_1.score += new_pos;
-                let instances: u128 = count * roll_count;
-
-                if new_state.player_1.score >= 21 {
-                    p_1_wins += instances;
-                } else {
-                    new_game_states
-                        .entry(new_state)
-                        .and_modify(|e| *e += instances)
-                        .or_insert(instances);
-                }
+/**
+ * Counts the universes that player 1 and player 2 win in, with player 1 about to move.
+ */
+fn count_wins(memo: &mut Memo<GameState, (u128, u128)>, state: GameState) -> (u128, u128) {
+    memo.cached(state, |memo| {
+        let mut p_1_wins = 0_u128;
+        let mut p_2_wins = 0_u128;
+        for (roll, roll_count) in DIRAC_ROLLS {
+            let new_pos = ((roll + (state.player_1.position - 1)) % 10) + 1;
+            debug_assert!((1..=10).contains(&new_pos));
+
+            let player = Player {
+                position: new_pos,
+                score: state.player_1.score + new_pos,
+            };
+            if player.score >= 21 {
+                p_1_wins += roll_count;
+            } else {
+                // Player 2 moves next, so the players swap places.
+                let next = GameState {
+                    player_1: state.player_2,
+                    player_2: player,
+                };
+                let (next_wins, player_wins) = count_wins(memo, next);
+                p_1_wins += player_wins * roll_count;
+                p_2_wins += next_wins * roll_count;
             }
         }
-        game_states = new_game_states;
-
-        // process player 2
-        let mut new_game_states = HashMap::new();
-        for (state, count) in &game_states {
-            for (roll, roll_count) in &combos {
-                let mut new_state = *state;
-
-                let new_pos = ((roll + (state.player_2.position - 1)) % 10) + 1;
-                debug_assert!((1..=10).contains(&new_pos));
-
-                new_state.player_2.position = new_pos;
-                new_state.player_2.score += new_pos;
-                let instances: u128 = count * roll_count;
-
-                if new_state.player_2.score >= 21 {
-                    p_2_wins += instances;
-                } else {
-                    new_game_states
-                        .entry(new_state)
-                        .and_modify(|e| *e += instances)
-                        .or_insert(instances);
-                }
-            }
-        }
-        game_states = new_game_states;
-    }
-
-    p_1_wins.max(p_2_wins)
+        (p_1_wins, p_2_wins)
+    })
 }
 
 #[cfg(test)]
@@ -204,7 +193,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            eval_game_from_file("./inputs/day-21-input-test.txt").unwrap(),
+            eval_game_from_file(include_str!("../inputs/day-21-input-test.txt")).unwrap(),
             739785
         );
     }
@@ -212,7 +201,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            eval_game_from_file("./inputs/day-21-input.txt").unwrap(),
+            eval_game_from_file(include_str!("../inputs/day-21-input.txt")).unwrap(),
             571032
         );
     }
@@ -220,7 +209,7 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            eval_dirac_game_from_file("./inputs/day-21-input-test.txt").unwrap(),
+            eval_dirac_game_from_file(include_str!("../inputs/day-21-input-test.txt")).unwrap(),
             444356092776315_u128
         );
     }
@@ -228,7 +217,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            eval_dirac_game_from_file("./inputs/day-21-input.txt").unwrap(),
+            eval_dirac_game_from_file(include_str!("../inputs/day-21-input.txt")).unwrap(),
             49975322685009_u128
         );
     }
@@ -1,20 +1,33 @@
-use std::io::{self};
+use utils::{
+    error::{AocError, AocResult},
+    solution::{Answer, Solution},
+};
 
-use utils::file_utils::read_lines;
+pub struct Day3;
 
-pub fn day_3() -> io::Result<i32> {
-    calc_power_consumption("./inputs/day-3-input.txt")
-}
-pub fn day_3_part_2() -> io::Result<i32> {
-    calc_gas("./inputs/day-3-input.txt")
+impl Solution for Day3 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        calc_power_consumption(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        calc_gas(input).map(Answer::from)
+    }
 }
 
-fn calc_power_consumption(filename: &str) -> io::Result<i32> {
+fn calc_power_consumption(input: &str) -> AocResult<i32> {
     let mut line_length: u32 = 0;
 
     let mut values = Vec::new();
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
@@ -26,6 +39,12 @@ fn calc_power_consumption(filename: &str) -> io::Result<i32> {
                 let exp = ((line.len() - 1) - a).try_into().unwrap();
                 debug_assert!(exp < line.len().try_into().unwrap());
                 value += 2_i32.pow(exp);
+            } else if b != '0' {
+                return Err(AocError::parse_at(
+                    i,
+                    a,
+                    format!("expected a bit, found '{b}'"),
+                ));
             }
         }
         values.push(value);
@@ -56,12 +75,11 @@ fn calc_power_consumption(filename: &str) -> io::Result<i32> {
     Ok(gamma * epsilon)
 }
 
-fn calc_gas(filename: &str) -> io::Result<i32> {
+fn calc_gas(input: &str) -> AocResult<i32> {
     let mut line_length: u32 = 0;
 
     let mut values = Vec::new();
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
@@ -73,6 +91,12 @@ fn calc_gas(filename: &str) -> io::Result<i32> {
                 let exp = ((line.len() - 1) - a).try_into().unwrap();
                 debug_assert!(exp < line.len().try_into().unwrap());
                 value += 2_i32.pow(exp);
+            } else if b != '0' {
+                return Err(AocError::parse_at(
+                    i,
+                    a,
+                    format!("expected a bit, found '{b}'"),
+                ));
             }
         }
         values.push(value);
@@ -134,10 +158,12 @@ fn calc_gas(filename: &str) -> io::Result<i32> {
         }
     }
 
-    debug_assert_eq!(oxygen_filter.len(), 1);
-    debug_assert_eq!(co2_filter.len(), 1);
-
-    Ok(oxygen_filter[0] * co2_filter[0])
+    match (&oxygen_filter[..], &co2_filter[..]) {
+        ([oxygen], [co2]) => Ok(oxygen * co2),
+        _ => Err(AocError::invalid_state(
+            "expected a single oxygen and CO2 rating to be left",
+        )),
+    }
 }
 
 #[cfg(test)]
@@ -148,7 +174,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            calc_power_consumption("./inputs/day-3-input-test.txt").unwrap(),
+            calc_power_consumption(include_str!("../inputs/day-3-input-test.txt")).unwrap(),
             198
         );
     }
@@ -156,18 +182,24 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            calc_power_consumption("./inputs/day-3-input.txt").unwrap(),
+            calc_power_consumption(include_str!("../inputs/day-3-input.txt")).unwrap(),
             3882564
         );
     }
 
     #[test]
     fn part_2_small_test() {
-        assert_eq!(calc_gas("./inputs/day-3-input-test.txt").unwrap(), 230);
+        assert_eq!(
+            calc_gas(include_str!("../inputs/day-3-input-test.txt")).unwrap(),
+            230
+        );
     }
 
     #[test]
     fn part_2_test() {
-        assert_eq!(calc_gas("./inputs/day-3-input.txt").unwrap(), 3385170);
+        assert_eq!(
+            calc_gas(include_str!("../inputs/day-3-input.txt")).unwrap(),
+            3385170
+        );
     }
 }
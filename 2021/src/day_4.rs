@@ -1,14 +1,28 @@
-use std::io::{self};
-
 use itertools::Itertools;
 
-use utils::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    solution::{Answer, Solution},
+};
 
-pub fn day_4() -> io::Result<i32> {
-    determine_winning_board("./inputs/day-4-input.txt")
-}
-pub fn day_4_part_2() -> io::Result<i32> {
-    determine_losing_board("./inputs/day-4-input.txt")
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        determine_winning_board(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        determine_losing_board(input).map(Answer::from)
+    }
 }
 
 struct BoardLine {
@@ -19,13 +33,38 @@ struct BoardLine {
 
 const BOARD_SIZE: usize = 5;
 
-fn determine_winning_board(filename: &str) -> io::Result<i32> {
+/**
+ * Reads one row of a bingo board, checking it holds exactly `BOARD_SIZE` numbers.
+ */
+fn parse_board_row(line: &str, i: usize, board_id: i32) -> AocResult<BoardLine> {
+    let values: Vec<i32> = line
+        .split_whitespace()
+        .map(|v| v.parse::<i32>())
+        .collect::<Result<_, _>>()
+        .on_line(i)?;
+    if values.len() != BOARD_SIZE {
+        return Err(AocError::parse(
+            i,
+            format!(
+                "expected {BOARD_SIZE} numbers in a board row, found {}",
+                values.len()
+            ),
+        ));
+    }
+
+    Ok(BoardLine {
+        line: values,
+        board_id,
+        row: true,
+    })
+}
+
+fn determine_winning_board(input: &str) -> AocResult<i32> {
     let mut calls = Vec::new();
     let mut board_count = 0;
     let mut board_lines: Vec<BoardLine> = Vec::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             let mut columns = Vec::new();
             let boards: Vec<&BoardLine> = board_lines
@@ -49,20 +88,13 @@ fn determine_winning_board(filename: &str) -> io::Result<i32> {
         }
 
         if board_count == 0 {
-            calls.extend(line.split(',').map(|v| v.parse::<i32>().unwrap()));
+            for call in line.split(',') {
+                calls.push(call.parse::<i32>().on_line(i)?);
+            }
             continue;
         }
 
-        let board_line = BoardLine {
-            line: line
-                .split(' ')
-                .filter(|v| !v.is_empty())
-                .map(|v| v.trim().parse::<i32>().unwrap())
-                .collect(),
-            board_id: board_count,
-            row: true,
-        };
-        board_lines.push(board_line);
+        board_lines.push(parse_board_row(line, i, board_count)?);
     }
 
     let mut winning_board = None;
@@ -85,22 +117,20 @@ fn determine_winning_board(filename: &str) -> io::Result<i32> {
             .iter()
             .filter(|l| l.board_id == winning_board_id && l.row)
             .map(|l| l.line.iter().sum::<i32>())
-            .reduce(|acc, e| acc + e)
-            .unwrap();
+            .sum::<i32>();
 
         return Ok(call * board_sum);
     }
 
-    panic!("Failed to find winning board.");
+    Err(AocError::invalid_state("no board wins"))
 }
 
-fn determine_losing_board(filename: &str) -> io::Result<i32> {
+fn determine_losing_board(input: &str) -> AocResult<i32> {
     let mut calls = Vec::new();
     let mut board_count = 0;
     let mut board_lines: Vec<BoardLine> = Vec::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             let mut columns = Vec::new();
             let boards: Vec<&BoardLine> = board_lines
@@ -124,20 +154,13 @@ fn determine_losing_board(filename: &str) -> io::Result<i32> {
         }
 
         if board_count == 0 {
-            calls.extend(line.split(',').map(|v| v.parse::<i32>().unwrap()));
+            for call in line.split(',') {
+                calls.push(call.parse::<i32>().on_line(i)?);
+            }
             continue;
         }
 
-        let board_line = BoardLine {
-            line: line
-                .split(' ')
-                .filter(|v| !v.is_empty())
-                .map(|v| v.trim().parse::<i32>().unwrap())
-                .collect(),
-            board_id: board_count,
-            row: true,
-        };
-        board_lines.push(board_line);
+        board_lines.push(parse_board_row(line, i, board_count)?);
     }
 
     let mut board_ids = board_lines
@@ -172,13 +195,12 @@ fn determine_losing_board(filename: &str) -> io::Result<i32> {
             .iter()
             .filter(|l| l.board_id == losing_board_id && l.row)
             .map(|l| l.line.iter().sum::<i32>())
-            .reduce(|acc, e| acc + e)
-            .unwrap();
+            .sum::<i32>();
 
         return Ok(call * board_sum);
     }
 
-    panic!("Failed to find losing board.");
+    Err(AocError::invalid_state("no single board is left to lose"))
 }
 
 #[cfg(test)]
@@ -189,7 +211,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            determine_winning_board("./inputs/day-4-input-test.txt").unwrap(),
+            determine_winning_board(include_str!("../inputs/day-4-input-test.txt")).unwrap(),
             4512
         );
     }
@@ -197,7 +219,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            determine_winning_board("./inputs/day-4-input.txt").unwrap(),
+            determine_winning_board(include_str!("../inputs/day-4-input.txt")).unwrap(),
             31424
         );
     }
@@ -205,7 +227,7 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            determine_losing_board("./inputs/day-4-input-test.txt").unwrap(),
+            determine_losing_board(include_str!("../inputs/day-4-input-test.txt")).unwrap(),
             1924
         );
     }
@@ -213,8 +235,21 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            determine_losing_board("./inputs/day-4-input.txt").unwrap(),
+            determine_losing_board(include_str!("../inputs/day-4-input.txt")).unwrap(),
             23042
         );
     }
+
+    #[test]
+    fn short_row_test() {
+        let input = include_str!("../inputs/day-4-input-test.txt").replacen(
+            "22 13 17 11  0",
+            "22 13 17 11",
+            1,
+        );
+        let error = determine_winning_board(&input).unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: 3, .. }), "{error}");
+        let error = determine_losing_board(&input).unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: 3, .. }), "{error}");
+    }
 }
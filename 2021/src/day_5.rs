@@ -1,16 +1,28 @@
-use std::{
-    cmp::Ordering,
-    collections::HashMap,
-    io::{self},
+use std::{cmp::Ordering, collections::HashMap};
+
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    solution::{Answer, Solution},
 };
 
-use utils::file_utils::read_lines;
+pub struct Day5;
 
-pub fn day_5() -> io::Result<i32> {
-    find_orthogonal_overlaps("./inputs/day-5-input.txt")
-}
-pub fn day_5_part_2() -> io::Result<i32> {
-    find_overlaps("./inputs/day-5-input.txt")
+impl Solution for Day5 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        find_orthogonal_overlaps(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        find_overlaps(input).map(Answer::from)
+    }
 }
 
 struct Scan {
@@ -28,38 +40,63 @@ impl Scan {
     fn is_vertical(&self) -> bool {
         self.x1 == self.x2
     }
+
+    fn is_diagonal(&self) -> bool {
+        (self.x1 - self.x2).abs() == (self.y1 - self.y2).abs()
+    }
+}
+
+/**
+ * Parses a point given as `x,y`.
+ */
+fn parse_point(point: &str, i: usize) -> AocResult<(i32, i32)> {
+    let (x, y) = point
+        .split_once(',')
+        .ok_or_else(|| AocError::parse(i, format!("expected a point 'x,y', found '{point}'")))?;
+    Ok((x.parse().on_line(i)?, y.parse().on_line(i)?))
 }
 
-fn load_scans(filename: &str) -> io::Result<Vec<Scan>> {
+fn load_scans(input: &str) -> AocResult<Vec<Scan>> {
     let mut scans: Vec<Scan> = Vec::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
-        let parsed = sscanf::sscanf!(line, "{},{} -> {},{}", i32, i32, i32, i32);
-
-        if let Ok((x1, y1, x2, y2)) = parsed {
-            let scan = Scan { x1, y1, x2, y2 };
-            scans.push(scan);
-        } else {
-            panic!("Unparseable input: {line}");
-        }
+        let (start, end) = line
+            .split_once(" -> ")
+            .ok_or_else(|| AocError::parse(i, "expected a line like 'x1,y1 -> x2,y2'"))?;
+        let (x1, y1) = parse_point(start, i)?;
+        let (x2, y2) = parse_point(end, i)?;
+        scans.push(Scan { x1, y1, x2, y2 });
     }
     Ok(scans)
 }
 
-fn determine_overlaps(orthogonal_only: bool, scans: &Vec<Scan>) -> i32 {
+/**
+ * Counts the points where lines overlap. Lines must be horizontal, vertical or at 45 degrees.
+ */
+fn determine_overlaps(orthogonal_only: bool, scans: &Vec<Scan>) -> AocResult<i32> {
     let mut map: HashMap<(i32, i32), i32> = HashMap::new();
 
     for scan in scans {
         let start_coord = (scan.x1, scan.y1);
         let end_coord = (scan.x2, scan.y2);
 
-        if orthogonal_only && (!scan.is_horizontal() && !scan.is_vertical()) {
-            continue;
+        if !scan.is_horizontal() && !scan.is_vertical() {
+            if orthogonal_only {
+                continue;
+            }
+            if !scan.is_diagonal() {
+                return Err(AocError::invalid_state(format!(
+                    "the line {x1},{y1} -> {x2},{y2} is not horizontal, vertical or at 45 degrees",
+                    x1 = scan.x1,
+                    y1 = scan.y1,
+                    x2 = scan.x2,
+                    y2 = scan.y2
+                )));
+            }
         }
 
         let offset_x = match scan.x1.cmp(&scan.x2) {
@@ -101,17 +138,17 @@ fn determine_overlaps(orthogonal_only: bool, scans: &Vec<Scan>) -> i32 {
         }
     }
 
-    map.iter().filter(|(_, v)| *v >= &2).count() as i32
+    Ok(map.iter().filter(|(_, v)| *v >= &2).count() as i32)
 }
 
-fn find_orthogonal_overlaps(filename: &str) -> io::Result<i32> {
-    let scans = load_scans(filename)?;
-    Ok(determine_overlaps(true, &scans))
+fn find_orthogonal_overlaps(input: &str) -> AocResult<i32> {
+    let scans = load_scans(input)?;
+    determine_overlaps(true, &scans)
 }
 
-fn find_overlaps(filename: &str) -> io::Result<i32> {
-    let scans = load_scans(filename)?;
-    Ok(determine_overlaps(false, &scans))
+fn find_overlaps(input: &str) -> AocResult<i32> {
+    let scans = load_scans(input)?;
+    determine_overlaps(false, &scans)
 }
 
 #[cfg(test)]
@@ -122,7 +159,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            find_orthogonal_overlaps("./inputs/day-5-input-test.txt").unwrap(),
+            find_orthogonal_overlaps(include_str!("../inputs/day-5-input-test.txt")).unwrap(),
             5
         );
     }
@@ -130,18 +167,39 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            find_orthogonal_overlaps("./inputs/day-5-input.txt").unwrap(),
+            find_orthogonal_overlaps(include_str!("../inputs/day-5-input.txt")).unwrap(),
             6311
         );
     }
 
     #[test]
     fn part_2_small_test() {
-        assert_eq!(find_overlaps("./inputs/day-5-input-test.txt").unwrap(), 12);
+        assert_eq!(
+            find_overlaps(include_str!("../inputs/day-5-input-test.txt")).unwrap(),
+            12
+        );
     }
 
     #[test]
     fn part_2_test() {
-        assert_eq!(find_overlaps("./inputs/day-5-input.txt").unwrap(), 19929);
+        assert_eq!(
+            find_overlaps(include_str!("../inputs/day-5-input.txt")).unwrap(),
+            19929
+        );
+    }
+
+    #[test]
+    fn invalid_test() {
+        assert!(matches!(
+            load_scans("0,9 -> 5,9\n95x,596 -> 957,182\n"),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+        assert!(load_scans("0,9 -> 5,9,1\n").is_err());
+        assert!(load_scans("0,9 - 5,9\n").is_err());
+        assert!(matches!(
+            find_overlaps("0,0 -> 2,5\n"),
+            Err(AocError::InvalidState(_))
+        ));
+        assert_eq!(find_orthogonal_overlaps("0,0 -> 2,5\n").unwrap(), 0);
     }
 }
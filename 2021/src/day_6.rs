@@ -1,20 +1,33 @@
-use std::{
-    collections::HashMap,
-    io::{self},
-};
+use std::collections::HashMap;
 
 use itertools::Itertools;
 
-use utils::file_utils::read_lines;
+use utils::{
+    error::{AocResult, LineContext},
+    solution::{Answer, Solution},
+};
 
 const RESET_TIMER: i32 = 6;
 const NEW_TIMER: i32 = 8;
 
-pub fn day_6() -> io::Result<i64> {
-    process_generations(80, "./inputs/day-6-input.txt")
-}
-pub fn day_6_part_2() -> io::Result<i64> {
-    process_generations(256, "./inputs/day-6-input.txt")
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        6
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        process_generations(80, input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        process_generations(256, input).map(Answer::from)
+    }
 }
 
 struct Generation {
@@ -22,17 +35,16 @@ struct Generation {
     amount: i64,
 }
 
-fn load_generation(filename: &str) -> io::Result<Vec<Generation>> {
+fn load_generation(input: &str) -> AocResult<Vec<Generation>> {
     let mut generations: HashMap<i32, Generation> = HashMap::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
         for split in line.split(',') {
-            let timer = split.parse::<i32>().unwrap();
+            let timer = split.parse::<i32>().on_line(i)?;
 
             if let Some(generation) = generations.get_mut(&timer) {
                 generation.amount += 1;
@@ -50,7 +62,6 @@ fn process_generation(generations: Vec<Generation>) -> Vec<Generation> {
     for mut gen in generations {
         if gen.timer == 0 {
             new_fish += gen.amount;
-            gen.timer = RESET_TIMER;
         } else {
             gen.timer -= 1;
             new_gens.push(gen);
@@ -77,8 +88,8 @@ fn process_generation(generations: Vec<Generation>) -> Vec<Generation> {
     new_gens
 }
 
-fn process_generations(days: i32, filename: &str) -> io::Result<i64> {
-    let mut gen = load_generation(filename)?;
+fn process_generations(days: i32, input: &str) -> AocResult<i64> {
+    let mut gen = load_generation(input)?;
     for _ in 0..days {
         gen = process_generation(gen);
     }
@@ -93,7 +104,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            process_generations(80, "./inputs/day-6-input-test.txt").unwrap(),
+            process_generations(80, include_str!("../inputs/day-6-input-test.txt")).unwrap(),
             5934
         );
     }
@@ -101,7 +112,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            process_generations(80, "./inputs/day-6-input.txt").unwrap(),
+            process_generations(80, include_str!("../inputs/day-6-input.txt")).unwrap(),
             362639
         );
     }
@@ -109,7 +120,7 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            process_generations(256, "./inputs/day-6-input-test.txt").unwrap(),
+            process_generations(256, include_str!("../inputs/day-6-input-test.txt")).unwrap(),
             26_984_457_539
         );
     }
@@ -117,7 +128,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            process_generations(256, "./inputs/day-6-input.txt").unwrap(),
+            process_generations(256, include_str!("../inputs/day-6-input.txt")).unwrap(),
             1_639_854_996_917
         );
     }
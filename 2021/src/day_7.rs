@@ -1,36 +1,64 @@
-use std::io::{self};
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    solution::{Answer, Solution},
+};
 
-use utils::file_utils::read_lines;
+pub struct Day7;
 
-pub fn day_7() -> io::Result<i32> {
-    find_low_fuel_position("./inputs/day-7-input.txt")
-}
-pub fn day_7_part_2() -> io::Result<i32> {
-    find_low_triangle_fuel_position("./inputs/day-7-input.txt")
+impl Solution for Day7 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        find_low_fuel_position(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        find_low_triangle_fuel_position(input).map(Answer::from)
+    }
 }
 
-fn load_positions(filename: &str) -> io::Result<Vec<i32>> {
+fn load_positions(input: &str) -> AocResult<Vec<i32>> {
     let mut positions = Vec::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
         for split in line.split(',') {
-            let position = split.parse::<i32>().unwrap();
+            let position = split.parse::<i32>().on_line(i)?;
             positions.push(position);
         }
     }
+    if positions.is_empty() {
+        return Err(AocError::parse(0, "no crab positions"));
+    }
     Ok(positions)
 }
 
-fn calc_fuel(positions: Vec<i32>) -> Vec<i32> {
-    let max = positions.iter().max().unwrap().to_owned();
+/**
+ * Finds the lowest and highest crab positions, which bound where they could line up.
+ */
+fn crab_range(positions: &[i32]) -> AocResult<(i32, i32)> {
+    positions
+        .iter()
+        .min()
+        .zip(positions.iter().max())
+        .map(|(min, max)| (*min, *max))
+        .ok_or_else(|| AocError::invalid_state("there are no crabs"))
+}
+
+fn calc_fuel(positions: Vec<i32>) -> AocResult<Vec<i32>> {
+    let (min, max) = crab_range(&positions)?;
 
     let mut fuels = Vec::new();
-    for pos in 0..=max {
+    for pos in min..=max {
         let mut fuel = 0;
         for sub in &positions {
             let diff = (pos - sub).abs();
@@ -39,43 +67,51 @@ fn calc_fuel(positions: Vec<i32>) -> Vec<i32> {
         fuels.push(fuel);
     }
 
-    fuels
+    Ok(fuels)
 }
 
-fn calc_triangle_fuel(positions: Vec<i32>) -> Vec<i32> {
-    let max = positions.iter().max().unwrap().to_owned();
+fn calc_triangle_fuel(positions: Vec<i32>) -> AocResult<Vec<i32>> {
+    let (min, max) = crab_range(&positions)?;
 
     let mut triangle_nums = Vec::new();
     let mut prev = 0;
     // pre calc triangle numbers
-    for i in 0..=max {
+    for i in 0..=(max - min) {
         prev += i;
         triangle_nums.push(prev);
     }
 
     let mut fuels = Vec::new();
-    for pos in 0..=max {
+    for pos in min..=max {
         let mut fuel = 0;
         for sub in &positions {
             let diff = (pos - sub).unsigned_abs() as usize;
-            fuel += triangle_nums.get(diff).unwrap();
+            fuel += triangle_nums[diff];
         }
         fuels.push(fuel);
     }
 
-    fuels
+    Ok(fuels)
 }
 
-fn find_low_fuel_position(filename: &str) -> io::Result<i32> {
-    let position = load_positions(filename)?;
-    let fuels = calc_fuel(position);
-    Ok(fuels.iter().min().unwrap().to_owned())
+fn find_low_fuel_position(input: &str) -> AocResult<i32> {
+    let position = load_positions(input)?;
+    let fuels = calc_fuel(position)?;
+    lowest_fuel(&fuels)
 }
 
-fn find_low_triangle_fuel_position(filename: &str) -> io::Result<i32> {
-    let position = load_positions(filename)?;
-    let fuels = calc_triangle_fuel(position);
-    Ok(fuels.iter().min().unwrap().to_owned())
+fn find_low_triangle_fuel_position(input: &str) -> AocResult<i32> {
+    let position = load_positions(input)?;
+    let fuels = calc_triangle_fuel(position)?;
+    lowest_fuel(&fuels)
+}
+
+fn lowest_fuel(fuels: &[i32]) -> AocResult<i32> {
+    fuels
+        .iter()
+        .min()
+        .copied()
+        .ok_or_else(|| AocError::invalid_state("there are no crabs"))
 }
 
 #[cfg(test)]
@@ -86,7 +122,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            find_low_fuel_position("./inputs/day-7-input-test.txt").unwrap(),
+            find_low_fuel_position(include_str!("../inputs/day-7-input-test.txt")).unwrap(),
             37
         );
     }
@@ -94,7 +130,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            find_low_fuel_position("./inputs/day-7-input.txt").unwrap(),
+            find_low_fuel_position(include_str!("../inputs/day-7-input.txt")).unwrap(),
             352707
         );
     }
@@ -102,7 +138,8 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            find_low_triangle_fuel_position("./inputs/day-7-input-test.txt").unwrap(),
+            find_low_triangle_fuel_position(include_str!("../inputs/day-7-input-test.txt"))
+                .unwrap(),
             168
         );
     }
@@ -110,8 +147,15 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            find_low_triangle_fuel_position("./inputs/day-7-input.txt").unwrap(),
+            find_low_triangle_fuel_position(include_str!("../inputs/day-7-input.txt")).unwrap(),
             95519693
         );
     }
+
+    #[test]
+    fn negative_test() {
+        assert_eq!(find_low_fuel_position("-3,-1,-5\n").unwrap(), 4);
+        assert_eq!(find_low_triangle_fuel_position("-3,-1,-5\n").unwrap(), 6);
+        assert!(find_low_fuel_position("\n").is_err());
+    }
 }
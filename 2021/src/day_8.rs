@@ -1,11 +1,11 @@
-use std::{
-    collections::{HashMap, HashSet},
-    io::{self},
-};
+use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
 
-use utils::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult},
+    solution::{Answer, Solution},
+};
 
 /**
  *  aaaa
@@ -27,7 +27,7 @@ struct SegmentDisplay {
 }
 
 impl SegmentDisplay {
-    fn calc_number(&self, digits: &str) -> i32 {
+    fn calc_number(&self, digits: &str) -> AocResult<i32> {
         let chars: String = digits.chars().sorted().collect();
 
         // 0
@@ -39,12 +39,12 @@ impl SegmentDisplay {
             && chars.contains(self.f)
             && chars.contains(self.g)
         {
-            return 0;
+            return Ok(0);
         }
 
         // 1
         if chars.len() == 2 && chars.contains(self.c) && chars.contains(self.f) {
-            return 1;
+            return Ok(1);
         }
 
         // 2
@@ -55,7 +55,7 @@ impl SegmentDisplay {
             && chars.contains(self.e)
             && chars.contains(self.g)
         {
-            return 2;
+            return Ok(2);
         }
 
         // 3
@@ -66,7 +66,7 @@ impl SegmentDisplay {
             && chars.contains(self.f)
             && chars.contains(self.g)
         {
-            return 3;
+            return Ok(3);
         }
 
         // 4
@@ -76,7 +76,7 @@ impl SegmentDisplay {
             && chars.contains(self.d)
             && chars.contains(self.f)
         {
-            return 4;
+            return Ok(4);
         }
 
         // 5
@@ -87,7 +87,7 @@ impl SegmentDisplay {
             && chars.contains(self.f)
             && chars.contains(self.g)
         {
-            return 5;
+            return Ok(5);
         }
 
         // 6
@@ -99,7 +99,7 @@ impl SegmentDisplay {
             && chars.contains(self.f)
             && chars.contains(self.g)
         {
-            return 6;
+            return Ok(6);
         }
 
         // 7
@@ -108,7 +108,7 @@ impl SegmentDisplay {
             && chars.contains(self.c)
             && chars.contains(self.f)
         {
-            return 7;
+            return Ok(7);
         }
 
         // 8
@@ -121,7 +121,7 @@ impl SegmentDisplay {
             && chars.contains(self.f)
             && chars.contains(self.g)
         {
-            return 8;
+            return Ok(8);
         }
 
         // 9
@@ -133,10 +133,12 @@ impl SegmentDisplay {
             && chars.contains(self.f)
             && chars.contains(self.g)
         {
-            return 9;
+            return Ok(9);
         }
 
-        panic!("Invalid combination: '{chars}'")
+        Err(AocError::invalid_state(format!(
+            "invalid combination: '{chars}'"
+        )))
     }
 }
 
@@ -145,23 +147,38 @@ struct DisplayEntry {
     output_values: Vec<String>,
 }
 
-pub fn day_8() -> io::Result<i32> {
-    load_find_unique_numbers("./inputs/day-8-input.txt")
-}
-pub fn day_8_part_2() -> io::Result<i32> {
-    solve_displays("./inputs/day-8-input.txt")
+pub struct Day8;
+
+impl Solution for Day8 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        8
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        load_find_unique_numbers(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        solve_displays(input).map(Answer::from)
+    }
 }
 
-fn load_displays(filename: &str) -> io::Result<Vec<DisplayEntry>> {
+fn load_displays(input: &str) -> AocResult<Vec<DisplayEntry>> {
     let mut displays = Vec::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
-        let (signal_pattern_str, output_str) = line.split('|').collect_tuple().unwrap();
+        let (signal_pattern_str, output_str) = line
+            .split('|')
+            .collect_tuple()
+            .ok_or_else(|| AocError::parse(i, "expected patterns and outputs separated by '|'"))?;
         displays.push(DisplayEntry {
             signal_patterns: signal_pattern_str
                 .split(' ')
@@ -200,87 +217,81 @@ fn find_unique_numbers(displays: &Vec<DisplayEntry>) -> i32 {
     count as i32
 }
 
-fn load_find_unique_numbers(filename: &str) -> io::Result<i32> {
-    let displays = load_displays(filename)?;
+fn load_find_unique_numbers(input: &str) -> AocResult<i32> {
+    let displays = load_displays(input)?;
     Ok(find_unique_numbers(&displays))
 }
 
-fn solve_pattern(display_entry: &DisplayEntry) -> SegmentDisplay {
+fn solve_pattern(display_entry: &DisplayEntry) -> AocResult<SegmentDisplay> {
+    let unsolvable =
+        || AocError::invalid_state("the signal patterns don't fit a seven segment display");
     let segments_by_count = collect_segments_by_count(display_entry);
 
     let one_pattern = display_entry
         .signal_patterns
         .iter()
         .find(|p| p.len() == 2)
-        .unwrap()
+        .ok_or_else(unsolvable)?
         .chars();
     let four_pattern = display_entry
         .signal_patterns
         .iter()
         .find(|p| p.len() == 4)
-        .unwrap()
+        .ok_or_else(unsolvable)?
         .chars();
     let seven_pattern = display_entry
         .signal_patterns
         .iter()
         .find(|p| p.len() == 3)
-        .unwrap()
+        .ok_or_else(unsolvable)?
         .chars();
 
     // a segment is always in 7 but not in 1.
     let a_map = seven_pattern
         .clone()
         .find(|c| !one_pattern.clone().contains(c))
-        .unwrap();
+        .ok_or_else(unsolvable)?;
 
     // b segment appears exactly 6 times across all patterns.
     let b_map = segments_by_count
         .get(&6)
-        .unwrap()
-        .iter()
-        .exactly_one()
-        .unwrap()
-        .to_owned();
+        .and_then(|letters| letters.iter().exactly_one().ok())
+        .copied()
+        .ok_or_else(unsolvable)?;
 
     // e segment appears exactly 4 times across all patterns.
     let e_map = segments_by_count
         .get(&4)
-        .unwrap()
-        .iter()
-        .exactly_one()
-        .unwrap()
-        .to_owned();
+        .and_then(|letters| letters.iter().exactly_one().ok())
+        .copied()
+        .ok_or_else(unsolvable)?;
 
     // f segment appears exactly 9 times across all patterns.
     let f_map = segments_by_count
         .get(&9)
-        .unwrap()
-        .iter()
-        .exactly_one()
-        .unwrap()
-        .to_owned();
+        .and_then(|letters| letters.iter().exactly_one().ok())
+        .copied()
+        .ok_or_else(unsolvable)?;
 
     // c segment is always in 7 but isn't a or f.
     let c_map = seven_pattern
         .clone()
         .find(|c| c != &a_map && c != &f_map)
-        .unwrap();
+        .ok_or_else(unsolvable)?;
 
     // d segment is always in 4 but isn't b, c, or f.
     let d_map = four_pattern
         .clone()
         .find(|c| c != &b_map && c != &c_map && c != &f_map)
-        .unwrap();
+        .ok_or_else(unsolvable)?;
 
     let g_map = segments_by_count
         .get(&7)
-        .unwrap()
-        .iter()
-        .find(|c| c != &&d_map)
-        .unwrap()
-        .to_owned();
+        .and_then(|letters| letters.iter().find(|c| c != &&d_map))
+        .copied()
+        .ok_or_else(unsolvable)?;
 
-    SegmentDisplay {
+    Ok(SegmentDisplay {
         a: a_map,
         b: b_map,
         c: c_map,
@@ -288,7 +299,7 @@ fn solve_pattern(display_entry: &DisplayEntry) -> SegmentDisplay {
         e: e_map,
         f: f_map,
         g: g_map,
-    }
+    })
 }
 
 fn collect_segments_by_count(display_entry: &DisplayEntry) -> HashMap<usize, Vec<char>> {
@@ -313,17 +324,17 @@ fn collect_segments_by_count(display_entry: &DisplayEntry) -> HashMap<usize, Vec
     letter_count_map
 }
 
-fn solve_displays(filename: &str) -> io::Result<i32> {
+fn solve_displays(input: &str) -> AocResult<i32> {
     let mut result = 0;
 
-    let displays = load_displays(filename)?;
+    let displays = load_displays(input)?;
 
     for display in displays {
-        let segment = solve_pattern(&display);
+        let segment = solve_pattern(&display)?;
         let mut answer = 0;
         for output in &display.output_values {
             answer *= 10;
-            let digit = segment.calc_number(output);
+            let digit = segment.calc_number(output)?;
             answer += digit;
         }
         result += answer;
@@ -339,7 +350,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            load_find_unique_numbers("./inputs/day-8-input-test.txt").unwrap(),
+            load_find_unique_numbers(include_str!("../inputs/day-8-input-test.txt")).unwrap(),
             26
         );
     }
@@ -347,7 +358,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            load_find_unique_numbers("./inputs/day-8-input.txt").unwrap(),
+            load_find_unique_numbers(include_str!("../inputs/day-8-input.txt")).unwrap(),
             534
         );
     }
@@ -355,13 +366,16 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            solve_displays("./inputs/day-8-input-test.txt").unwrap(),
+            solve_displays(include_str!("../inputs/day-8-input-test.txt")).unwrap(),
             61229
         );
     }
 
     #[test]
     fn part_2_test() {
-        assert_eq!(solve_displays("./inputs/day-8-input.txt").unwrap(), 1070188);
+        assert_eq!(
+            solve_displays(include_str!("../inputs/day-8-input.txt")).unwrap(),
+            1070188
+        );
     }
 }
@@ -1,29 +1,41 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    io::{self},
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use utils::{
+    error::{AocResult, LineContext},
+    solution::{Answer, Solution},
 };
 
-use utils::file_utils::read_lines;
+pub struct Day9;
 
-pub fn day_9() -> io::Result<i32> {
-    find_risk_of_lowpoint("./inputs/day-9-input.txt")
-}
-pub fn day_9_part_2() -> io::Result<i32> {
-    find_basins("./inputs/day-9-input.txt")
+impl Solution for Day9 {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        9
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        find_risk_of_lowpoint(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        find_basins(input).map(Answer::from)
+    }
 }
 
-fn load_map(filename: &str) -> io::Result<HashMap<(i32, i32), i32>> {
+fn load_map(input: &str) -> AocResult<HashMap<(i32, i32), i32>> {
     let mut map = HashMap::new();
 
-    let lines = read_lines(filename)?;
     let mut y = 0;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
         for (x, c) in line.trim().chars().enumerate() {
-            let height = format!("{c}").parse::<i32>().unwrap();
+            let height = format!("{c}").parse::<i32>().on_line(i)?;
             map.insert((x as i32, y), height);
         }
 
@@ -32,8 +44,8 @@ fn load_map(filename: &str) -> io::Result<HashMap<(i32, i32), i32>> {
     Ok(map)
 }
 
-fn find_risk_of_lowpoint(filename: &str) -> io::Result<i32> {
-    let map = load_map(filename)?;
+fn find_risk_of_lowpoint(input: &str) -> AocResult<i32> {
+    let map = load_map(input)?;
     let mut risk = 0;
     'outer: for ((x, y), height) in &map {
         let adjacent: Vec<(i32, i32)> = vec![(x - 1, *y), (x + 1, *y), (*x, y - 1), (*x, y + 1)];
@@ -50,8 +62,8 @@ fn find_risk_of_lowpoint(filename: &str) -> io::Result<i32> {
     Ok(risk)
 }
 
-fn find_basins(filename: &str) -> io::Result<i32> {
-    let map = load_map(filename)?;
+fn find_basins(input: &str) -> AocResult<i32> {
+    let map = load_map(input)?;
     let mut lowpoints = Vec::new();
     'outer: for ((x, y), height) in &map {
         let adjacent: Vec<(i32, i32)> = vec![(x - 1, *y), (x + 1, *y), (*x, y - 1), (*x, y + 1)];
@@ -114,7 +126,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            find_risk_of_lowpoint("./inputs/day-9-input-test.txt").unwrap(),
+            find_risk_of_lowpoint(include_str!("../inputs/day-9-input-test.txt")).unwrap(),
             15
         );
     }
@@ -122,18 +134,24 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            find_risk_of_lowpoint("./inputs/day-9-input.txt").unwrap(),
+            find_risk_of_lowpoint(include_str!("../inputs/day-9-input.txt")).unwrap(),
             524
         );
     }
 
     #[test]
     fn part_2_small_test() {
-        assert_eq!(find_basins("./inputs/day-9-input-test.txt").unwrap(), 1134);
+        assert_eq!(
+            find_basins(include_str!("../inputs/day-9-input-test.txt")).unwrap(),
+            1134
+        );
     }
 
     #[test]
     fn part_2_test() {
-        assert_eq!(find_basins("./inputs/day-9-input.txt").unwrap(), 1235430);
+        assert_eq!(
+            find_basins(include_str!("../inputs/day-9-input.txt")).unwrap(),
+            1235430
+        );
     }
 }
@@ -0,0 +1,73 @@
+pub mod day_1;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_2;
+pub mod day_21;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+
+use utils::solution::Registry;
+
+/**
+ * Directory of this crate. Puzzle inputs are kept in its `inputs` directory.
+ */
+pub const DIRECTORY: &str = env!("CARGO_MANIFEST_DIR");
+
+/**
+ * Registers every 2021 solution.
+ */
+pub fn register(registry: &mut Registry) {
+    registry.register(day_1::Day1);
+    registry.register(day_2::Day2);
+    registry.register(day_3::Day3);
+    registry.register(day_4::Day4);
+    registry.register(day_5::Day5);
+    registry.register(day_6::Day6);
+    registry.register(day_7::Day7);
+    registry.register(day_8::Day8);
+    registry.register(day_9::Day9);
+    registry.register(day_10::Day10);
+    registry.register(day_11::Day11);
+    registry.register(day_12::Day12);
+    registry.register(day_13::Day13);
+    registry.register(day_14::Day14);
+    registry.register(day_15::Day15);
+    registry.register(day_16::Day16);
+    registry.register(day_17::Day17);
+    registry.register(day_18::Day18);
+    registry.register(day_21::Day21);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn register_test() {
+        let mut registry = Registry::new();
+        register(&mut registry);
+
+        assert_eq!(registry.len(), 19);
+        assert!(registry.iter().all(|s| s.year() == 2021));
+        assert!(registry
+            .iter()
+            .map(|s| s.day())
+            .tuple_windows()
+            .all(|(a, b)| a < b));
+    }
+}
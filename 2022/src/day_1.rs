@@ -1,39 +1,53 @@
-use std::io::{self};
-
-use crate::file_utils::read_lines;
-
-/**
- * Gets the highest calorie count from day-1-input.txt.
- */
-pub fn day_1() -> io::Result<i32> {
-    let mut calories = fetch_calories()?;
-    calories.sort();
-    calories.reverse();
-    Ok(*calories.first().unwrap())
-}
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    solution::{Answer, Solution},
+};
+
+pub struct Day1;
 
-/**
- * Gets the sum of the 3 highest calorie counts from day-1-input.txt.
- */
-pub fn day_1_part_2() -> io::Result<i32> {
-    let mut calories = fetch_calories()?;
-    calories.sort();
-    calories.reverse();
-    Ok(calories.iter().take(3).sum::<i32>())
+impl Solution for Day1 {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        1
+    }
+
+    /**
+     * Gets the highest calorie count.
+     */
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        let mut calories = fetch_calories(input)?;
+        calories.sort();
+        calories.reverse();
+        calories
+            .first()
+            .map(|c| Answer::from(*c))
+            .ok_or_else(|| AocError::invalid_state("there are no elves"))
+    }
+
+    /**
+     * Gets the sum of the 3 highest calorie counts.
+     */
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        let mut calories = fetch_calories(input)?;
+        calories.sort();
+        calories.reverse();
+        Ok(Answer::from(calories.iter().take(3).sum::<i32>()))
+    }
 }
 
-fn fetch_calories() -> io::Result<Vec<i32>> {
+fn fetch_calories(input: &str) -> AocResult<Vec<i32>> {
     let mut calories_vec = Vec::new();
 
-    let lines = read_lines("./inputs/day-1-input.txt")?;
     let mut calories = 0;
-    for line in lines.flatten() {
-        let line_calories_opt = line.parse::<i32>();
-        if let Ok(line_calories) = line_calories_opt {
-            calories += line_calories;
-        } else if line.is_empty() {
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
             calories_vec.push(calories);
             calories = 0;
+        } else {
+            calories += line.parse::<i32>().on_line(i)?;
         }
     }
     Ok(calories_vec)
@@ -46,11 +60,19 @@ mod tests {
 
     #[test]
     fn test() {
-        assert_eq!(day_1().unwrap(), 70116);
+        assert_eq!(
+            Day1.part_1(include_str!("../inputs/day-1-input.txt"))
+                .unwrap(),
+            Answer::Integer(70116)
+        );
     }
 
     #[test]
     fn part_2_test() {
-        assert_eq!(day_1_part_2().unwrap(), 206582);
+        assert_eq!(
+            Day1.part_2(include_str!("../inputs/day-1-input.txt"))
+                .unwrap(),
+            Answer::Integer(206582)
+        );
     }
 }
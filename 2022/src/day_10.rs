@@ -1,8 +1,8 @@
-use std::io;
-
-use itertools::Itertools;
-
-use crate::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    ocr,
+    solution::{Answer, Solution},
+};
 
 struct Cpu {
     pub x_reg: i32,
@@ -44,12 +44,29 @@ impl Cpu {
     }
 }
 
-pub fn day_10() -> io::Result<(i32, String)> {
-    let result = draw_cycles("./inputs/day-10-input.txt")?;
-    Ok(result)
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        let (signal_strength, _) = draw_cycles(input)?;
+        Ok(Answer::from(signal_strength))
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        let (_, display) = draw_cycles(input)?;
+        ocr::recognise(&display).map(Answer::Text)
+    }
 }
 
-fn draw_cycles(filename: &str) -> io::Result<(i32, String)> {
+fn draw_cycles(input: &str) -> AocResult<(i32, String)> {
     let mut result = 0;
     let mut last_x = 0;
     let mut cpu = Cpu::new();
@@ -57,21 +74,18 @@ fn draw_cycles(filename: &str) -> io::Result<(i32, String)> {
     let mut sample_cycle = 19;
     let step_sample_cycle = 40;
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
         if line.starts_with("noop") {
             cpu.noop();
-        } else if line.starts_with("addx") {
-            if let Some((_, v_s)) = line.split(' ').collect_tuple() {
-                let v = v_s.parse::<i32>().unwrap();
-                cpu.addx(v);
-            }
+        } else if let Some(v_s) = line.strip_prefix("addx ") {
+            let v = v_s.parse::<i32>().on_line(i)?;
+            cpu.addx(v);
         } else {
-            panic!("Unknown command: {line}");
+            return Err(AocError::parse(i, format!("unknown command '{line}'")));
         }
         if cpu.cycle_count > sample_cycle {
             result += (sample_cycle + 1) * last_x;
@@ -91,20 +105,29 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            draw_cycles("./inputs/day-10-input-test.txt").unwrap().0,
+            draw_cycles(include_str!("../inputs/day-10-input-test.txt"))
+                .unwrap()
+                .0,
             13140
         );
     }
 
     #[test]
     fn test() {
-        assert_eq!(draw_cycles("./inputs/day-10-input.txt").unwrap().0, 15220);
+        assert_eq!(
+            draw_cycles(include_str!("../inputs/day-10-input.txt"))
+                .unwrap()
+                .0,
+            15220
+        );
     }
 
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            draw_cycles("./inputs/day-10-input-test.txt").unwrap().1,
+            draw_cycles(include_str!("../inputs/day-10-input-test.txt"))
+                .unwrap()
+                .1,
             r#"##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
 ####....####....####....####....####....
@@ -117,7 +140,9 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            draw_cycles("./inputs/day-10-input.txt").unwrap().1,
+            draw_cycles(include_str!("../inputs/day-10-input.txt"))
+                .unwrap()
+                .1,
             r#"###..####.####.####.#..#.###..####..##..
 #..#.#.......#.#....#.#..#..#.#....#..#.
 #..#.###....#..###..##...###..###..#..#.
@@ -125,5 +150,14 @@ mod tests {
 #.#..#....#....#....#.#..#..#.#....#..#.
 #..#.#....####.####.#..#.###..#....#..#."#
         );
+        assert_eq!(
+            ocr::recognise(
+                &draw_cycles(include_str!("../inputs/day-10-input.txt"))
+                    .unwrap()
+                    .1
+            )
+            .unwrap(),
+            "RFZEKBFA"
+        );
     }
 }
@@ -1,8 +1,11 @@
-use std::io;
-
 use itertools::Itertools;
 
-use crate::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    math::checked_lcm,
+    parse::{blocks, field, list},
+    solution::{Answer, Solution},
+};
 
 const STARTING_ITEMS_PREFIX: &str = "Starting items: ";
 
@@ -11,13 +14,12 @@ const TEST_PREFIX: &str = "Test: divisible by ";
 const TRUE_PREFIX: &str = "If true: throw to monkey ";
 const FALSE_PREFIX: &str = "If false: throw to monkey ";
 
-#[derive(Default)]
 struct Monkey {
     pub items: Vec<i64>,
     pub operation: Operation,
     pub test: i64,
-    pub true_result: i64,
-    pub false_result: i64,
+    pub true_result: usize,
+    pub false_result: usize,
     pub inspected_items: i64,
 }
 
@@ -25,12 +27,13 @@ impl Monkey {
     /**
      * Throws items -> (monkey id, new item)
      */
-    pub fn throw_items(&mut self, worry_level: i64, mod_value: i64) -> Vec<(i64, i64)> {
+    pub fn throw_items(&mut self, worry_level: i64, mod_value: i64) -> Vec<(usize, i64)> {
         let mut results = Vec::new();
 
         for item in &self.items {
             self.inspected_items += 1;
-            let new_value = (self.operation.calc(*item) / worry_level) % mod_value;
+            let new_value =
+                (self.operation.calc(*item) / worry_level as i128 % mod_value as i128) as i64;
 
             let result_monkey = if new_value % self.test == 0 {
                 self.true_result
@@ -50,11 +53,11 @@ enum Operand {
 }
 
 impl Operand {
-    pub fn from_str(s: &str) -> Self {
+    pub fn from_str(s: &str) -> Result<Self, String> {
         match s {
-            "+" => Self::Plus,
-            "*" => Self::Multiply,
-            _ => panic!("Unknown operation"),
+            "+" => Ok(Self::Plus),
+            "*" => Ok(Self::Multiply),
+            _ => Err(format!("unknown operation '{s}'")),
         }
     }
 }
@@ -67,40 +70,37 @@ struct Operation {
     pub b: Option<i64>,
 }
 
-impl Default for Operation {
-    fn default() -> Self {
-        Self {
-            a: Default::default(),
-            operand: Operand::Plus,
-            b: Default::default(),
-        }
-    }
-}
-
 impl Operation {
-    pub fn from_str(line: &str) -> Self {
+    pub fn from_str(line: &str) -> Result<Self, String> {
         if let Some((a_s, op_s, b_s)) = line.split(' ').collect_tuple() {
-            let a = Self::value_from_str(a_s);
-            let operand = Operand::from_str(op_s);
-            let b = Self::value_from_str(b_s);
+            let a = Self::value_from_str(a_s)?;
+            let operand = Operand::from_str(op_s)?;
+            let b = Self::value_from_str(b_s)?;
 
-            Operation { a, operand, b }
+            Ok(Operation { a, operand, b })
         } else {
-            panic!("Cannot get operation: {line}.");
+            Err(format!("cannot get operation from '{line}'"))
         }
     }
 
-    fn value_from_str(value: &str) -> Option<i64> {
+    fn value_from_str(value: &str) -> Result<Option<i64>, String> {
         let mut result = None;
         if value != "old" {
-            result = Some(value.parse::<i64>().unwrap());
+            result = Some(
+                value
+                    .parse::<i64>()
+                    .map_err(|err| format!("invalid operand '{value}': {err}"))?,
+            );
         }
-        result
+        Ok(result)
     }
 
-    pub fn calc(&self, value: i64) -> i64 {
-        let av = self.a.unwrap_or(value);
-        let bv = self.b.unwrap_or(value);
+    /**
+     * Applies the operation, widening so that multiplying two worry levels can't overflow.
+     */
+    pub fn calc(&self, value: i64) -> i128 {
+        let av = self.a.unwrap_or(value) as i128;
+        let bv = self.b.unwrap_or(value) as i128;
 
         match self.operand {
             Operand::Plus => av + bv,
@@ -109,68 +109,91 @@ impl Operation {
     }
 }
 
-pub fn day_11() -> io::Result<i64> {
-    let positions = monkey_sim(3, 20, "./inputs/day-11-input.txt")?;
-    Ok(positions)
-}
+pub struct Day11;
 
-pub fn day_11_part_2() -> io::Result<i64> {
-    let positions = monkey_sim(1, 10000, "./inputs/day-11-input.txt")?;
-    Ok(positions)
-}
+impl Solution for Day11 {
+    fn year(&self) -> u32 {
+        2022
+    }
 
-fn monkey_sim(worry_level: i64, rounds: i64, filename: &str) -> io::Result<i64> {
-    let mut monkey_list = Vec::new();
-    let mut current_monkey = Monkey::default();
+    fn day(&self) -> u32 {
+        11
+    }
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
-        if line.is_empty() {
-            continue;
-        }
-        let trim = line.trim();
-        if trim.starts_with("Monkey") {
-            if !trim.contains('0') {
-                monkey_list.push(current_monkey);
-            }
-            current_monkey = Monkey::default();
-        } else if trim.starts_with(STARTING_ITEMS_PREFIX) {
-            let items_s = trim.replace(STARTING_ITEMS_PREFIX, "");
-            let items = items_s
-                .split(',')
-                .map(|s| s.trim().parse::<i64>().unwrap())
-                .collect_vec();
-            current_monkey.items = items;
-        } else if trim.starts_with(OPERATION_PREFIX) {
-            let operation = trim.replace(OPERATION_PREFIX, "");
-            current_monkey.operation = Operation::from_str(operation.as_str());
-        } else if trim.starts_with(TEST_PREFIX) {
-            let test_s = trim.replace(TEST_PREFIX, "");
-            current_monkey.test = test_s.parse::<i64>().unwrap();
-        } else if trim.starts_with(TRUE_PREFIX) {
-            let value_s = trim.replace(TRUE_PREFIX, "");
-            current_monkey.true_result = value_s.parse::<i64>().unwrap();
-        } else if trim.starts_with(FALSE_PREFIX) {
-            let value_s = trim.replace(FALSE_PREFIX, "");
-            current_monkey.false_result = value_s.parse::<i64>().unwrap();
-        } else {
-            panic!("Unknown line {trim}.");
-        }
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        let positions = monkey_sim(3, 20, input)?;
+        Ok(Answer::from(positions))
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        let positions = monkey_sim(1, 10000, input)?;
+        Ok(Answer::from(positions))
     }
+}
+
+/**
+ * Loads the monkeys' notes, which are blocks of lines each starting with the monkey's number.
+ */
+fn load_monkeys(input: &str) -> AocResult<Vec<Monkey>> {
+    let mut monkeys = Vec::new();
+    for block in blocks(input) {
+        let (i, line) = block.line(1)?;
+        let items = list(field(line, STARTING_ITEMS_PREFIX, i)?, ",", i)?;
+
+        let (i, line) = block.line(2)?;
+        let operation = Operation::from_str(field(line, OPERATION_PREFIX, i)?).on_line(i)?;
+
+        let (i, line) = block.line(3)?;
+        let test = field(line, TEST_PREFIX, i)?.parse::<i64>().on_line(i)?;
+        if test <= 0 {
+            return Err(AocError::parse(
+                i,
+                format!("the test must divide by a positive number, not {test}"),
+            ));
+        }
+
+        let (i, line) = block.line(4)?;
+        let true_result = field(line, TRUE_PREFIX, i)?.parse::<usize>().on_line(i)?;
 
-    monkey_list.push(current_monkey);
+        let (i, line) = block.line(5)?;
+        let false_result = field(line, FALSE_PREFIX, i)?.parse::<usize>().on_line(i)?;
 
-    let mut mod_value = 1;
-    for monkey in &monkey_list {
-        mod_value *= monkey.test;
+        monkeys.push(Monkey {
+            items,
+            operation,
+            test,
+            true_result,
+            false_result,
+            inspected_items: 0,
+        });
     }
+    Ok(monkeys)
+}
+
+fn monkey_sim(worry_level: i64, rounds: i64, input: &str) -> AocResult<i64> {
+    let mut monkey_list = load_monkeys(input)?;
+
+    // Worry levels only matter for the divisibility tests, so they can be kept modulo a
+    // number that every test divides.
+    let mod_value = monkey_list
+        .iter()
+        .try_fold(1, |acc, monkey| checked_lcm(acc, monkey.test))
+        .ok_or_else(|| AocError::invalid_state("the tests have no common multiple that fits"))?;
 
     for _ in 0..rounds {
         for monkey_i in 0..monkey_list.len() {
             let throws = monkey_list[monkey_i].throw_items(worry_level, mod_value);
 
             for (monkey_id, item) in throws {
-                monkey_list[monkey_id as usize].items.push(item);
+                monkey_list
+                    .get_mut(monkey_id)
+                    .ok_or_else(|| {
+                        AocError::invalid_state(format!(
+                            "monkey {monkey_i} throws to monkey {monkey_id}, which doesn't exist"
+                        ))
+                    })?
+                    .items
+                    .push(item);
             }
         }
     }
@@ -179,7 +202,13 @@ fn monkey_sim(worry_level: i64, rounds: i64, filename: &str) -> io::Result<i64>
     item_inspected.sort();
     item_inspected.reverse();
 
-    Ok(item_inspected[0] * item_inspected[1])
+    match item_inspected[..] {
+        [first, second, ..] => Ok(first * second),
+        _ => Err(AocError::invalid_state(format!(
+            "expected at least 2 monkeys, found {}",
+            item_inspected.len()
+        ))),
+    }
 }
 
 #[cfg(test)]
@@ -190,7 +219,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            monkey_sim(3, 20, "./inputs/day-11-input-test.txt").unwrap(),
+            monkey_sim(3, 20, include_str!("../inputs/day-11-input-test.txt")).unwrap(),
             10605
         );
     }
@@ -198,7 +227,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            monkey_sim(3, 20, "./inputs/day-11-input.txt").unwrap(),
+            monkey_sim(3, 20, include_str!("../inputs/day-11-input.txt")).unwrap(),
             108240
         );
     }
@@ -206,7 +235,7 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            monkey_sim(1, 10000, "./inputs/day-11-input-test.txt").unwrap(),
+            monkey_sim(1, 10000, include_str!("../inputs/day-11-input-test.txt")).unwrap(),
             2713310158
         );
     }
@@ -214,8 +243,33 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            monkey_sim(1, 10000, "./inputs/day-11-input.txt").unwrap(),
+            monkey_sim(1, 10000, include_str!("../inputs/day-11-input.txt")).unwrap(),
             25712998901
         );
     }
+
+    #[test]
+    fn invalid_test() {
+        let input = include_str!("../inputs/day-11-input-test.txt");
+        let zero = input.replacen("divisible by 23", "divisible by 0", 1);
+        assert!(matches!(
+            monkey_sim(3, 20, &zero),
+            Err(AocError::Parse { line: 4, .. })
+        ));
+        let missing = input.replacen("throw to monkey 3", "throw to monkey 999999999999", 1);
+        assert!(matches!(
+            monkey_sim(3, 20, &missing),
+            Err(AocError::InvalidState(_))
+        ));
+        let negative = input.replacen("throw to monkey 2", "throw to monkey -1", 1);
+        assert!(monkey_sim(3, 20, &negative).is_err());
+        let huge = input
+            .replacen("divisible by 23", "divisible by 1000000007", 1)
+            .replacen("divisible by 19", "divisible by 1000000009", 1)
+            .replacen("divisible by 13", "divisible by 998244353", 1);
+        assert!(matches!(
+            monkey_sim(1, 10000, &huge),
+            Err(AocError::InvalidState(_))
+        ));
+    }
 }
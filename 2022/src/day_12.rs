@@ -1,6 +1,8 @@
-use std::{collections::VecDeque, io};
-
-use crate::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult},
+    search::bfs,
+    solution::{Answer, Solution},
+};
 
 struct Map {
     cells: Vec<Vec<i32>>,
@@ -36,119 +38,50 @@ impl Map {
         result
     }
 
+    #[cfg(test)]
     pub fn width(&self) -> i32 {
         self.cells[0].len() as i32
     }
 
+    #[cfg(test)]
     pub fn height(&self) -> i32 {
         self.cells.len() as i32
     }
 }
 
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-    None,
-}
-
-struct MapTree {
-    pub nodes: Vec<MapTreeNode>,
-}
+pub struct Day12;
 
-impl MapTree {
-    pub fn new() -> MapTree {
-        MapTree { nodes: Vec::new() }
+impl Solution for Day12 {
+    fn year(&self) -> u32 {
+        2022
     }
 
-    /**
-     * Creates a root, returning its index.
-     */
-    pub fn create_root(&mut self, x: i32, y: i32) -> usize {
-        self.nodes.push(MapTreeNode {
-            parent_index: None,
-            children_index: Vec::new(),
-            direction: Direction::None,
-            x,
-            y,
-            step_count: 0,
-        });
-        self.nodes.len() - 1
+    fn day(&self) -> u32 {
+        12
     }
 
-    pub fn create_node(
-        &mut self,
-        x: i32,
-        y: i32,
-        parent_index: usize,
-        direction: Direction,
-    ) -> usize {
-        self.nodes.push(MapTreeNode {
-            parent_index: Some(parent_index),
-            children_index: Vec::new(),
-            direction,
-            x,
-            y,
-            step_count: self.nodes[parent_index].step_count + 1,
-        });
-        let index = self.nodes.len() - 1;
-        self.nodes[parent_index].children_index.push(index);
-        index
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        let map = build_map(input)?;
+        let positions = climb_sim(map.start, &map)
+            .ok_or_else(|| AocError::invalid_state("there is no route to the summit"))?;
+        Ok(Answer::from(positions))
     }
 
-    pub fn fetch_node_coords(&mut self, index: usize) -> (i32, i32, i32) {
-        (
-            self.nodes[index].x,
-            self.nodes[index].y,
-            self.nodes[index].step_count,
-        )
-    }
-
-    pub fn location_visited(&self, x: i32, y: i32) -> bool {
-        // I believe there's a chance a short-cut could be found that this function
-        // would disallow, but that hasn't happened in the test cases so *shrug*.
-        for node in &self.nodes {
-            if node.x == x && node.y == y {
-                return true;
-            }
-        }
-        false
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        let map = build_map(input)?;
+        let positions = descend_sim(map.end, &map)
+            .ok_or_else(|| AocError::invalid_state("there is no route down to the lowest point"))?;
+        Ok(Answer::from(positions))
     }
 }
 
-struct MapTreeNode {
-    pub parent_index: Option<usize>,
-    pub children_index: Vec<usize>,
-    /**
-     * Direction of travel that brought traveller to this node.
-     */
-    pub direction: Direction,
-    pub x: i32,
-    pub y: i32,
-    pub step_count: i32,
-}
-
-pub fn day_12() -> io::Result<i32> {
-    let map = build_map("./inputs/day-12-input.txt")?;
-    let positions = climb_sim(map.start, &map).unwrap();
-    Ok(positions)
-}
-
-pub fn day_12_part_2() -> io::Result<i32> {
-    let map = build_map("./inputs/day-12-input.txt")?;
-    let positions = descend_sim(map.end, &map).unwrap();
-    Ok(positions)
-}
-
-fn build_map(filename: &str) -> io::Result<Map> {
+fn build_map(input: &str) -> AocResult<Map> {
     let mut map = Map::new();
 
-    let lines = read_lines(filename)?;
     let mut y = 0;
     let mut start_opt = None;
     let mut end_opt = None;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
@@ -164,7 +97,8 @@ fn build_map(filename: &str) -> io::Result<Map> {
                     end_opt = Some((x as i32, y));
                     fetch_elevation('z')
                 }
-                _ => fetch_elevation(c),
+                'a'..='z' => fetch_elevation(c),
+                _ => return Err(AocError::parse_at(i, x, format!("unknown elevation '{c}'"))),
             };
             row.push(elevation);
         }
@@ -174,300 +108,50 @@ fn build_map(filename: &str) -> io::Result<Map> {
     if let Some(start) = start_opt {
         map.start = start;
     } else {
-        panic!("Start position was not found.");
+        return Err(AocError::invalid_state("the start position was not found"));
     }
     if let Some(end) = end_opt {
         map.end = end;
     } else {
-        panic!("End position was not found.");
+        return Err(AocError::invalid_state("the end position was not found"));
     }
     Ok(map)
 }
 
 fn climb_sim(start_point: (i32, i32), map: &Map) -> Option<i32> {
-    let jump_height = 1;
-
-    let mut map_tree = MapTree::new();
-
-    let current_node_index = map_tree.create_root(start_point.0, start_point.1);
-
-    let mut check_stack = VecDeque::new();
-    check_stack.push_front(current_node_index);
-
-    let mut completed_route_step_count = None;
-
-    while !check_stack.is_empty() {
-        let index = check_stack.pop_front().unwrap();
-
-        let (current_x, current_y, step_count) = map_tree.fetch_node_coords(index);
+    let (steps, _) = bfs(
+        start_point,
+        |&position| climbable_neighbours(map, position, 1),
+        |&position| position == map.end,
+    )?;
 
-        if let Some(completed_count) = completed_route_step_count {
-            if step_count > completed_count {
-                continue;
-            }
-        }
-
-        if current_x == map.end.0 && current_y == map.end.1 {
-            completed_route_step_count = Some(step_count);
-            continue;
-        }
-
-        let current_elevation = map.fetch_cell(current_x, current_y).unwrap();
-
-        // north
-        if let Some(left_index) = check_cell(
-            map,
-            &mut map_tree,
-            jump_height,
-            index,
-            current_elevation,
-            (current_x, current_y),
-            Direction::North,
-        ) {
-            check_stack.push_back(left_index);
-        }
-
-        // east
-        if let Some(east_index) = check_cell(
-            map,
-            &mut map_tree,
-            jump_height,
-            index,
-            current_elevation,
-            (current_x, current_y),
-            Direction::East,
-        ) {
-            check_stack.push_back(east_index);
-        }
-
-        // south
-        if let Some(south_index) = check_cell(
-            map,
-            &mut map_tree,
-            jump_height,
-            index,
-            current_elevation,
-            (current_x, current_y),
-            Direction::South,
-        ) {
-            check_stack.push_back(south_index);
-        }
-
-        // west
-        if let Some(west_index) = check_cell(
-            map,
-            &mut map_tree,
-            jump_height,
-            index,
-            current_elevation,
-            (current_x, current_y),
-            Direction::West,
-        ) {
-            check_stack.push_back(west_index);
-        }
-    }
-
-    let mut results = Vec::new();
-    for i in 0..map_tree.nodes.len() {
-        if map_tree.nodes[i].x == map.end.0 && map_tree.nodes[i].y == map.end.1 {
-            results.push((i, map_tree.nodes[i].step_count));
-        }
-    }
-
-    if results.is_empty() {
-        return None;
-    }
-
-    results.sort_by(|(_, a), (_, b)| a.cmp(b));
-
-    let result = results[0];
-
-    let mut display = Vec::new();
-    let mut display_index = result.0;
-    while let Some(p_r) = map_tree.nodes[display_index].parent_index {
-        let c = match map_tree.nodes[display_index].direction {
-            Direction::North => '^',
-            Direction::East => '>',
-            Direction::South => 'v',
-            Direction::West => '<',
-            Direction::None => '?',
-        };
-        display.push((map_tree.nodes[p_r].x, map_tree.nodes[p_r].y, c));
-        display_index = p_r;
-    }
-
-    //print_journey(map, display);
-
-    Some(map_tree.nodes[result.0].step_count)
+    Some(steps as i32)
 }
 
 fn descend_sim(start_point: (i32, i32), map: &Map) -> Option<i32> {
-    let jump_height = -1;
-
-    let mut map_tree = MapTree::new();
+    let (steps, _) = bfs(
+        start_point,
+        |&position| climbable_neighbours(map, position, -1),
+        |&(x, y)| map.fetch_cell(x, y) == Some(0),
+    )?;
 
-    let current_node_index = map_tree.create_root(start_point.0, start_point.1);
-
-    let mut check_stack = VecDeque::new();
-    check_stack.push_front(current_node_index);
-
-    let mut completed_route_step_count = None;
-
-    while !check_stack.is_empty() {
-        let index = check_stack.pop_front().unwrap();
-
-        let (current_x, current_y, step_count) = map_tree.fetch_node_coords(index);
-
-        if let Some(completed_count) = completed_route_step_count {
-            if step_count > completed_count {
-                continue;
-            }
-        }
-
-        let current_elevation = map.fetch_cell(current_x, current_y).unwrap();
-        if current_elevation == 0 {
-            completed_route_step_count = Some(step_count);
-            continue;
-        }
-
-        // north
-        if let Some(left_index) = check_cell(
-            map,
-            &mut map_tree,
-            jump_height,
-            index,
-            current_elevation,
-            (current_x, current_y),
-            Direction::North,
-        ) {
-            check_stack.push_back(left_index);
-        }
-
-        // east
-        if let Some(east_index) = check_cell(
-            map,
-            &mut map_tree,
-            jump_height,
-            index,
-            current_elevation,
-            (current_x, current_y),
-            Direction::East,
-        ) {
-            check_stack.push_back(east_index);
-        }
-
-        // south
-        if let Some(south_index) = check_cell(
-            map,
-            &mut map_tree,
-            jump_height,
-            index,
-            current_elevation,
-            (current_x, current_y),
-            Direction::South,
-        ) {
-            check_stack.push_back(south_index);
-        }
-
-        // west
-        if let Some(west_index) = check_cell(
-            map,
-            &mut map_tree,
-            jump_height,
-            index,
-            current_elevation,
-            (current_x, current_y),
-            Direction::West,
-        ) {
-            check_stack.push_back(west_index);
-        }
-    }
-
-    let mut results = Vec::new();
-    for i in 0..map_tree.nodes.len() {
-        if map.fetch_cell(map_tree.nodes[i].x, map_tree.nodes[i].y) == Some(0) {
-            results.push((i, map_tree.nodes[i].step_count));
-        }
-    }
-
-    if results.is_empty() {
-        return None;
-    }
-
-    results.sort_by(|(_, a), (_, b)| a.cmp(b));
-
-    let result = results[0];
-
-    let mut display = Vec::new();
-    let mut display_index = result.0;
-    while let Some(p_r) = map_tree.nodes[display_index].parent_index {
-        let c = match map_tree.nodes[display_index].direction {
-            Direction::North => '^',
-            Direction::East => '>',
-            Direction::South => 'v',
-            Direction::West => '<',
-            Direction::None => '?',
-        };
-        display.push((map_tree.nodes[p_r].x, map_tree.nodes[p_r].y, c));
-        display_index = p_r;
-    }
-
-    //print_journey(map, display);
-
-    Some(map_tree.nodes[result.0].step_count)
-}
-
-#[allow(dead_code)]
-fn print_journey(map: &Map, display: Vec<(i32, i32, char)>) {
-    for y in 0..map.height() {
-        for x in 0..map.width() {
-            if x == map.start.0 && y == map.start.1 {
-                print!("S");
-            } else if x == map.end.0 && y == map.end.1 {
-                print!("E");
-            } else {
-                let mut to_print = '.';
-                for (dx, dy, c) in &display {
-                    if y == *dy && x == *dx {
-                        to_print = *c;
-                        break;
-                    }
-                }
-                print!("{to_print}");
-            }
-        }
-        println!();
-    }
+    Some(steps as i32)
 }
 
-fn check_cell(
-    map: &Map,
-    map_tree: &mut MapTree,
-    jump_height: i32,
-    index: usize,
-    current_elevation: i32,
-    current_coord: (i32, i32),
-    direction: Direction,
-) -> Option<usize> {
-    let new_x = match direction {
-        Direction::East => current_coord.0 + 1,
-        Direction::West => current_coord.0 - 1,
-        _ => current_coord.0,
+/**
+ * Gets the cells north, east, south and west of the position that can be moved to.
+ */
+fn climbable_neighbours(map: &Map, (x, y): (i32, i32), jump_height: i32) -> Vec<(i32, i32)> {
+    let Some(current_elevation) = map.fetch_cell(x, y) else {
+        return Vec::new();
     };
-    let new_y = match direction {
-        Direction::North => current_coord.1 - 1,
-        Direction::South => current_coord.1 + 1,
-        _ => current_coord.1,
-    };
-    if let Some(elevation) = map.fetch_cell(new_x, new_y) {
-        if is_climable(current_elevation, elevation, jump_height)
-            && !map_tree.location_visited(new_x, new_y)
-        {
-            let new_index = map_tree.create_node(new_x, new_y, index, direction);
-            return Some(new_index);
-        }
-    }
-    None
+    [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)]
+        .into_iter()
+        .filter(|&(new_x, new_y)| {
+            map.fetch_cell(new_x, new_y)
+                .is_some_and(|elevation| is_climable(current_elevation, elevation, jump_height))
+        })
+        .collect()
 }
 
 fn is_climable(current_elevation: i32, target_elevation: i32, jump_height: i32) -> bool {
@@ -485,8 +169,34 @@ fn fetch_elevation(c: char) -> i32 {
 #[cfg(test)]
 mod tests {
 
+    use utils::{coordinate::Coordinate, render::Picture};
+
     use super::*;
 
+    /**
+     * Draws the map shaded by elevation, from dark to light, with the route over it in red.
+     */
+    fn render_journey(map: &Map, route: &[(i32, i32)]) -> Picture {
+        let mut picture = Picture::new(
+            Coordinate::origin(),
+            map.width() as usize,
+            map.height() as usize,
+            [0, 0, 0],
+        );
+        for y in 0..map.height() {
+            for x in 0..map.width() {
+                let shade = map.fetch_cell(x, y).unwrap_or(0) as u8 * 9;
+                picture.set(Coordinate::new(x, y), [shade, shade, shade]);
+            }
+        }
+        let route = route
+            .iter()
+            .map(|&position| Coordinate::from(position))
+            .collect::<Vec<_>>();
+        picture.draw_path(&route, [255, 0, 0]);
+        picture
+    }
+
     #[test]
     fn fetch_elevation_test() {
         assert_eq!(0, fetch_elevation('a'));
@@ -495,25 +205,57 @@ mod tests {
 
     #[test]
     fn small_test() {
-        let map = build_map("./inputs/day-12-input-test.txt").unwrap();
+        let map = build_map(include_str!("../inputs/day-12-input-test.txt")).unwrap();
         assert_eq!(climb_sim(map.start, &map).unwrap(), 31);
     }
 
     #[test]
     fn test() {
-        let map = build_map("./inputs/day-12-input.txt").unwrap();
+        let map = build_map(include_str!("../inputs/day-12-input.txt")).unwrap();
         assert_eq!(climb_sim(map.start, &map).unwrap(), 380);
     }
 
     #[test]
     fn part_2_small_test() {
-        let map = build_map("./inputs/day-12-input-test.txt").unwrap();
+        let map = build_map(include_str!("../inputs/day-12-input-test.txt")).unwrap();
         assert_eq!(descend_sim(map.end, &map).unwrap(), 29);
     }
 
     #[test]
     fn part_2_test() {
-        let map = build_map("./inputs/day-12-input.txt").unwrap();
+        let map = build_map(include_str!("../inputs/day-12-input.txt")).unwrap();
         assert_eq!(descend_sim(map.end, &map).unwrap(), 375);
     }
+
+    #[test]
+    fn render_journey_test() {
+        let map = build_map(include_str!("../inputs/day-12-input-test.txt")).unwrap();
+        let (_, route) = bfs(
+            map.start,
+            |&position| climbable_neighbours(&map, position, 1),
+            |&position| position == map.end,
+        )
+        .unwrap();
+        let picture = render_journey(&map, &route);
+        assert_eq!((picture.width(), picture.height()), (8, 5));
+
+        let red = [255, 0, 0];
+        let cells = (0..5)
+            .flat_map(|y| (0..8).map(move |x| Coordinate::new(x, y)))
+            .filter_map(|c| picture.get(c))
+            .collect::<Vec<_>>();
+        assert_eq!(cells.iter().filter(|&&c| c == red).count(), route.len());
+        assert_eq!(picture.get(Coordinate::new(2, 5)), None);
+
+        // Off the route, cells get lighter the higher they are.
+        let off_route = (0..5)
+            .flat_map(|y| (0..8).map(move |x| (x, y)))
+            .filter(|position| !route.contains(position))
+            .collect::<Vec<_>>();
+        assert!(!off_route.is_empty());
+        for (x, y) in off_route {
+            let shade = map.fetch_cell(x, y).unwrap() as u8 * 9;
+            assert_eq!(picture.get(Coordinate::new(x, y)), Some([shade; 3]));
+        }
+    }
 }
@@ -1,9 +1,12 @@
 use core::fmt;
-use std::{cmp::Ordering, io};
+use std::cmp::Ordering;
 
 use itertools::Itertools;
 
-use crate::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult},
+    solution::{Answer, Solution},
+};
 
 struct Packet {
     pub value: Option<i32>,
@@ -20,19 +23,61 @@ impl Packet {
         }
     }
 
-    pub fn from_str(value: &str) -> Packet {
+    /**
+     * Parses a packet from the line of the puzzle input at the given index, rejecting anything
+     * that is not a well formed list.
+     */
+    pub fn parse(value: &str, index: usize) -> AocResult<Packet> {
+        Packet::check(value)
+            .and_then(|()| Packet::from_str(value))
+            .and_then(|packet| {
+                if packet.fetch_str() == value {
+                    Ok(packet)
+                } else {
+                    Err(format!("malformed packet '{value}'"))
+                }
+            })
+            .map_err(|message| AocError::parse(index, message))
+    }
+
+    /**
+     * Checks that the value is a list with balanced brackets holding only numbers.
+     */
+    fn check(value: &str) -> Result<(), String> {
+        if !value.starts_with('[') {
+            return Err(format!("expected a list, found '{value}'"));
+        }
+        let mut depth = 0;
+        for c in value.chars() {
+            match c {
+                '[' => depth += 1,
+                ']' if depth == 0 => return Err("unbalanced ']'".to_string()),
+                ']' => depth -= 1,
+                ',' | '0'..='9' => {}
+                other => return Err(format!("unexpected value '{other}'")),
+            }
+        }
+        if depth != 0 {
+            return Err("unbalanced '['".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn from_str(value: &str) -> Result<Packet, String> {
         let mut char_buffer = Vec::new();
 
         for c in value.chars() {
             char_buffer.push(c);
         }
-        let mut packets = Packet::from_vec(char_buffer);
-        assert_eq!(1, packets.len());
+        let mut packets = Packet::from_vec(char_buffer)?;
+        if packets.len() != 1 {
+            return Err(format!("expected a single packet, found {}", packets.len()));
+        }
 
-        packets.swap_remove(0)
+        Ok(packets.swap_remove(0))
     }
 
-    pub fn from_vec(char_buffer: Vec<char>) -> Vec<Packet> {
+    pub fn from_vec(char_buffer: Vec<char>) -> Result<Vec<Packet>, String> {
         let mut list = Vec::new();
 
         let mut number_buffer = "".to_string();
@@ -47,7 +92,9 @@ impl Packet {
                     let mut slice = Vec::new();
                     loop {
                         char_i += 1;
-                        let f_char = char_buffer[char_i];
+                        let f_char = *char_buffer
+                            .get(char_i)
+                            .ok_or_else(|| "unbalanced '['".to_string())?;
                         if f_char == '[' {
                             pair += 1;
                         } else if f_char == ']' {
@@ -58,7 +105,7 @@ impl Packet {
                         }
                         slice.push(f_char);
                     }
-                    let inner_packets = Packet::from_vec(slice);
+                    let inner_packets = Packet::from_vec(slice)?;
 
                     list.push(Packet {
                         value: None,
@@ -67,30 +114,25 @@ impl Packet {
                     char_i += 1;
                 }
                 ',' => {
-                    if let Ok(num_value) = number_buffer.parse::<i32>() {
-                        list.push(Packet::from_number(num_value));
+                    if !number_buffer.is_empty() {
+                        list.push(Packet::from_number(parse_number(&number_buffer)?));
                         number_buffer.clear();
                     }
                 }
                 other => {
                     if other.is_ascii_digit() {
-                        number_buffer.push_str(&other.to_string());
+                        number_buffer.push(other);
                     } else {
-                        panic!("Unexpected value: '{other}'.");
+                        return Err(format!("unexpected value '{other}'"));
                     }
                 }
             }
             char_i += 1;
         }
         if !number_buffer.is_empty() {
-            if let Ok(num_value) = number_buffer.parse::<i32>() {
-                list.push(Packet::from_number(num_value));
-                number_buffer.clear();
-            } else {
-                panic!("Cannot parse number: '{number_buffer}'.");
-            }
+            list.push(Packet::from_number(parse_number(&number_buffer)?));
         }
-        list
+        Ok(list)
     }
 
     pub fn from_number(value: i32) -> Packet {
@@ -101,9 +143,32 @@ impl Packet {
     }
 }
 
+fn parse_number(number: &str) -> Result<i32, String> {
+    number
+        .parse::<i32>()
+        .map_err(|err| format!("cannot parse number '{number}': {err}"))
+}
+
 impl Ord for Packet {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        match (self.value, other.value) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(v), None) => {
+                let list_a = Packet {
+                    value: None,
+                    list: vec![Packet::from_number(v)],
+                };
+                cmp_array(&list_a, other)
+            }
+            (None, Some(v)) => {
+                let list_b = Packet {
+                    value: None,
+                    list: vec![Packet::from_number(v)],
+                };
+                cmp_array(self, &list_b)
+            }
+            (None, None) => cmp_array(self, other),
+        }
     }
 }
 
@@ -117,23 +182,7 @@ impl PartialEq for Packet {
 
 impl PartialOrd for Packet {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        if self.value.is_some() && other.value.is_some() {
-            return Some(self.value.unwrap().cmp(&other.value.unwrap()));
-        } else if let Some(v) = self.value {
-            let list_a = Packet {
-                value: None,
-                list: vec![Packet::from_number(v)],
-            };
-            return Some(cmp_array(&list_a, other));
-        } else if let Some(v) = other.value {
-            let list_b = Packet {
-                value: None,
-                list: vec![Packet::from_number(v)],
-            };
-            return Some(cmp_array(self, &list_b));
-        }
-
-        Some(cmp_array(self, other))
+        Some(self.cmp(other))
     }
 }
 
@@ -161,18 +210,17 @@ impl fmt::Display for Packet {
     }
 }
 
-fn compare_sets_from_file(filename: &str) -> io::Result<i32> {
-    let lines = read_lines(filename)?;
-
+fn compare_sets_from_file(input: &str) -> AocResult<i32> {
     let mut result = 0;
 
     let mut a = None;
     let mut b = None;
     let mut index = 1;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
-            let aw = a.unwrap();
-            let bw = b.unwrap();
+            let (Some(aw), Some(bw)) = (a, b) else {
+                return Err(AocError::parse(i, "expected a pair of packets"));
+            };
 
             if aw < bw {
                 result += index;
@@ -185,40 +233,36 @@ fn compare_sets_from_file(filename: &str) -> io::Result<i32> {
             continue;
         }
 
+        let p = Packet::parse(line, i)?;
         if a.is_none() {
-            let p = Packet::from_str(line.as_str());
-            assert!(p.fetch_str() == line.as_str());
             a = Some(p);
         } else {
-            let p = Packet::from_str(line.as_str());
-            assert!(p.fetch_str() == line.as_str());
             b = Some(p);
         }
     }
     Ok(result)
 }
 
-fn order_sets_from_file(filename: &str) -> io::Result<i32> {
-    let lines = read_lines(filename)?;
-
+fn order_sets_from_file(input: &str) -> AocResult<i32> {
     let divider_a = "[[2]]";
     let divider_b = "[[6]]";
 
     let mut packets = Vec::new();
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
-        packets.push(Packet::from_str(line.as_str()));
+        packets.push(Packet::parse(line, i)?);
     }
 
-    packets.push(Packet::from_str(divider_a));
-    packets.push(Packet::from_str(divider_b));
+    let divider = |value| Packet::from_str(value).map_err(AocError::invalid_state);
+    packets.push(divider(divider_a)?);
+    packets.push(divider(divider_b)?);
 
     packets.sort();
 
-    let a = Packet::from_str(divider_a);
-    let b = Packet::from_str(divider_b);
+    let a = divider(divider_a)?;
+    let b = divider(divider_b)?;
     let mut divider_a_index = None;
     let mut divider_b_index = None;
     for (i, p) in packets.iter().enumerate() {
@@ -229,15 +273,30 @@ fn order_sets_from_file(filename: &str) -> io::Result<i32> {
         }
     }
 
-    Ok(divider_a_index.unwrap() as i32 * divider_b_index.unwrap() as i32)
+    let missing = || AocError::invalid_state("a divider packet went missing while sorting");
+    let divider_a_index = divider_a_index.ok_or_else(missing)?;
+    let divider_b_index = divider_b_index.ok_or_else(missing)?;
+    Ok(divider_a_index as i32 * divider_b_index as i32)
 }
 
-pub fn day_13() -> io::Result<i32> {
-    compare_sets_from_file("./inputs/day-13-input.txt")
-}
+pub struct Day13;
+
+impl Solution for Day13 {
+    fn year(&self) -> u32 {
+        2022
+    }
 
-pub fn day_13_part_2() -> io::Result<i32> {
-    order_sets_from_file("./inputs/day-13-input.txt")
+    fn day(&self) -> u32 {
+        13
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        compare_sets_from_file(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        order_sets_from_file(input).map(Answer::from)
+    }
 }
 
 #[cfg(test)]
@@ -245,13 +304,17 @@ mod tests {
 
     use super::*;
 
+    fn packet(value: &str) -> Packet {
+        Packet::from_str(value).unwrap()
+    }
+
     #[test]
     fn packet_test() {
-        let a = Packet::from_str("[9]");
+        let a = packet("[9]");
 
-        let b = Packet::from_str("[1,[2,3,4]]");
+        let b = packet("[1,[2,3,4]]");
 
-        let c = Packet::from_str("[17,512]");
+        let c = packet("[17,512]");
 
         assert_eq!(1, a.list.len());
         assert_eq!(Some(9), a.list[0].value);
@@ -276,51 +339,60 @@ mod tests {
 
     #[test]
     fn equal_test() {
-        assert!(Packet::from_str("[1,1,3,1,1]") != Packet::from_str("[1,1,5,1,1]"));
-        assert!(Packet::from_str("[1,1,3,1,1]") == Packet::from_str("[1,1,3,1,1]"));
+        assert!(packet("[1,1,3,1,1]") != packet("[1,1,5,1,1]"));
+        assert!(packet("[1,1,3,1,1]") == packet("[1,1,3,1,1]"));
 
-        assert!(Packet::from_str("[[2]]") == Packet::from_str("[[2]]"));
-        assert!(Packet::from_str("[[6]]") == Packet::from_str("[[6]]"));
+        assert!(packet("[[2]]") == packet("[[2]]"));
+        assert!(packet("[[6]]") == packet("[[6]]"));
 
-        assert!(Packet::from_str("[[2]]") != Packet::from_str("[[6]]"));
-        assert!(Packet::from_str("[[6]]") != Packet::from_str("[[2]]"));
+        assert!(packet("[[2]]") != packet("[[6]]"));
+        assert!(packet("[[6]]") != packet("[[2]]"));
     }
 
     #[test]
     fn compare_test() {
-        let res = Packet::from_str("[1,1,3,1,1]").partial_cmp(&Packet::from_str("[1,1,5,1,1]"));
-
-        if let Some(r) = res {
-            println!("{r:?}");
-        } else {
-            panic!("no result");
-        }
-        assert!(Packet::from_str("[1,1,3,1,1]") < Packet::from_str("[1,1,5,1,1]"));
-        assert!(Packet::from_str("[[1],[2,3,4]]") < Packet::from_str("[[1],4]"));
-        assert!(Packet::from_str("[9]") > Packet::from_str("[[8,7,6]]"));
-        assert!(Packet::from_str("[[4,4],4,4]") < Packet::from_str("[[4,4],4,4,4]"));
-        assert!(Packet::from_str("[7,7,7,7]") > Packet::from_str("[7,7,7]"));
-        assert!(Packet::from_str("[]") < Packet::from_str("[3]"));
-        assert!(Packet::from_str("[[[]]]") > Packet::from_str("[[]]"));
+        let res = packet("[1,1,3,1,1]").partial_cmp(&packet("[1,1,5,1,1]"));
+        assert_eq!(res, Some(Ordering::Less));
+        assert!(packet("[1,1,3,1,1]") < packet("[1,1,5,1,1]"));
+        assert!(packet("[[1],[2,3,4]]") < packet("[[1],4]"));
+        assert!(packet("[9]") > packet("[[8,7,6]]"));
+        assert!(packet("[[4,4],4,4]") < packet("[[4,4],4,4,4]"));
+        assert!(packet("[7,7,7,7]") > packet("[7,7,7]"));
+        assert!(packet("[]") < packet("[3]"));
+        assert!(packet("[[[]]]") > packet("[[]]"));
+        assert!(packet("[1,[2,[3,[4,[5,6,7]]]],8,9]") > packet("[1,[2,[3,[4,[5,6,0]]]],8,9]"));
+        assert!(packet("[5,6,7]") > packet("[5,6,0]"));
+        assert!(packet("[[4,[4,[5],[7,2],[4,0,3],[]],[[10,8,1]]],[5,[[1,1,7,3],[],[8,10,6,5,4],0,9],[[3,5,6,10],[10,0,2,10],5,[1,6,7]],2],[],[8,4,0,5]]") <
+packet("[[[5,[8,8,6,4,4],[2,9,0,9]],6,[],[[4,0],[],0]],[],[],[8,3]]"));
         assert!(
-            Packet::from_str("[1,[2,[3,[4,[5,6,7]]]],8,9]")
-                > Packet::from_str("[1,[2,[3,[4,[5,6,0]]]],8,9]")
-        );
-        assert!(Packet::from_str("[5,6,7]") > Packet::from_str("[5,6,0]"));
-        assert!(Packet::from_str("[[4,[4,[5],[7,2],[4,0,3],[]],[[10,8,1]]],[5,[[1,1,7,3],[],[8,10,6,5,4],0,9],[[3,5,6,10],[10,0,2,10],5,[1,6,7]],2],[],[8,4,0,5]]") <
-Packet::from_str("[[[5,[8,8,6,4,4],[2,9,0,9]],6,[],[[4,0],[],0]],[],[],[8,3]]"));
-        assert!(
-            Packet::from_str("[4,[4,[5],[7,2],[4,0,3],[]],[[10,8,1]]]")
-                < Packet::from_str("[[5,[8,8,6,4,4],[2,9,0,9]],6,[],[[4,0],[],0]]")
+            packet("[4,[4,[5],[7,2],[4,0,3],[]],[[10,8,1]]]")
+                < packet("[[5,[8,8,6,4,4],[2,9,0,9]],6,[],[[4,0],[],0]]")
         );
 
-        assert!(Packet::from_str("[4]",) < Packet::from_str("[5,[8,8,6,4,4],[2,9,0,9]]"));
+        assert!(packet("[4]",) < packet("[5,[8,8,6,4,4],[2,9,0,9]]"));
+    }
+
+    #[test]
+    fn invalid_test() {
+        for value in ["[999999999999]", "[1,x]", "[[1]", "1", "[1]]"] {
+            assert!(
+                matches!(
+                    Packet::parse(value, 2),
+                    Err(AocError::Parse { line: 3, .. })
+                ),
+                "{value}"
+            );
+        }
+        assert!(matches!(
+            order_sets_from_file("[1]\n[999999999999]\n"),
+            Err(AocError::Parse { line: 2, .. })
+        ));
     }
 
     #[test]
     fn small_test() {
         assert_eq!(
-            compare_sets_from_file("./inputs/day-13-input-test.txt").unwrap(),
+            compare_sets_from_file(include_str!("../inputs/day-13-input-test.txt")).unwrap(),
             13
         );
     }
@@ -328,7 +400,7 @@ Packet::from_str("[[[5,[8,8,6,4,4],[2,9,0,9]],6,[],[[4,0],[],0]],[],[],[8,3]]"))
     #[test]
     fn test() {
         assert_eq!(
-            compare_sets_from_file("./inputs/day-13-input.txt").unwrap(),
+            compare_sets_from_file(include_str!("../inputs/day-13-input.txt")).unwrap(),
             6046
         );
     }
@@ -336,7 +408,7 @@ Packet::from_str("[[[5,[8,8,6,4,4],[2,9,0,9]],6,[],[[4,0],[],0]],[],[],[8,3]]"))
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            order_sets_from_file("./inputs/day-13-input-test.txt").unwrap(),
+            order_sets_from_file(include_str!("../inputs/day-13-input-test.txt")).unwrap(),
             140
         )
     }
@@ -344,7 +416,7 @@ Packet::from_str("[[[5,[8,8,6,4,4],[2,9,0,9]],6,[],[[4,0],[],0]],[],[],[8,3]]"))
     #[test]
     fn part_2_test() {
         assert_eq!(
-            order_sets_from_file("./inputs/day-13-input.txt").unwrap(),
+            order_sets_from_file(include_str!("../inputs/day-13-input.txt")).unwrap(),
             21423
         )
     }
@@ -1,60 +1,26 @@
-use std::{collections::HashMap, io};
+use std::collections::HashMap;
 
 use itertools::Itertools;
 
-use crate::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    solution::{Answer, Solution},
+};
 
 type Map = HashMap<(i32, i32), char>;
 
 const SAND_X: i32 = 500;
 const SAND_Y: i32 = 0;
 
-#[allow(dead_code)]
-fn draw_map(map: &Map) {
-    let mut min_x = i32::MAX;
-    let min_y = 0; //i32::MAX;
-
-    let mut max_x = i32::MIN;
-    let mut max_y = i32::MIN;
-
-    for (x, y) in map.keys() {
-        if x < &min_x {
-            min_x = *x;
-        }
-        if x > &max_x {
-            max_x = *x;
-        }
-        if y > &max_y {
-            max_y = *y
-        }
-    }
-
-    println!("({min_x},{min_y}) -> ({max_x},{max_y})");
-    for y in min_y..=max_y {
-        for x in min_x..=max_x {
-            if y == SAND_Y && x == SAND_X {
-                print!("+");
-            } else if let Some(c) = map.get(&(x, y)) {
-                print!("{c}");
-            } else {
-                print!(".");
-            }
-        }
-        println!();
-    }
-}
-
-fn read_slice_from_file(filename: &str) -> io::Result<Map> {
-    let lines = read_lines(filename)?;
-
+fn read_slice_from_file(input: &str) -> AocResult<Map> {
     let mut map: Map = HashMap::new();
-    for line in lines.flatten() {
+    for (index, line) in input.lines().enumerate() {
         let mut prev_coord = None;
         let parts = line.split("->");
         for part in parts {
             if let Some((x, y)) = part.trim().split(',').collect_tuple() {
-                let nx = x.parse::<i32>().unwrap();
-                let ny = y.parse::<i32>().unwrap();
+                let nx = x.parse::<i32>().on_line(index)?;
+                let ny = y.parse::<i32>().on_line(index)?;
                 if let Some((prev_x, prev_y)) = prev_coord {
                     let mut x_step = 1;
                     let mut x_limit = nx + 1;
@@ -81,14 +47,17 @@ fn read_slice_from_file(filename: &str) -> io::Result<Map> {
                 }
                 prev_coord = Some((nx, ny));
             } else {
-                panic!("Failed to parse.");
+                return Err(AocError::parse(
+                    index,
+                    format!("expected a point 'x,y', found '{part}'"),
+                ));
             }
         }
     }
     Ok(map)
 }
 
-fn sand_simulation(map: &mut Map, floor_plane_diff: Option<i32>) -> i32 {
+fn sand_simulation(map: &mut Map, floor_plane_diff: Option<i32>) -> AocResult<i32> {
     let mut result = 0;
     let mut max_y = i32::MIN;
 
@@ -110,13 +79,13 @@ fn sand_simulation(map: &mut Map, floor_plane_diff: Option<i32>) -> i32 {
 
         if map.contains_key(&(SAND_X, SAND_Y)) {
             // sand hole is clogged
-            return result;
+            return Ok(result);
         }
 
         loop {
             if floor_plane_diff.is_none() && active_sand_y > max_y {
                 // off bottom of map
-                return result;
+                return Ok(result);
             }
 
             // check down
@@ -145,54 +114,135 @@ fn sand_simulation(map: &mut Map, floor_plane_diff: Option<i32>) -> i32 {
             }
         }
     }
-    panic!("Sand simulation took too long.");
+    Err(AocError::invalid_state("the sand never stops falling"))
 }
 
-pub fn day_14() -> io::Result<i32> {
-    let mut map = read_slice_from_file("./inputs/day-14-input.txt")?;
-    let result = sand_simulation(&mut map, None);
-    Ok(result)
-}
+pub struct Day14;
+
+impl Solution for Day14 {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        14
+    }
 
-pub fn day_14_part_2() -> io::Result<i32> {
-    let mut map = read_slice_from_file("./inputs/day-14-input.txt")?;
-    let result = sand_simulation(&mut map, Some(2));
-    Ok(result)
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        let mut map = read_slice_from_file(input)?;
+        let result = sand_simulation(&mut map, None)?;
+        Ok(Answer::from(result))
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        let mut map = read_slice_from_file(input)?;
+        let result = sand_simulation(&mut map, Some(2))?;
+        Ok(Answer::from(result))
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
+    use utils::{
+        coordinate::{Coordinate, CoordinateMap},
+        render::{self, Picture},
+    };
+
     use super::*;
 
+    fn draw_map(map: &Map) {
+        let mut min_x = i32::MAX;
+        let min_y = 0; //i32::MAX;
+
+        let mut max_x = i32::MIN;
+        let mut max_y = i32::MIN;
+
+        for (x, y) in map.keys() {
+            if x < &min_x {
+                min_x = *x;
+            }
+            if x > &max_x {
+                max_x = *x;
+            }
+            if y > &max_y {
+                max_y = *y
+            }
+        }
+
+        println!("({min_x},{min_y}) -> ({max_x},{max_y})");
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                if y == SAND_Y && x == SAND_X {
+                    print!("+");
+                } else if let Some(c) = map.get(&(x, y)) {
+                    print!("{c}");
+                } else {
+                    print!(".");
+                }
+            }
+            println!();
+        }
+    }
+
+    /**
+     * Draws the rock in grey and the settled sand in yellow.
+     */
+    fn render_map(map: &Map) -> Picture {
+        let cells = map
+            .iter()
+            .map(|(&position, &c)| (Coordinate::from(position), c))
+            .collect::<CoordinateMap<_>>();
+        Picture::from_map(&cells, render::BLACK, |_, &c| match c {
+            'o' => [230, 200, 80],
+            _ => [128, 128, 128],
+        })
+    }
+
     #[test]
     fn small_test() {
-        let mut map = read_slice_from_file("./inputs/day-14-input-test.txt").unwrap();
-        let result = sand_simulation(&mut map, None);
+        let mut map =
+            read_slice_from_file(include_str!("../inputs/day-14-input-test.txt")).unwrap();
+        let result = sand_simulation(&mut map, None).unwrap();
         draw_map(&map);
         assert_eq!(result, 24);
     }
 
+    #[test]
+    fn render_map_test() {
+        let mut map =
+            read_slice_from_file(include_str!("../inputs/day-14-input-test.txt")).unwrap();
+        sand_simulation(&mut map, None).unwrap();
+        let picture = render_map(&map);
+
+        // The rock spans x from 494 to 503 and y from 4 to 9, and sand settles up to y = 2.
+        assert_eq!((picture.width(), picture.height()), (10, 8));
+        assert_eq!(picture.get(Coordinate::new(494, 9)), Some([128, 128, 128]));
+        assert_eq!(picture.get(Coordinate::new(500, 2)), Some([230, 200, 80]));
+        assert_eq!(picture.get(Coordinate::new(494, 2)), Some(render::BLACK));
+    }
+
     #[test]
     fn test() {
-        let mut map = read_slice_from_file("./inputs/day-14-input.txt").unwrap();
-        let result = sand_simulation(&mut map, None);
+        let mut map = read_slice_from_file(include_str!("../inputs/day-14-input.txt")).unwrap();
+        let result = sand_simulation(&mut map, None).unwrap();
         draw_map(&map);
         assert_eq!(result, 618);
     }
 
     #[test]
     fn part_2_small_test() {
-        let mut map = read_slice_from_file("./inputs/day-14-input-test.txt").unwrap();
-        let result = sand_simulation(&mut map, Some(2));
+        let mut map =
+            read_slice_from_file(include_str!("../inputs/day-14-input-test.txt")).unwrap();
+        let result = sand_simulation(&mut map, Some(2)).unwrap();
         draw_map(&map);
         assert_eq!(result, 93);
     }
 
     #[test]
     fn part_2_test() {
-        let mut map = read_slice_from_file("./inputs/day-14-input.txt").unwrap();
-        let result = sand_simulation(&mut map, Some(2));
+        let mut map = read_slice_from_file(include_str!("../inputs/day-14-input.txt")).unwrap();
+        let result = sand_simulation(&mut map, Some(2)).unwrap();
         draw_map(&map);
         assert_eq!(result, 26358);
     }
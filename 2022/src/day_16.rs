@@ -1,26 +1,23 @@
-use std::{
-    collections::{HashMap, HashSet},
-    io,
-};
-
 use itertools::Itertools;
 
-use crate::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    memo::Memo,
+    search::distances,
+    solution::{Answer, ParsedSolution},
+};
 
-type NodeList = Vec<ValveNode>;
+pub type NodeList = Vec<ValveNode>;
 
 trait NodeListExt {
     fn fetch_index_by_name(&self, name: &str) -> Option<usize>;
 
     fn fetch_or_create_index_by_name(&mut self, name: &str) -> usize;
 
-    fn fetch_connected_name(&self, index: usize) -> Vec<String>;
-
+    #[cfg(test)]
     fn fetch_names(&self, indexes: &[usize]) -> Vec<String>;
 
-    fn fetch_journey_length(&self, current_index: usize, destination_index: usize) -> i32;
-
-    fn fetch_all_journey_length(&self, current_index: usize) -> Vec<i32>;
+    fn fetch_all_journey_length(&self, current_index: usize) -> Vec<Option<i32>>;
 }
 
 impl NodeListExt for NodeList {
@@ -44,14 +41,7 @@ impl NodeListExt for NodeList {
         }
     }
 
-    fn fetch_connected_name(&self, index: usize) -> Vec<String> {
-        let mut names = Vec::new();
-        for i in &self[index].connected_indexes {
-            names.push(self[*i].name.clone());
-        }
-        names
-    }
-
+    #[cfg(test)]
     fn fetch_names(&self, indexes: &[usize]) -> Vec<String> {
         let mut names = Vec::new();
         for i in indexes {
@@ -60,62 +50,22 @@ impl NodeListExt for NodeList {
         names
     }
 
-    fn fetch_journey_length(&self, current_index: usize, destination_index: usize) -> i32 {
-        let mut visited = vec![-1; self.len()];
-        visited[current_index] = 0;
-
-        let mut indexes_to_check = Vec::new();
-        for c in &self[current_index].connected_indexes {
-            visited[*c] = 1;
-            indexes_to_check.push(*c);
-        }
-        let mut steps = 2;
-        while visited[destination_index] == -1 || !indexes_to_check.is_empty() {
-            let mut new_indexes: Vec<usize> = Vec::new();
-            for i in indexes_to_check {
-                for c in &self[i].connected_indexes {
-                    if visited[*c] == -1 {
-                        visited[*c] = steps;
-                        new_indexes.push(*c);
-                    }
-                }
-            }
-            steps += 1;
-            indexes_to_check = new_indexes;
-        }
-
-        visited[destination_index]
-    }
-
-    fn fetch_all_journey_length(&self, current_index: usize) -> Vec<i32> {
-        let mut visited = vec![-1; self.len()];
-        visited[current_index] = 0;
-
-        let mut indexes_to_check = Vec::new();
-        for c in &self[current_index].connected_indexes {
-            visited[*c] = 1;
-            indexes_to_check.push(*c);
-        }
-        let mut steps = 2;
-        while !indexes_to_check.is_empty() {
-            let mut new_indexes: Vec<usize> = Vec::new();
-            for i in indexes_to_check {
-                for c in &self[i].connected_indexes {
-                    if visited[*c] == -1 {
-                        visited[*c] = steps;
-                        new_indexes.push(*c);
-                    }
-                }
-            }
-            steps += 1;
-            indexes_to_check = new_indexes;
-        }
-
-        visited
+    /**
+     * Gets the number of steps to every valve, or nothing for the valves that can't be reached.
+     */
+    fn fetch_all_journey_length(&self, current_index: usize) -> Vec<Option<i32>> {
+        let lengths = distances(current_index, |&i| {
+            self[i]
+                .connected_indexes
+                .iter()
+                .map(|&c| (c, 1))
+                .collect_vec()
+        });
+        (0..self.len()).map(|i| lengths.get(&i).copied()).collect()
     }
 }
 
-struct ValveNode {
+pub struct ValveNode {
     pub name: String,
     pub connected_indexes: Vec<usize>,
     pub flow_rate: i32,
@@ -131,11 +81,10 @@ impl ValveNode {
     }
 }
 
-fn build_nodes(filename: &str) -> io::Result<NodeList> {
+fn build_nodes(input: &str) -> AocResult<NodeList> {
     let mut node_list = NodeList::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
@@ -147,164 +96,173 @@ fn build_nodes(filename: &str) -> io::Result<NodeList> {
             .replace("leads", "lead");
 
         // Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
-        let parsed = sscanf::sscanf!(
+        let (name, flow_rate, connected) = sscanf::sscanf!(
             fixed,
             "Valve {} has flow rate={}; tunnel lead to valve {}",
             String,
             i32,
             String
-        );
-        if let Ok((name, flow_rate, connected)) = parsed {
-            let index = node_list.fetch_or_create_index_by_name(name.as_str());
-
-            node_list[index].flow_rate = flow_rate;
-            let indexes = connected
-                .split(',')
-                .map(|c| node_list.fetch_or_create_index_by_name(c))
-                .collect_vec();
-            node_list[index].connected_indexes.extend(indexes);
-        }
+        )
+        .on_line(i)?;
+        let index = node_list.fetch_or_create_index_by_name(name.as_str());
+
+        node_list[index].flow_rate = flow_rate;
+        let indexes = connected
+            .split(',')
+            .map(|c| node_list.fetch_or_create_index_by_name(c))
+            .collect_vec();
+        node_list[index].connected_indexes.extend(indexes);
     }
     Ok(node_list)
 }
 
-#[derive(Clone)]
-struct JourneyResult {
-    pub pressure_released: i32,
-
-    pub valves_with_time: Vec<(String, i32)>,
-
-    pub time_remaining: i32,
-
-    pub visited: HashSet<usize>,
-
-    pub current_index: usize,
+/**
+ * What the search for the most pressure needs besides the state of a journey: the valves worth
+ * opening and how long it takes to walk between valves.
+ */
+struct Valves {
+    start_index: usize,
+    minutes: i32,
+    /**
+     * The indexes and flow rates of the valves that release pressure. Each is given a bit, by
+     * position, in the set of opened valves.
+     */
+    flowing: Vec<(usize, i32)>,
+    journey_lengths: Vec<Vec<Option<i32>>>,
 }
 
-fn journey_recurse(
-    journey_lengths: &Vec<Vec<i32>>,
-    node_list: &NodeList,
-    journey_result: JourneyResult,
-) -> Vec<JourneyResult> {
-    let mut results = Vec::new();
-
-    for (i, node) in node_list.iter().enumerate() {
-        if node.flow_rate > 0 && !journey_result.visited.contains(&i) {
-            let distance = journey_lengths[journey_result.current_index][i];
-
-            let updated_time = journey_result.time_remaining - (distance + 1);
-            if updated_time < 0 {
-                continue;
-            }
-
-            let mut sub_journey_result = journey_result.clone();
-
-            sub_journey_result.time_remaining = updated_time;
-            sub_journey_result.pressure_released +=
-                sub_journey_result.time_remaining * node_list[i].flow_rate;
-            sub_journey_result
-                .valves_with_time
-                .push((node_list[i].name.clone(), sub_journey_result.time_remaining));
-            sub_journey_result.visited.insert(i);
-            sub_journey_result.current_index = i;
-
-            let result = journey_recurse(journey_lengths, node_list, sub_journey_result);
-            results.extend(result);
+impl Valves {
+    fn new(node_list: &NodeList, minutes: i32) -> AocResult<Self> {
+        let start_index = node_list
+            .fetch_index_by_name("AA")
+            .ok_or_else(|| AocError::invalid_state("there is no valve AA to start from"))?;
+        let flowing = node_list
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.flow_rate > 0)
+            .map(|(i, node)| (i, node.flow_rate))
+            .collect_vec();
+        if flowing.len() > u64::BITS as usize {
+            return Err(AocError::invalid_state(format!(
+                "{} valves release pressure, but at most {} can be tracked",
+                flowing.len(),
+                u64::BITS
+            )));
         }
+        let journey_lengths = (0..node_list.len())
+            .map(|i| node_list.fetch_all_journey_length(i))
+            .collect();
+        Ok(Valves {
+            start_index,
+            minutes,
+            flowing,
+            journey_lengths,
+        })
     }
 
-    results.push(journey_result);
-    results
+    fn start(&self, opened: u64, elephant_to_come: bool) -> Journey {
+        Journey {
+            current_index: self.start_index,
+            time_remaining: self.minutes,
+            opened,
+            elephant_to_come,
+        }
+    }
 }
 
-fn open_valves(node_list: &mut NodeList) -> i32 {
-    let start_node_name = "AA";
-    let start_node_index = node_list.fetch_index_by_name("AA").unwrap();
-    let remaining_minutes = 30;
-
-    let mut visited = HashSet::new();
-    visited.insert(start_node_index);
-    let mut journey_lengths = Vec::new();
-    for (i, node) in node_list.iter().enumerate() {
-        let lengths = node_list.fetch_all_journey_length(i);
-        journey_lengths.push(lengths);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Journey {
+    current_index: usize,
+    time_remaining: i32,
+    /**
+     * The bits of the flowing valves that have already been opened.
+     */
+    opened: u64,
+    /**
+     * Whether the elephant still has its own journey to make from the start once this one
+     * ends.
+     */
+    elephant_to_come: bool,
+}
 
-        if node.flow_rate == 0 {
-            visited.insert(i);
+/**
+ * Gets the most pressure that can still be released by the journey, counting each valve's
+ * pressure for the rest of the time once it is opened.
+ */
+fn most_pressure(memo: &mut Memo<Journey, i32>, valves: &Valves, journey: Journey) -> i32 {
+    memo.cached(journey, |memo| {
+        let mut best = if journey.elephant_to_come {
+            most_pressure(memo, valves, valves.start(journey.opened, false))
+        } else {
+            0
+        };
+        for (bit, &(i, flow_rate)) in valves.flowing.iter().enumerate() {
+            if journey.opened & (1 << bit) != 0 {
+                continue;
+            }
+            let Some(distance) = valves.journey_lengths[journey.current_index][i] else {
+                continue;
+            };
+            let time_remaining = journey.time_remaining - (distance + 1);
+            if time_remaining <= 0 {
+                continue;
+            }
+            let next = Journey {
+                current_index: i,
+                time_remaining,
+                opened: journey.opened | (1 << bit),
+                ..journey
+            };
+            best = best.max(time_remaining * flow_rate + most_pressure(memo, valves, next));
         }
-    }
-
-    let journey_result = JourneyResult {
-        pressure_released: 0,
-        valves_with_time: vec![(start_node_name.to_string(), 30)],
-        time_remaining: remaining_minutes,
-        visited,
-        current_index: start_node_index,
-    };
-    let mut results = journey_recurse(&journey_lengths, node_list, journey_result);
+        best
+    })
+}
 
-    results.sort_by(|a, b| b.pressure_released.cmp(&a.pressure_released));
+fn open_valves(node_list: &NodeList) -> AocResult<i32> {
+    let valves = Valves::new(node_list, 30)?;
+    Ok(most_pressure(
+        &mut Memo::named("most_pressure"),
+        &valves,
+        valves.start(0, false),
+    ))
+}
 
-    results[0].pressure_released
+fn open_valves_with_two(node_list: &NodeList) -> AocResult<i32> {
+    let valves = Valves::new(node_list, 26)?;
+    Ok(most_pressure(
+        &mut Memo::named("most_pressure"),
+        &valves,
+        valves.start(0, true),
+    ))
 }
 
-fn open_valves_with_two(node_list: &mut NodeList) -> i32 {
-    let start_node_name = "AA";
-    let start_node_index = node_list.fetch_index_by_name("AA").unwrap();
-    let remaining_minutes = 26;
+pub struct Day16;
 
-    let mut journey_lengths = Vec::new();
-    for (i, _) in node_list.iter().enumerate() {
-        let lengths = node_list.fetch_all_journey_length(i);
-        journey_lengths.push(lengths);
-    }
+impl ParsedSolution for Day16 {
+    type Input = NodeList;
 
-    let journey_result = JourneyResult {
-        pressure_released: 0,
-        valves_with_time: vec![(start_node_name.to_string(), 30)],
-        time_remaining: remaining_minutes,
-        visited: HashSet::new(),
-        current_index: start_node_index,
-    };
-    let results = journey_recurse(&journey_lengths, node_list, journey_result);
-    let mut deduped = HashMap::new();
-    for r in results {
-        let key = r.visited.iter().map(|v| 1_usize << v).sum::<usize>();
-
-        let pressure = i32::max(
-            *deduped.get(&key).unwrap_or(&r.pressure_released),
-            r.pressure_released,
-        );
-        deduped.insert(key, pressure);
+    fn year(&self) -> u32 {
+        2022
     }
 
-    let mut not_overlapping_results = Vec::new();
-    for (k1, v1) in &deduped {
-        for (k2, v2) in &deduped {
-            if k1 & k2 == 0 {
-                not_overlapping_results.push(v1 + v2);
-            }
-        }
+    fn day(&self) -> u32 {
+        16
     }
 
-    not_overlapping_results.sort();
-    not_overlapping_results.reverse();
-
-    not_overlapping_results[0]
-}
-
-pub fn day_16() -> io::Result<i32> {
-    let mut node_list = build_nodes("./inputs/day-16-input.txt").unwrap();
-
-    let result = open_valves(&mut node_list);
-    Ok(result)
-}
+    fn parse(&self, input: &str) -> AocResult<NodeList> {
+        build_nodes(input)
+    }
 
-pub fn day_16_part_2() -> io::Result<i32> {
-    let mut node_list = build_nodes("./inputs/day-16-input.txt").unwrap();
+    fn part_1(&self, node_list: &NodeList) -> AocResult<Answer> {
+        let result = open_valves(node_list)?;
+        Ok(Answer::from(result))
+    }
 
-    let result = open_valves_with_two(&mut node_list);
-    Ok(result)
+    fn part_2(&self, node_list: &NodeList) -> AocResult<Answer> {
+        let result = open_valves_with_two(node_list)?;
+        Ok(Answer::from(result))
+    }
 }
 
 #[cfg(test)]
@@ -314,7 +272,7 @@ mod tests {
 
     #[test]
     fn build_nodes_test() {
-        let node_list = build_nodes("./inputs/day-16-input-test.txt").unwrap();
+        let node_list = build_nodes(include_str!("../inputs/day-16-input-test.txt")).unwrap();
 
         for node in &node_list {
             println!(
@@ -329,43 +287,43 @@ mod tests {
 
     #[test]
     fn fetch_journey_length_test() {
-        let node_list = build_nodes("./inputs/day-16-input-test.txt").unwrap();
+        let node_list = build_nodes(include_str!("../inputs/day-16-input-test.txt")).unwrap();
 
         let aa = node_list.fetch_index_by_name("AA").unwrap();
         let hh = node_list.fetch_index_by_name("HH").unwrap();
 
-        assert_eq!(5, node_list.fetch_journey_length(aa, hh));
+        assert_eq!(Some(5), node_list.fetch_all_journey_length(aa)[hh]);
     }
 
     #[test]
     fn small_test() {
-        let mut node_list = build_nodes("./inputs/day-16-input-test.txt").unwrap();
+        let node_list = build_nodes(include_str!("../inputs/day-16-input-test.txt")).unwrap();
 
-        let result = open_valves(&mut node_list);
+        let result = open_valves(&node_list).unwrap();
         assert_eq!(result, 1651);
     }
 
     #[test]
     fn test() {
-        let mut node_list = build_nodes("./inputs/day-16-input.txt").unwrap();
+        let node_list = build_nodes(include_str!("../inputs/day-16-input.txt")).unwrap();
 
-        let result = open_valves(&mut node_list);
+        let result = open_valves(&node_list).unwrap();
         assert_eq!(result, 2059);
     }
 
     #[test]
     fn part_2_small_test() {
-        let mut node_list = build_nodes("./inputs/day-16-input-test.txt").unwrap();
+        let node_list = build_nodes(include_str!("../inputs/day-16-input-test.txt")).unwrap();
 
-        let result = open_valves_with_two(&mut node_list);
+        let result = open_valves_with_two(&node_list).unwrap();
         assert_eq!(result, 1707);
     }
 
     #[test]
     fn part_2_test() {
-        let mut node_list = build_nodes("./inputs/day-16-input.txt").unwrap();
+        let node_list = build_nodes(include_str!("../inputs/day-16-input.txt")).unwrap();
 
-        let result = open_valves_with_two(&mut node_list);
+        let result = open_valves_with_two(&node_list).unwrap();
         assert_eq!(result, 2790);
     }
 }
@@ -1,9 +1,11 @@
-use std::{
-    collections::{HashMap, HashSet},
-    io,
-};
+use std::collections::HashSet;
 
-use crate::file_utils::read_lines;
+use utils::{
+    cycle::extrapolate,
+    error::{AocError, AocResult},
+    simulation::Simulation,
+    solution::{Answer, Solution},
+};
 
 const ARENA_WIDTH: usize = 7;
 
@@ -12,6 +14,11 @@ const DOWN_MARGIN: i32 = 3;
 
 const ROW_CACHE_SIZE: usize = 18;
 
+/**
+ * How many rows of the top of the pile are shown when visualising.
+ */
+const VIEW_HEIGHT: i32 = 40;
+
 #[derive(Eq, PartialEq, Hash)]
 struct CaveSequenceState {
     jet_index: u16,
@@ -19,136 +26,143 @@ struct CaveSequenceState {
     rows: [u8; ROW_CACHE_SIZE],
 }
 
-struct CaveSequenceInfo {
-    height: i32,
-    rock_number: u64,
+struct Cave {
+    arena: HashSet<(i32, i32)>,
+    top: i32,
+    jet_index: usize,
+    shape_id: i32,
 }
 
-fn calculate_height(blocks_to_drop: u64, filename: &str) -> io::Result<u64> {
-    let mut arena = HashSet::new();
-
-    for x in 0..ARENA_WIDTH {
-        arena.insert((x as i32, 0));
-    }
-
-    let mut jets = Vec::new();
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
-        if line.is_empty() {
-            continue;
+impl Cave {
+    fn new() -> Cave {
+        let mut arena = HashSet::new();
+        for x in 0..ARENA_WIDTH {
+            arena.insert((x as i32, 0));
         }
-        for c in line.chars() {
-            jets.push(c);
+        Cave {
+            arena,
+            top: 0,
+            jet_index: 0,
+            shape_id: 0,
         }
     }
 
-    let mut top: i32 = 0;
-
-    let mut shape_id = 0;
-    let mut shape = fetch_shape(shape_id);
-    let mut shape_coord = (LEFT_MARGIN, DOWN_MARGIN as i32 + 1);
-    let mut blocks_dropped = 0;
-
-    let mut sequence_found = false;
+    fn drop_rock(&mut self, jets: &[char]) {
+        let shape = fetch_shape(self.shape_id);
+        let mut shape_coord = (LEFT_MARGIN, self.top + DOWN_MARGIN + 1);
 
-    let mut sequence_map: HashMap<CaveSequenceState, CaveSequenceInfo> = HashMap::new();
+        loop {
+            let jet = jets[self.jet_index];
+            self.jet_index = (self.jet_index + 1) % jets.len();
 
-    let mut top_mod: u64 = 0;
+            let mut x_move_amount = match jet {
+                '<' => -1,
+                '>' => 1,
+                _ => unreachable!("jets are checked while loading"),
+            };
 
-    for (total_jet_index, jet) in jets.iter().cycle().enumerate() {
-        let jet_index = total_jet_index % jets.len();
-        let mut x_move_amount = match jet {
-            '<' => -1,
-            '>' => 1,
-            _ => panic!("Bad input"),
-        };
-
-        for (x, y) in &shape {
-            let m_x = x + shape_coord.0 + x_move_amount;
-            let m_y = y + shape_coord.1;
+            for (x, y) in &shape {
+                let m_x = x + shape_coord.0 + x_move_amount;
+                let m_y = y + shape_coord.1;
 
-            if m_x < 0 || m_x >= ARENA_WIDTH as i32 || arena.contains(&(m_x, m_y)) {
-                x_move_amount = 0;
-                break;
+                if m_x < 0 || m_x >= ARENA_WIDTH as i32 || self.arena.contains(&(m_x, m_y)) {
+                    x_move_amount = 0;
+                    break;
+                }
             }
-        }
 
-        shape_coord.0 += x_move_amount;
+            shape_coord.0 += x_move_amount;
 
-        let mut y_move_amount = -1;
+            let mut y_move_amount = -1;
 
-        for (x, y) in &shape {
-            let m_x = x + shape_coord.0;
-            let m_y = y + shape_coord.1 + y_move_amount;
-            if m_y < 0 || arena.contains(&(m_x, m_y)) {
-                y_move_amount = 0;
-                break;
-            }
-        }
-
-        shape_coord.1 += y_move_amount;
-
-        if y_move_amount == 0 {
             for (x, y) in &shape {
                 let m_x = x + shape_coord.0;
-                let m_y = y + shape_coord.1;
-
-                top = top.max(m_y);
-
-                arena.insert((m_x, m_y));
+                let m_y = y + shape_coord.1 + y_move_amount;
+                if m_y < 0 || self.arena.contains(&(m_x, m_y)) {
+                    y_move_amount = 0;
+                    break;
+                }
             }
 
-            if blocks_dropped > 0 && !sequence_found {
-                let mut cache_rows = [0_u8; ROW_CACHE_SIZE];
-                for (i, row) in cache_rows.iter_mut().enumerate().take(ROW_CACHE_SIZE) {
-                    let y = top - i as i32;
-                    let mut res = 0;
-                    for x in 0..ARENA_WIDTH as i32 {
-                        res += if arena.contains(&(x, y)) { 1 << x } else { 0 };
-                    }
-                    *row = res;
-                }
+            shape_coord.1 += y_move_amount;
 
-                let key = CaveSequenceState {
-                    jet_index: jet_index as u16,
-                    shape_undex: shape_id as u8,
-                    rows: cache_rows,
-                };
+            if y_move_amount == 0 {
+                for (x, y) in &shape {
+                    let m_x = x + shape_coord.0;
+                    let m_y = y + shape_coord.1;
 
-                if let Some(info) = sequence_map.get(&key) {
-                    let height_delta = top - info.height;
-                    let block_amount_delta = blocks_dropped - info.rock_number;
-                    let adj = (blocks_to_drop - blocks_dropped) / block_amount_delta;
+                    self.top = self.top.max(m_y);
 
-                    top_mod = height_delta as u64 * adj;
-                    blocks_dropped += adj * block_amount_delta;
-                    sequence_found = true;
-                } else {
-                    sequence_map.insert(
-                        key,
-                        CaveSequenceInfo {
-                            height: top,
-                            rock_number: blocks_dropped,
-                        },
-                    );
+                    self.arena.insert((m_x, m_y));
                 }
+                break;
             }
+        }
 
-            shape_id += 1;
-            shape_id %= 5;
+        self.shape_id += 1;
+        self.shape_id %= 5;
+    }
 
-            shape = fetch_shape(shape_id);
-            shape_coord = (LEFT_MARGIN, top + DOWN_MARGIN as i32 + 1);
-            assert!(shape_coord.1 >= 0);
+    /**
+     * Gets what decides how the next rocks fall: the next jet and shape, and the shape of the
+     * top of the pile.
+     */
+    fn sequence_state(&self) -> CaveSequenceState {
+        let mut cache_rows = [0_u8; ROW_CACHE_SIZE];
+        for (i, row) in cache_rows.iter_mut().enumerate() {
+            let y = self.top - i as i32;
+            let mut res = 0;
+            for x in 0..ARENA_WIDTH as i32 {
+                res += if self.arena.contains(&(x, y)) {
+                    1 << x
+                } else {
+                    0
+                };
+            }
+            *row = res;
+        }
 
-            blocks_dropped += 1;
-            if blocks_dropped == blocks_to_drop {
-                break;
+        CaveSequenceState {
+            jet_index: self.jet_index as u16,
+            shape_undex: self.shape_id as u8,
+            rows: cache_rows,
+        }
+    }
+}
+
+fn load_jets(input: &str) -> AocResult<Vec<char>> {
+    let mut jets = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        for (column, c) in line.chars().enumerate() {
+            if c != '<' && c != '>' {
+                return Err(AocError::parse_at(
+                    i,
+                    column,
+                    format!("expected a jet '<' or '>', found '{c}'"),
+                ));
             }
+            jets.push(c);
         }
     }
+    if jets.is_empty() {
+        return Err(AocError::invalid_state("there are no jets"));
+    }
+    Ok(jets)
+}
 
-    Ok(top as u64 + top_mod)
+fn calculate_height(blocks_to_drop: u64, input: &str) -> AocResult<u64> {
+    let jets = load_jets(input)?;
+    let height = extrapolate(
+        Cave::new(),
+        blocks_to_drop as usize,
+        |cave| cave.drop_rock(&jets),
+        Cave::sequence_state,
+        |cave| cave.top as i64,
+    );
+    Ok(height as u64)
 }
 
 fn fetch_shape(shape_id: i32) -> Vec<(i32, i32)> {
@@ -182,14 +196,87 @@ fn fetch_shape(shape_id: i32) -> Vec<(i32, i32)> {
     }
 }
 
-pub fn day_17() -> io::Result<u64> {
-    let result = calculate_height(2022, "./inputs/day-17-input.txt")?;
-    Ok(result)
+pub struct Day17;
+
+impl Solution for Day17 {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        17
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        let result = calculate_height(2022, input)?;
+        Ok(Answer::from(result))
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        let result = calculate_height(1_000_000_000_000, input)?;
+        Ok(Answer::from(result))
+    }
+
+    fn simulate(&self, part: u32, input: &str) -> AocResult<Box<dyn Simulation>> {
+        Ok(Box::new(FallingRocks {
+            cave: Cave::new(),
+            jets: load_jets(input)?,
+            rocks: 0,
+            limit: if part == 1 { 2022 } else { 1_000_000_000_000 },
+        }))
+    }
 }
 
-pub fn day_17_part_2() -> io::Result<u64> {
-    let result = calculate_height(1_000_000_000_000, "./inputs/day-17-input.txt")?;
-    Ok(result)
+/**
+ * Rocks dropping into the cave one at a time, up to the number the part asks for.
+ */
+struct FallingRocks {
+    cave: Cave,
+    jets: Vec<char>,
+    rocks: u64,
+    limit: u64,
+}
+
+impl Simulation for FallingRocks {
+    fn step(&mut self) -> bool {
+        if self.rocks >= self.limit {
+            return false;
+        }
+        self.cave.drop_rock(&self.jets);
+        self.rocks += 1;
+        true
+    }
+
+    fn render(&self) -> Vec<String> {
+        let bottom = (self.cave.top - VIEW_HEIGHT + 1).max(1);
+        let mut rows = (bottom..=self.cave.top)
+            .rev()
+            .map(|y| {
+                let cells = (0..ARENA_WIDTH as i32)
+                    .map(|x| {
+                        if self.cave.arena.contains(&(x, y)) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>();
+                format!("|{cells}|")
+            })
+            .collect::<Vec<_>>();
+        if bottom == 1 {
+            rows.push(format!("+{}+", "-".repeat(ARENA_WIDTH)));
+        }
+        rows
+    }
+
+    fn metrics(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("Rocks", self.rocks.to_string()),
+            ("Height", self.cave.top.to_string()),
+            ("Next jet", self.cave.jet_index.to_string()),
+        ]
+    }
 }
 
 #[cfg(test)]
@@ -199,26 +286,48 @@ mod tests {
 
     #[test]
     fn small_test() {
-        let result = calculate_height(2022, "./inputs/day-17-input-test.txt").unwrap();
+        let result =
+            calculate_height(2022, include_str!("../inputs/day-17-input-test.txt")).unwrap();
         assert_eq!(result, 3068);
     }
 
     #[test]
     fn test() {
-        let result = calculate_height(2022, "./inputs/day-17-input.txt").unwrap();
+        let result = calculate_height(2022, include_str!("../inputs/day-17-input.txt")).unwrap();
         assert_eq!(result, 3193);
     }
 
+    #[test]
+    fn simulate_test() {
+        let mut simulation = Day17
+            .simulate(1, include_str!("../inputs/day-17-input-test.txt"))
+            .unwrap();
+        assert_eq!(simulation.render(), ["+-------+"]);
+        assert!(simulation.step());
+        assert_eq!(simulation.render(), ["|..####.|", "+-------+"]);
+        while simulation.step() {}
+        assert_eq!(simulation.metrics()[1], ("Height", "3068".to_string()));
+        assert_eq!(simulation.render().len(), VIEW_HEIGHT as usize);
+    }
+
     #[test]
     fn part_2_small_test() {
-        let result = calculate_height(1_000_000_000_000, "./inputs/day-17-input-test.txt").unwrap();
+        let result = calculate_height(
+            1_000_000_000_000,
+            include_str!("../inputs/day-17-input-test.txt"),
+        )
+        .unwrap();
         let expected = 1_514_285_714_288;
         assert_eq!(result, expected);
     }
 
     #[test]
     fn part_2_test() {
-        let result = calculate_height(1_000_000_000_000, "./inputs/day-17-input.txt").unwrap();
+        let result = calculate_height(
+            1_000_000_000_000,
+            include_str!("../inputs/day-17-input.txt"),
+        )
+        .unwrap();
         let expected = 1_577_650_429_835;
         assert_eq!(result, expected);
     }
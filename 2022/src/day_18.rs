@@ -1,85 +1,56 @@
-use std::{
-    collections::{HashSet, VecDeque},
-    io,
-};
+use std::collections::{HashSet, VecDeque};
 
 use itertools::Itertools;
 
-use crate::file_utils::read_lines;
+use utils::{
+    coordinate::Coordinate3,
+    error::{AocError, AocResult, LineContext},
+    solution::{Answer, Solution},
+};
 
-fn calculate_exposed_sides(filename: &str) -> io::Result<i32> {
+fn load_cubes(input: &str) -> AocResult<HashSet<Coordinate3>> {
     let mut cubes = HashSet::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
         if let Some((x_s, y_s, z_s)) = line.split(',').collect_tuple() {
-            let x = x_s.parse::<i32>().unwrap();
-            let y = y_s.parse::<i32>().unwrap();
-            let z = z_s.parse::<i32>().unwrap();
+            let x = x_s.parse::<i32>().on_line(i)?;
+            let y = y_s.parse::<i32>().on_line(i)?;
+            let z = z_s.parse::<i32>().on_line(i)?;
 
-            cubes.insert((x, y, z));
+            cubes.insert(Coordinate3::new(x, y, z));
         } else {
-            panic!("Bad input: {line}");
+            return Err(AocError::parse(
+                i,
+                format!("expected a cube 'x,y,z', found '{line}'"),
+            ));
         }
     }
-
-    let mut sides = 0;
-    for (x, y, z) in &cubes {
-        if !cubes.contains(&(x + 1, *y, *z)) {
-            sides += 1;
-        }
-        if !cubes.contains(&(x - 1, *y, *z)) {
-            sides += 1;
-        }
-        if !cubes.contains(&(*x, y + 1, *z)) {
-            sides += 1;
-        }
-        if !cubes.contains(&(*x, *y - 1, *z)) {
-            sides += 1;
-        }
-        if !cubes.contains(&(*x, *y, z + 1)) {
-            sides += 1;
-        }
-        if !cubes.contains(&(*x, *y, z - 1)) {
-            sides += 1;
-        }
+    if cubes.is_empty() {
+        return Err(AocError::invalid_state("there are no cubes"));
     }
-
-    Ok(sides)
+    Ok(cubes)
 }
 
-fn calculate_external_sides(filename: &str) -> io::Result<i32> {
-    let mut cubes = HashSet::new();
-
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
-        if line.is_empty() {
-            continue;
-        }
-
-        if let Some((x_s, y_s, z_s)) = line.split(',').collect_tuple() {
-            let x = x_s.parse::<i32>().unwrap();
-            let y = y_s.parse::<i32>().unwrap();
-            let z = z_s.parse::<i32>().unwrap();
+fn calculate_exposed_sides(input: &str) -> AocResult<i32> {
+    let cubes = load_cubes(input)?;
+    Ok(count_exposed_sides(&cubes))
+}
 
-            cubes.insert((x, y, z));
-        } else {
-            panic!("Bad input: {line}");
-        }
-    }
+fn calculate_external_sides(input: &str) -> AocResult<i32> {
+    let mut cubes = load_cubes(input)?;
 
-    let max_width = cubes.iter().map(|(x, _, _)| x).max().unwrap().to_owned();
-    let min_width = cubes.iter().map(|(x, _, _)| x).min().unwrap().to_owned();
+    let max_width = cubes.iter().map(|c| c.x).max().unwrap();
+    let min_width = cubes.iter().map(|c| c.x).min().unwrap();
 
-    let max_height = cubes.iter().map(|(_, y, _)| y).max().unwrap().to_owned();
-    let min_height = cubes.iter().map(|(_, y, _)| y).min().unwrap().to_owned();
+    let max_height = cubes.iter().map(|c| c.y).max().unwrap();
+    let min_height = cubes.iter().map(|c| c.y).min().unwrap();
 
-    let max_depth = cubes.iter().map(|(_, _, z)| z).max().unwrap().to_owned();
-    let min_depth = cubes.iter().map(|(_, _, z)| z).min().unwrap().to_owned();
+    let max_depth = cubes.iter().map(|c| c.z).max().unwrap();
+    let min_depth = cubes.iter().map(|c| c.z).min().unwrap();
 
     let margin = 5;
     let min = min_width.min(min_height.min(min_depth)) - margin;
@@ -88,57 +59,51 @@ fn calculate_external_sides(filename: &str) -> io::Result<i32> {
     let mut cube_fill = HashSet::new();
     for i in min..max {
         for j in min..max {
-            cubes.insert((i, j, min));
-            cubes.insert((i, j, max));
-            cubes.insert((i, min, j));
-            cubes.insert((i, max, j));
-            cubes.insert((min, i, j));
-            cubes.insert((max, i, j));
+            cubes.insert(Coordinate3::new(i, j, min));
+            cubes.insert(Coordinate3::new(i, j, max));
+            cubes.insert(Coordinate3::new(i, min, j));
+            cubes.insert(Coordinate3::new(i, max, j));
+            cubes.insert(Coordinate3::new(min, i, j));
+            cubes.insert(Coordinate3::new(max, i, j));
         }
     }
 
-    fill_cubes(&mut cube_fill, &cubes, (min + 1, min + 1, min + 1));
+    fill_cubes(
+        &mut cube_fill,
+        &cubes,
+        Coordinate3::new(min + 1, min + 1, min + 1),
+    );
 
     let mut inverse = HashSet::new();
     for i in (min + margin)..=(max - margin) {
         for j in (min + margin)..=(max - margin) {
             for k in (min + margin)..=(max - margin) {
-                if !cube_fill.contains(&(i, j, k)) {
-                    inverse.insert((i, j, k));
+                let cube = Coordinate3::new(i, j, k);
+                if !cube_fill.contains(&cube) {
+                    inverse.insert(cube);
                 }
             }
         }
     }
 
-    let mut sides = 0;
-    for (x, y, z) in &inverse {
-        if !inverse.contains(&(x + 1, *y, *z)) {
-            sides += 1;
-        }
-        if !inverse.contains(&(x - 1, *y, *z)) {
-            sides += 1;
-        }
-        if !inverse.contains(&(*x, y + 1, *z)) {
-            sides += 1;
-        }
-        if !inverse.contains(&(*x, *y - 1, *z)) {
-            sides += 1;
-        }
-        if !inverse.contains(&(*x, *y, z + 1)) {
-            sides += 1;
-        }
-        if !inverse.contains(&(*x, *y, z - 1)) {
-            sides += 1;
-        }
-    }
+    Ok(count_exposed_sides(&inverse))
+}
 
-    Ok(sides)
+/**
+ * Counts the faces of the cubes that aren't touching another cube.
+ */
+fn count_exposed_sides(cubes: &HashSet<Coordinate3>) -> i32 {
+    cubes
+        .iter()
+        .flat_map(Coordinate3::orthogonal)
+        .filter(|c| !cubes.contains(c))
+        .count() as i32
 }
 
 fn fill_cubes(
-    cubes: &mut HashSet<(i32, i32, i32)>,
-    reference: &HashSet<(i32, i32, i32)>,
-    fill_point: (i32, i32, i32),
+    cubes: &mut HashSet<Coordinate3>,
+    reference: &HashSet<Coordinate3>,
+    fill_point: Coordinate3,
 ) {
     let mut stack = VecDeque::new();
     stack.push_back(fill_point);
@@ -146,18 +111,7 @@ fn fill_cubes(
     while let Some(current_fill_point) = stack.pop_front() {
         if !reference.contains(&current_fill_point) && !cubes.contains(&current_fill_point) {
             cubes.insert(current_fill_point);
-            let (x, y, z) = current_fill_point;
-
-            let mut points = Vec::new();
-
-            points.push((x - 1, y, z));
-            points.push((x + 1, y, z));
-            points.push((x, y - 1, z));
-            points.push((x, y + 1, z));
-            points.push((x, y, z - 1));
-            points.push((x, y, z + 1));
-
-            for p in points {
+            for p in current_fill_point.orthogonal() {
                 if !reference.contains(&p) && !cubes.contains(&p) {
                     stack.push_back(p);
                 }
@@ -166,14 +120,26 @@ fn fill_cubes(
     }
 }
 
-pub fn day_18() -> io::Result<i32> {
-    let result = calculate_exposed_sides("./inputs/day-18-input.txt")?;
-    Ok(result)
-}
+pub struct Day18;
+
+impl Solution for Day18 {
+    fn year(&self) -> u32 {
+        2022
+    }
 
-pub fn day_18_part_2() -> io::Result<i32> {
-    let result = calculate_external_sides("./inputs/day-18-input.txt")?;
-    Ok(result)
+    fn day(&self) -> u32 {
+        18
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        let result = calculate_exposed_sides(input)?;
+        Ok(Answer::from(result))
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        let result = calculate_external_sides(input)?;
+        Ok(Answer::from(result))
+    }
 }
 
 #[cfg(test)]
@@ -183,25 +149,27 @@ mod tests {
 
     #[test]
     fn small_test() {
-        let result = calculate_exposed_sides("./inputs/day-18-input-test.txt").unwrap();
+        let result =
+            calculate_exposed_sides(include_str!("../inputs/day-18-input-test.txt")).unwrap();
         assert_eq!(result, 64);
     }
 
     #[test]
     fn test() {
-        let result = calculate_exposed_sides("./inputs/day-18-input.txt").unwrap();
+        let result = calculate_exposed_sides(include_str!("../inputs/day-18-input.txt")).unwrap();
         assert_eq!(result, 3470);
     }
 
     #[test]
     fn part_2_small_test() {
-        let result = calculate_external_sides("./inputs/day-18-input-test.txt").unwrap();
+        let result =
+            calculate_external_sides(include_str!("../inputs/day-18-input-test.txt")).unwrap();
         assert_eq!(result, 58);
     }
 
     #[test]
     fn part_2_test() {
-        let result = calculate_external_sides("./inputs/day-18-input.txt").unwrap();
+        let result = calculate_external_sides(include_str!("../inputs/day-18-input.txt")).unwrap();
         assert_eq!(result, 1986);
     }
 }
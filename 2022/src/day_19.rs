@@ -1,13 +1,14 @@
-use std::{collections::VecDeque, io};
+use std::collections::VecDeque;
 
-use itertools::Itertools;
-
-use crate::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    solution::{Answer, ParsedSolution},
+};
 
 const PART_1_TIME: i32 = 24;
 const PART_2_TIME: i32 = 32;
 
-struct Blueprint {
+pub struct Blueprint {
     pub id: i32,
 
     pub ore_robot_ore_cost: i32,
@@ -23,7 +24,7 @@ struct Blueprint {
 
 impl Blueprint {
     fn max_ore_bots_needed(&self) -> i32 {
-        vec![
+        [
             self.ore_robot_ore_cost,
             self.clay_robot_ore_cost,
             self.obsidian_robot_ore_cost,
@@ -126,11 +127,8 @@ impl State {
      */
     fn max_geodes(&self, total_time: i32) -> i32 {
         let remaining = total_time - self.time;
-        
 
-        (((remaining - 1) * remaining) / 2)
-            + (remaining * self.geode_robots)
-            + self.geode
+        (((remaining - 1) * remaining) / 2) + (remaining * self.geode_robots) + self.geode
     }
 
     fn add_resources(&mut self, times: i32) {
@@ -149,17 +147,24 @@ impl State {
     }
 }
 
-fn load_blueprints(filename: &str) -> io::Result<Vec<Blueprint>> {
+fn load_blueprints(input: &str) -> AocResult<Vec<Blueprint>> {
     let mut blueprints = Vec::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
         // Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
-        let parsed = sscanf::sscanf!(
+        let (
+            id,
+            ore_robot_ore_cost,
+            clay_robot_ore_cost,
+            obsidian_robot_ore_cost,
+            obsidian_robot_clay_cost,
+            geode_robot_ore_cost,
+            geode_robot_obsidian_cost,
+        ) = sscanf::sscanf!(
             line,
             "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.",
             i32,
@@ -169,9 +174,10 @@ fn load_blueprints(filename: &str) -> io::Result<Vec<Blueprint>> {
             i32,
             i32,
             i32
-        );
+        )
+        .on_line(i)?;
 
-        if let Ok((
+        let blueprint = Blueprint {
             id,
             ore_robot_ore_cost,
             clay_robot_ore_cost,
@@ -179,36 +185,23 @@ fn load_blueprints(filename: &str) -> io::Result<Vec<Blueprint>> {
             obsidian_robot_clay_cost,
             geode_robot_ore_cost,
             geode_robot_obsidian_cost,
-        )) = parsed
-        {
-            let blueprint = Blueprint {
-                id,
-                ore_robot_ore_cost,
-                clay_robot_ore_cost,
-                obsidian_robot_ore_cost,
-                obsidian_robot_clay_cost,
-                geode_robot_ore_cost,
-                geode_robot_obsidian_cost,
-            };
-            blueprints.push(blueprint);
-        } else {
-            panic!("Bad input: {line}");
-        }
+        };
+        blueprints.push(blueprint);
     }
     Ok(blueprints)
 }
 
-fn calculate_quality_levels(blueprints: Vec<Blueprint>, total_time: i32) -> i32 {
+fn calculate_quality_levels(blueprints: &[Blueprint], total_time: i32) -> AocResult<i32> {
     let mut result = 0;
     for blueprint in blueprints {
-        let best_state = simulate_blueprint(&blueprint, total_time);
+        let best_state = simulate_blueprint(blueprint, total_time)?;
         result += best_state.quality_level();
     }
 
-    result
+    Ok(result)
 }
 
-fn simulate_blueprint(blueprint: &Blueprint, total_time: i32) -> State {
+fn simulate_blueprint(blueprint: &Blueprint, total_time: i32) -> AocResult<State> {
     let mut start_state = State {
         blueprint_id: blueprint.id,
         ..Default::default()
@@ -338,35 +331,51 @@ fn simulate_blueprint(blueprint: &Blueprint, total_time: i32) -> State {
             stack.push_front(current);
         }
     }
-    if let Some(best_state) = best_state_opt {
-        best_state
-    } else {
-        panic!(
-            "A solution was not found for blueprint {bid}.",
+    best_state_opt.ok_or_else(|| {
+        AocError::invalid_state(format!(
+            "a solution was not found for blueprint {bid}",
             bid = blueprint.id
-        );
-    }
+        ))
+    })
 }
 
-pub fn day_19() -> io::Result<i32> {
-    let blueprints = load_blueprints("./inputs/day-19-input.txt")?;
-    let result = calculate_quality_levels(blueprints, PART_1_TIME);
-    Ok(result)
-}
+pub struct Day19;
 
-pub fn day_19_part_2() -> io::Result<i32> {
-    let blueprints = load_blueprints("./inputs/day-19-input.txt")?;
-    let results = blueprints
-        .iter()
-        .take(3)
-        .map(|f| simulate_blueprint(f, PART_2_TIME).geode)
-        .collect_vec();
-    Ok(results.iter().product())
+impl ParsedSolution for Day19 {
+    type Input = Vec<Blueprint>;
+
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        19
+    }
+
+    fn parse(&self, input: &str) -> AocResult<Vec<Blueprint>> {
+        load_blueprints(input)
+    }
+
+    fn part_1(&self, blueprints: &Vec<Blueprint>) -> AocResult<Answer> {
+        let result = calculate_quality_levels(blueprints, PART_1_TIME)?;
+        Ok(Answer::from(result))
+    }
+
+    fn part_2(&self, blueprints: &Vec<Blueprint>) -> AocResult<Answer> {
+        let results = blueprints
+            .iter()
+            .take(3)
+            .map(|f| simulate_blueprint(f, PART_2_TIME).map(|s| s.geode))
+            .collect::<AocResult<Vec<_>>>()?;
+        Ok(Answer::from(results.iter().product::<i32>()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
+    use itertools::Itertools;
+
     use super::*;
 
     #[test]
@@ -381,39 +390,39 @@ mod tests {
 
     #[test]
     fn single_blueprint_test() {
-        let blueprints = load_blueprints("./inputs/day-19-input-test.txt").unwrap();
-        let best_state = simulate_blueprint(&blueprints[0], PART_1_TIME);
+        let blueprints = load_blueprints(include_str!("../inputs/day-19-input-test.txt")).unwrap();
+        let best_state = simulate_blueprint(&blueprints[0], PART_1_TIME).unwrap();
         assert_eq!(best_state.quality_level(), 9);
     }
 
     #[test]
     fn small_test() {
-        let blueprints = load_blueprints("./inputs/day-19-input-test.txt").unwrap();
-        let result = calculate_quality_levels(blueprints, PART_1_TIME);
+        let blueprints = load_blueprints(include_str!("../inputs/day-19-input-test.txt")).unwrap();
+        let result = calculate_quality_levels(&blueprints, PART_1_TIME).unwrap();
         assert_eq!(result, 33);
     }
 
     #[test]
     fn test() {
-        let blueprints = load_blueprints("./inputs/day-19-input.txt").unwrap();
-        let result = calculate_quality_levels(blueprints, PART_1_TIME);
+        let blueprints = load_blueprints(include_str!("../inputs/day-19-input.txt")).unwrap();
+        let result = calculate_quality_levels(&blueprints, PART_1_TIME).unwrap();
         assert_eq!(result, 1349);
     }
 
     #[test]
     fn part_2_small_test() {
-        let blueprints = load_blueprints("./inputs/day-19-input-test.txt").unwrap();
-        let result = simulate_blueprint(&blueprints[0], PART_2_TIME);
+        let blueprints = load_blueprints(include_str!("../inputs/day-19-input-test.txt")).unwrap();
+        let result = simulate_blueprint(&blueprints[0], PART_2_TIME).unwrap();
         assert_eq!(result.geode, 56);
     }
 
     #[test]
     pub fn part_2_test() {
-        let blueprints = load_blueprints("./inputs/day-19-input.txt").unwrap();
+        let blueprints = load_blueprints(include_str!("../inputs/day-19-input.txt")).unwrap();
         let results = blueprints
             .iter()
             .take(3)
-            .map(|f| simulate_blueprint(f, PART_2_TIME).geode)
+            .map(|f| simulate_blueprint(f, PART_2_TIME).unwrap().geode)
             .collect_vec();
         let result = results.iter().product();
         assert_eq!(21840, result);
@@ -1,10 +1,8 @@
-use std::{
-    io::{self},
-    path::Path,
+use utils::{
+    error::{AocResult, LineContext},
+    solution::{Answer, Solution},
 };
 
-use crate::file_utils::read_lines;
-
 #[derive(Eq, PartialEq, Clone)]
 pub enum Shape {
     Rock,
@@ -13,46 +11,46 @@ pub enum Shape {
 }
 
 impl Shape {
-    pub fn opponent(line: &str) -> Self {
+    pub fn opponent(line: &str) -> Result<Self, String> {
         if line.contains('A') {
-            return Self::Rock;
+            return Ok(Self::Rock);
         } else if line.contains('B') {
-            return Self::Paper;
+            return Ok(Self::Paper);
         } else if line.contains('C') {
-            return Self::Scissors;
+            return Ok(Self::Scissors);
         }
-        panic!("No player symbol.");
+        Err("no opponent symbol".to_string())
     }
 
-    pub fn player(line: &str) -> Self {
+    pub fn player(line: &str) -> Result<Self, String> {
         if line.contains('X') {
-            return Self::Rock;
+            return Ok(Self::Rock);
         } else if line.contains('Y') {
-            return Self::Paper;
+            return Ok(Self::Paper);
         } else if line.contains('Z') {
-            return Self::Scissors;
+            return Ok(Self::Scissors);
         }
-        panic!("No opponent symbol.");
+        Err("no player symbol".to_string())
     }
 
-    pub fn from_result(line: &str, opponent: &Self) -> Self {
+    pub fn from_result(line: &str, opponent: &Self) -> Result<Self, String> {
         // X means you need to lose
         // Y means you need to end the round in a draw
         // Z means you need to win. Good luck!"
 
         // lose
         if line.contains('X') {
-            return opponent.beats();
+            return Ok(opponent.beats());
         }
         // draw
         else if line.contains('Y') {
-            return opponent.clone();
+            return Ok(opponent.clone());
         }
         // wins
         else if line.contains('Z') {
-            return opponent.loses();
+            return Ok(opponent.loses());
         }
-        panic!("Unknown game result.");
+        Err("no game result".to_string())
     }
 
     pub fn score(&self) -> i32 {
@@ -86,27 +84,35 @@ impl Shape {
     }
 }
 
-pub fn day_2() -> io::Result<i32> {
-    game_file_loader("./inputs/day-2-input.txt")
-}
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn year(&self) -> u32 {
+        2022
+    }
 
-pub fn day_2_part_2() -> io::Result<i32> {
-    part_2_game_file_loader("./inputs/day-2-input.txt")
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        game_file_loader(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        part_2_game_file_loader(input).map(Answer::from)
+    }
 }
 
-fn game_file_loader<P>(filename: P) -> io::Result<i32>
-where
-    P: AsRef<Path>,
-{
+fn game_file_loader(input: &str) -> AocResult<i32> {
     let mut score = 0;
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
-        let opponent = Shape::opponent(&line);
-        let player = Shape::player(&line);
+        let opponent = Shape::opponent(line).on_line(i)?;
+        let player = Shape::player(line).on_line(i)?;
 
         score += player.score();
 
@@ -141,20 +147,16 @@ where
     Ok(score)
 }
 
-fn part_2_game_file_loader<P>(filename: P) -> io::Result<i32>
-where
-    P: AsRef<Path>,
-{
+fn part_2_game_file_loader(input: &str) -> AocResult<i32> {
     let mut score = 0;
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
-        let opponent = Shape::opponent(&line);
+        let opponent = Shape::opponent(line).on_line(i)?;
 
-        let player = Shape::from_result(&line, &opponent);
+        let player = Shape::from_result(line, &opponent).on_line(i)?;
 
         score += player.score();
 
@@ -197,7 +199,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            game_file_loader("./inputs/day-2-input-test.txt").unwrap(),
+            game_file_loader(include_str!("../inputs/day-2-input-test.txt")).unwrap(),
             15
         );
     }
@@ -205,18 +207,26 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            part_2_game_file_loader("./inputs/day-2-input-test.txt").unwrap(),
+            part_2_game_file_loader(include_str!("../inputs/day-2-input-test.txt")).unwrap(),
             12
         );
     }
 
     #[test]
     fn test() {
-        assert_eq!(day_2().unwrap(), 17189);
+        assert_eq!(
+            Day2.part_1(include_str!("../inputs/day-2-input.txt"))
+                .unwrap(),
+            Answer::Integer(17189)
+        );
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(day_2_part_2().unwrap(), 13490);
+        assert_eq!(
+            Day2.part_2(include_str!("../inputs/day-2-input.txt"))
+                .unwrap(),
+            Answer::Integer(13490)
+        );
     }
 }
@@ -1,23 +1,30 @@
-use std::{collections::VecDeque, io};
+use std::collections::VecDeque;
 
 use itertools::Itertools;
 
-use crate::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    math::DynMod,
+    solution::{Answer, Solution},
+};
 
 const DECRYPTION_KEY: i64 = 811589153;
 
-fn decrypt(mix_amount: i32, decryption_key: i64, filename: &str) -> io::Result<Vec<i64>> {
+fn decrypt(mix_amount: i32, decryption_key: i64, input: &str) -> AocResult<Vec<i64>> {
     let mut numbers = VecDeque::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
-        let value = line.parse::<i64>().unwrap();
+        let value = line.parse::<i64>().on_line(i)?;
         numbers.push_back(value * decryption_key);
     }
 
+    // A single number has nowhere to move to.
+    if numbers.len() < 2 {
+        return Ok(numbers.into());
+    }
     let mut results = (0..numbers.len()).collect_vec();
 
     // heavily inspired by AxlLind's code.
@@ -25,47 +32,49 @@ fn decrypt(mix_amount: i32, decryption_key: i64, filename: &str) -> io::Result<V
         for (i, x) in numbers.iter().enumerate() {
             let index = results.iter().position(|&r| r == i).unwrap();
             results.remove(index);
-            let mut new_index = index as i64 + x;
-            new_index = new_index.rem_euclid(results.len() as i64);
-
-            debug_assert!(
-                new_index >= 0 && new_index < numbers.len() as i64 + 1,
-                "{new_index}"
-            );
-
-            results.insert(new_index as usize, i as usize);
+            let new_index = DynMod::new(index as i64, results.len() as i64)? + *x;
+            results.insert(new_index.value() as usize, i);
         }
     }
 
     Ok(results.iter().map(|&r| numbers[r]).collect_vec())
 }
 
-fn find_coordinates(mix_amount: i32, decryption_key: i64, filename: &str) -> io::Result<i64> {
-    let data = decrypt(mix_amount, decryption_key, filename)?;
+fn find_coordinates(mix_amount: i32, decryption_key: i64, input: &str) -> AocResult<i64> {
+    let data = decrypt(mix_amount, decryption_key, input)?;
 
-    let length = data.len();
-    let z_position = data.iter().position(|n| *n == 0).unwrap();
+    let z_position = data
+        .iter()
+        .position(|n| *n == 0)
+        .ok_or_else(|| AocError::invalid_state("the file has no 0"))?;
+    let z_position = DynMod::new(z_position as i64, data.len() as i64)?;
 
-    let i1000 = (z_position + 1000) % length;
-    let i2000 = (z_position + 2000) % length;
-    let i3000 = (z_position + 3000) % length;
+    Ok([1000, 2000, 3000]
+        .into_iter()
+        .map(|offset| data[(z_position + offset).value() as usize])
+        .sum())
+}
 
-    let c1000 = data[i1000];
-    let c2000 = data[i2000];
-    let c3000 = data[i3000];
+pub struct Day20;
 
-    let result = c1000 + c2000 + c3000;
-    Ok(result)
-}
+impl Solution for Day20 {
+    fn year(&self) -> u32 {
+        2022
+    }
 
-pub fn day_20() -> io::Result<i64> {
-    let result = find_coordinates(1, 1, "./inputs/day-20-input.txt")?;
-    Ok(result)
-}
+    fn day(&self) -> u32 {
+        20
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        let result = find_coordinates(1, 1, input)?;
+        Ok(Answer::from(result))
+    }
 
-pub fn day_20_part_2() -> io::Result<i64> {
-    let result = find_coordinates(10, DECRYPTION_KEY, "./inputs/day-20-input.txt")?;
-    Ok(result)
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        let result = find_coordinates(10, DECRYPTION_KEY, input)?;
+        Ok(Answer::from(result))
+    }
 }
 
 #[cfg(test)]
@@ -75,20 +84,25 @@ mod tests {
 
     #[test]
     fn small_test() {
-        let result = find_coordinates(1, 1, "./inputs/day-20-input-test.txt").unwrap();
+        let result =
+            find_coordinates(1, 1, include_str!("../inputs/day-20-input-test.txt")).unwrap();
         assert_eq!(3, result);
     }
 
     #[test]
     fn test() {
-        let result = find_coordinates(1, 1, "./inputs/day-20-input.txt").unwrap();
+        let result = find_coordinates(1, 1, include_str!("../inputs/day-20-input.txt")).unwrap();
         assert_eq!(2275, result);
     }
 
     #[test]
     fn part_2_small_test() {
-        let result =
-            find_coordinates(10, DECRYPTION_KEY, "./inputs/day-20-input-test.txt").unwrap();
+        let result = find_coordinates(
+            10,
+            DECRYPTION_KEY,
+            include_str!("../inputs/day-20-input-test.txt"),
+        )
+        .unwrap();
         assert_eq!(1623178306, result);
     }
 }
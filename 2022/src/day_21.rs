@@ -1,8 +1,12 @@
-use std::{collections::HashMap, io};
+use std::collections::HashMap;
 
 use itertools::Itertools;
 
-use crate::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    parse::record,
+    solution::{Answer, Solution},
+};
 
 const ROOT_NAME: &str = "root";
 const HUMAN_NAME: &str = "humn";
@@ -23,73 +27,85 @@ enum Operand {
 }
 
 impl Operand {
-    pub fn from_str(s: &str) -> Self {
+    pub fn from_str(s: &str) -> Result<Self, String> {
         match s {
-            "+" => Self::Plus,
-            "-" => Self::Minus,
-            "*" => Self::Multiply,
-            "/" => Self::Divide,
-            _ => panic!("Unknown operation"),
+            "+" => Ok(Self::Plus),
+            "-" => Ok(Self::Minus),
+            "*" => Ok(Self::Multiply),
+            "/" => Ok(Self::Divide),
+            _ => Err(format!("unknown operation '{s}'")),
         }
     }
 
-    pub fn calc(&self, a: i64, b: i64) -> i64 {
+    /**
+     * Applies the operation, or gives nothing if it overflows or divides by zero.
+     */
+    pub fn calc(&self, a: i64, b: i64) -> Option<i64> {
         match self {
-            Operand::Plus => a + b,
-            Operand::Multiply => a * b,
-            Operand::Minus => a - b,
-            Operand::Divide => a / b,
+            Operand::Plus => a.checked_add(b),
+            Operand::Multiply => a.checked_mul(b),
+            Operand::Minus => a.checked_sub(b),
+            Operand::Divide => a.checked_div(b),
         }
     }
 }
 
-pub fn day_21() -> io::Result<i64> {
-    let result = monkey_puzzle(false, "./inputs/day-21-input.txt")?;
-    Ok(result)
-}
+pub struct Day21;
+
+impl Solution for Day21 {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        21
+    }
 
-pub fn day_21_part_2() -> io::Result<i64> {
-    let result = monkey_puzzle(true, "./inputs/day-21-input.txt")?;
-    Ok(result)
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        let result = monkey_puzzle(false, input)?;
+        Ok(Answer::from(result))
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        let result = monkey_puzzle(true, input)?;
+        Ok(Answer::from(result))
+    }
 }
 
-fn monkey_puzzle(human_player: bool, filename: &str) -> io::Result<i64> {
+fn monkey_puzzle(human_player: bool, input: &str) -> AocResult<i64> {
     let mut monkey_list = HashMap::new();
     let mut known_numbers = HashMap::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
-        let trim = line.trim();
-
-        if let Some((name, operation)) = trim.split(':').collect_tuple() {
-            if human_player && name == HUMAN_NAME {
-                continue;
-            }
+        let (name, operation) = record(line, i)?;
+        if human_player && name == HUMAN_NAME {
+            continue;
+        }
 
-            if operation.contains('+')
-                || operation.contains('-')
-                || operation.contains('*')
-                || operation.contains('/')
-            {
-                if let Some((a, op, b)) = operation.trim().split(' ').collect_tuple() {
-                    let monkey = Monkey {
-                        operation: Operand::from_str(op),
-                        monkey_name_a: a.to_string(),
-                        monkey_name_b: b.to_string(),
-                    };
-                    monkey_list.insert(name.to_string(), monkey);
-                } else {
-                    panic!("Bad input.");
-                }
+        if operation.contains('+')
+            || operation.contains('-')
+            || operation.contains('*')
+            || operation.contains('/')
+        {
+            if let Some((a, op, b)) = operation.split(' ').collect_tuple() {
+                let monkey = Monkey {
+                    operation: Operand::from_str(op).on_line(i)?,
+                    monkey_name_a: a.to_string(),
+                    monkey_name_b: b.to_string(),
+                };
+                monkey_list.insert(name.to_string(), monkey);
             } else {
-                let value = operation.trim().parse::<i64>().unwrap();
-                known_numbers.insert(name.to_string(), value);
+                return Err(AocError::parse(
+                    i,
+                    format!("expected an operation 'a op b', found '{operation}'"),
+                ));
             }
         } else {
-            panic!("Bad input: '{trim}'");
+            let value = operation.parse::<i64>().on_line(i)?;
+            known_numbers.insert(name.to_string(), value);
         }
     }
 
@@ -106,7 +122,10 @@ fn monkey_puzzle(human_player: bool, filename: &str) -> io::Result<i64> {
                 let b_opt = known_numbers.get(&monkey.monkey_name_b);
 
                 if let Some(b) = b_opt {
-                    let result = monkey.operation.calc(*a, *b);
+                    let result = monkey
+                        .operation
+                        .calc(*a, *b)
+                        .ok_or_else(|| unworkable(name))?;
                     known_numbers.insert(name.clone(), result);
 
                     name_to_remove.push(name.clone());
@@ -114,8 +133,13 @@ fn monkey_puzzle(human_player: bool, filename: &str) -> io::Result<i64> {
             }
         }
 
+        if !human_player && name_to_remove.is_empty() {
+            return Err(AocError::invalid_state(
+                "the root monkey's number can never be worked out",
+            ));
+        }
         if human_player && name_to_remove.is_empty() {
-            let human_answer = algebra_human_answer(&mut monkey_list, &mut known_numbers);
+            let human_answer = algebra_human_answer(&mut monkey_list, &mut known_numbers)?;
             known_numbers.insert(HUMAN_NAME.to_string(), human_answer);
         }
 
@@ -124,10 +148,46 @@ fn monkey_puzzle(human_player: bool, filename: &str) -> io::Result<i64> {
         }
     }
 
-    if human_player {
-        Ok(known_numbers[HUMAN_NAME])
-    } else {
-        Ok(known_numbers[ROOT_NAME])
+    let name = if human_player { HUMAN_NAME } else { ROOT_NAME };
+    known_numbers
+        .get(name)
+        .copied()
+        .ok_or_else(|| AocError::invalid_state(format!("there is no '{name}' monkey")))
+}
+
+fn unworkable(name: &str) -> AocError {
+    AocError::invalid_state(format!(
+        "monkey '{name}' can't work out its number, as it overflows or divides by zero"
+    ))
+}
+
+/**
+ * Gets the monkey with the given name, which must be waiting on two others.
+ */
+fn waiting_monkey<'a>(
+    monkey_list: &'a HashMap<String, Monkey>,
+    name: &str,
+) -> AocResult<&'a Monkey> {
+    monkey_list.get(name).ok_or_else(|| {
+        AocError::invalid_state(format!("monkey '{name}' isn't waiting on other monkeys"))
+    })
+}
+
+/**
+ * Gets the number known for one of the two monkeys, along with the name of the other, unknown
+ * one.
+ */
+fn one_known(monkey: &Monkey, known_numbers: &HashMap<String, i64>) -> AocResult<(i64, String)> {
+    match (
+        known_numbers.get(&monkey.monkey_name_a),
+        known_numbers.get(&monkey.monkey_name_b),
+    ) {
+        (Some(&known), None) => Ok((known, monkey.monkey_name_b.clone())),
+        (None, Some(&known)) => Ok((known, monkey.monkey_name_a.clone())),
+        _ => Err(AocError::invalid_state(format!(
+            "expected exactly one of '{}' and '{}' to be known",
+            monkey.monkey_name_a, monkey.monkey_name_b
+        ))),
     }
 }
 
@@ -136,55 +196,37 @@ fn some_solve(
     should_equal: i64,
     source_monkey_list: &mut HashMap<String, Monkey>,
     source_known_numbers: &mut HashMap<String, i64>,
-) {
+) -> AocResult<()> {
     if node_name == HUMAN_NAME {
         source_known_numbers.insert(HUMAN_NAME.to_string(), should_equal);
-        return;
+        return Ok(());
     }
 
-    let node_a = &source_monkey_list[&node_name].monkey_name_a;
-    let node_b = &source_monkey_list[&node_name].monkey_name_b;
-
-    let value_a_opt = source_known_numbers.get(node_a);
-    let value_b_opt = source_known_numbers.get(node_b);
-
-    debug_assert!(
-        !(value_a_opt.is_some() && value_b_opt.is_some()),
-        "Both values are known"
-    );
-    debug_assert!(
-        value_a_opt.is_some() || value_b_opt.is_some(),
-        "Both values are unknown"
-    );
-
-    let known = value_a_opt.unwrap_or_else(|| value_b_opt.unwrap());
-    let new_node_name = if value_a_opt.is_none() {
-        &source_monkey_list[&node_name].monkey_name_a
-    } else {
-        &source_monkey_list[&node_name].monkey_name_b
-    }
-    .to_owned();
+    let monkey = waiting_monkey(source_monkey_list, &node_name)?;
+    let (known, new_node_name) = one_known(monkey, source_known_numbers)?;
+    let a_unknown = new_node_name == monkey.monkey_name_a;
 
     debug_assert!(new_node_name != node_name);
 
-    let new_value = match &source_monkey_list[&node_name].operation {
-        Operand::Plus => should_equal - known,
+    let new_value = match &monkey.operation {
+        Operand::Plus => should_equal.checked_sub(known),
         Operand::Minus => {
-            if value_a_opt.is_none() {
-                should_equal + known
+            if a_unknown {
+                should_equal.checked_add(known)
             } else {
-                known - should_equal
+                known.checked_sub(should_equal)
             }
         }
-        Operand::Multiply => should_equal / known,
+        Operand::Multiply => should_equal.checked_div(known),
         Operand::Divide => {
-            if value_a_opt.is_none() {
-                should_equal * known
+            if a_unknown {
+                should_equal.checked_mul(known)
             } else {
-                known / should_equal
+                known.checked_div(should_equal)
             }
         }
-    };
+    }
+    .ok_or_else(|| unworkable(&node_name))?;
     some_solve(
         new_node_name,
         new_value,
@@ -196,21 +238,15 @@ fn some_solve(
 fn algebra_human_answer(
     source_monkey_list: &mut HashMap<String, Monkey>,
     source_known_numbers: &mut HashMap<String, i64>,
-) -> i64 {
-    let co_a = source_known_numbers.get(&source_monkey_list[ROOT_NAME].monkey_name_a);
-    let co_b = source_known_numbers.get(&source_monkey_list[ROOT_NAME].monkey_name_b);
-
-    let eq = co_a.unwrap_or_else(|| co_b.unwrap()).to_owned();
-
-    let node_name = if co_a.is_none() {
-        &source_monkey_list[ROOT_NAME].monkey_name_a
-    } else {
-        &source_monkey_list[ROOT_NAME].monkey_name_b
-    }
-    .to_owned();
-    some_solve(node_name, eq, source_monkey_list, source_known_numbers);
-
-    source_known_numbers[HUMAN_NAME]
+) -> AocResult<i64> {
+    let root = waiting_monkey(source_monkey_list, ROOT_NAME)?;
+    let (eq, node_name) = one_known(root, source_known_numbers)?;
+    some_solve(node_name, eq, source_monkey_list, source_known_numbers)?;
+
+    source_known_numbers
+        .get(HUMAN_NAME)
+        .copied()
+        .ok_or_else(|| AocError::invalid_state("the human's number can't be worked out"))
 }
 
 #[cfg(test)]
@@ -221,7 +257,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            monkey_puzzle(false, "./inputs/day-21-input-test.txt").unwrap(),
+            monkey_puzzle(false, include_str!("../inputs/day-21-input-test.txt")).unwrap(),
             152
         );
     }
@@ -229,7 +265,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            monkey_puzzle(false, "./inputs/day-21-input.txt").unwrap(),
+            monkey_puzzle(false, include_str!("../inputs/day-21-input.txt")).unwrap(),
             142707821472432
         );
     }
@@ -237,7 +273,7 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            monkey_puzzle(true, "./inputs/day-21-input-test.txt").unwrap(),
+            monkey_puzzle(true, include_str!("../inputs/day-21-input-test.txt")).unwrap(),
             301
         );
     }
@@ -245,8 +281,25 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            monkey_puzzle(true, "./inputs/day-21-input.txt").unwrap(),
+            monkey_puzzle(true, include_str!("../inputs/day-21-input.txt")).unwrap(),
             3587647562851
         );
     }
+
+    #[test]
+    fn divide_by_zero_test() {
+        let input = include_str!("../inputs/day-21-input-test.txt").replace("lfqf: 4", "lfqf: 0");
+        assert!(matches!(
+            monkey_puzzle(false, &input),
+            Err(AocError::InvalidState(message)) if message.contains("'pppw'")
+        ));
+        // Working back from root, pppw has to be 0, which 4 can't be divided by to give it.
+        let input = include_str!("../inputs/day-21-input-test.txt")
+            .replace("pppw: cczh / lfqf", "pppw: lfqf / cczh")
+            .replace("sjmn: drzm * dbpl", "sjmn: drzm * zero\nzero: 0");
+        assert!(matches!(
+            monkey_puzzle(true, &input),
+            Err(AocError::InvalidState(message)) if message.contains("'pppw'")
+        ));
+    }
 }
@@ -1,22 +1,34 @@
-use std::{cmp::Ordering, collections::HashMap, io};
+use std::{cmp::Ordering, collections::HashMap};
 
-use crate::file_utils::read_lines;
+use utils::{
+    coordinate::{Coordinate, Direction4},
+    error::{AocError, AocResult, LineContext},
+    parse::tokens,
+    solution::{Answer, Solution},
+};
 
 type Map = HashMap<i32, HashMap<i32, MapSection>>;
 
-const NORTH_INDEX: usize = 0;
-const EAST_INDEX: usize = 1;
-const SOUTH_INDEX: usize = 2;
-const WEST_INDEX: usize = 3;
+pub struct Day22;
 
-pub fn day_22() -> io::Result<i32> {
-    let result = map_puzzle("./inputs/day-22-input.txt")?;
-    Ok(result)
-}
+impl Solution for Day22 {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        22
+    }
 
-pub fn day_22_part_2() -> io::Result<i32> {
-    let result = cube_puzzle("./inputs/day-22-input.txt")?;
-    Ok(result)
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        let result = map_puzzle(input)?;
+        Ok(Answer::from(result))
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        let result = cube_puzzle(input)?;
+        Ok(Answer::from(result))
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Copy)]
@@ -32,14 +44,6 @@ enum Direction {
     None,
 }
 
-#[derive(Debug)]
-enum Facing {
-    North,
-    East,
-    South,
-    West,
-}
-
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 enum Orientation {
     Same,
@@ -49,16 +53,15 @@ enum Orientation {
 }
 
 impl Orientation {
+    const ALL: [Orientation; 4] = [
+        Orientation::Same,
+        Orientation::OneClockwise,
+        Orientation::TwoClockwise,
+        Orientation::ThreeClockwise,
+    ];
+
     fn combine(self, a: &Orientation) -> Orientation {
-        let combined = (self.orientation_as_number() + a.orientation_as_number()) % 4;
-
-        match combined {
-            0 => Orientation::Same,
-            1 => Orientation::OneClockwise,
-            2 => Orientation::TwoClockwise,
-            3 => Orientation::ThreeClockwise,
-            _ => panic!("Bad orientation number: {combined}"),
-        }
+        Orientation::ALL[(self.orientation_as_number() + a.orientation_as_number()) % 4]
     }
 
     fn orientation_as_number(&self) -> usize {
@@ -70,41 +73,27 @@ impl Orientation {
         }
     }
 
-    fn resolve(&self, direction: usize) -> usize {
-        (self.orientation_as_number() + direction) % 4
+    /**
+     * The orientation that undoes this one.
+     */
+    fn inverse(self) -> Orientation {
+        Orientation::ALL[(4 - self.orientation_as_number()) % 4]
     }
-}
-
-fn opposite_direction(direction: usize) -> usize {
-    (direction + 2) % 4
-}
 
-impl Facing {
-    fn rotate_left(&self) -> Facing {
-        match self {
-            Facing::North => Facing::West,
-            Facing::East => Facing::North,
-            Facing::South => Facing::East,
-            Facing::West => Facing::South,
-        }
-    }
-
-    fn rotate_right(&self) -> Facing {
-        match self {
-            Facing::North => Facing::East,
-            Facing::East => Facing::South,
-            Facing::South => Facing::West,
-            Facing::West => Facing::North,
-        }
+    /**
+     * Turns a direction clockwise by this orientation.
+     */
+    fn rotate(self, direction: Direction4) -> Direction4 {
+        (0..self.orientation_as_number()).fold(direction, |direction, _| direction.turn_right())
     }
+}
 
-    fn fetch_digit(&self) -> i32 {
-        match self {
-            Facing::North => 3,
-            Facing::East => 0,
-            Facing::South => 1,
-            Facing::West => 2,
-        }
+fn fetch_facing_digit(facing: Direction4) -> i32 {
+    match facing {
+        Direction4::North => 3,
+        Direction4::East => 0,
+        Direction4::South => 1,
+        Direction4::West => 2,
     }
 }
 
@@ -141,6 +130,7 @@ struct CubeFace {
     x: i32,
     y: i32,
 
+    // indexed by `Direction4 as usize`
     connections: [Option<CubeFaceConnection>; 4],
 }
 
@@ -157,6 +147,25 @@ impl CubeFace {
     fn connection_count(&self) -> usize {
         self.connections.iter().filter(|c| c.is_some()).count()
     }
+
+    fn connection(&self, direction: Direction4) -> Option<CubeFaceConnection> {
+        self.connections[direction as usize]
+    }
+
+    /**
+     * Finds which side of this face touches another face.
+     */
+    fn direction_to(&self, face_id: usize) -> AocResult<Direction4> {
+        Direction4::ALL
+            .into_iter()
+            .find(|direction| {
+                self.connection(*direction)
+                    .is_some_and(|c| c.cube_face_id == face_id)
+            })
+            .ok_or_else(|| {
+                AocError::invalid_state(format!("face {} does not touch face {face_id}", self.id))
+            })
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -166,59 +175,30 @@ struct CubeFaceConnection {
     orientation: Orientation,
 }
 
-#[derive(Debug)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn calc_heading(direction: usize) -> Point {
-        match (direction + 4) % 4 {
-            0 => Point { x: 0, y: -1 },
-            1 => Point { x: 1, y: 0 },
-            2 => Point { x: 0, y: 1 },
-            3 => Point { x: -1, y: 0 },
-            _ => panic!("Unknown direction: {direction}"),
-        }
-    }
-}
-
-fn normal_direction(direction: usize) -> (usize, usize) {
-    match direction % 4 {
-        NORTH_INDEX | SOUTH_INDEX => (WEST_INDEX, EAST_INDEX),
-        EAST_INDEX | WEST_INDEX => (NORTH_INDEX, SOUTH_INDEX),
-        _ => panic!("Unknown direction: {direction}"),
-    }
-}
-
-fn load_map(filename: &str) -> io::Result<(Map, Vec<Instruction>)> {
+fn load_map(input: &str) -> AocResult<(Map, Vec<Instruction>)> {
     let mut map: Map = HashMap::new();
     let mut instructions = Vec::new();
-    let lines = read_lines(filename)?;
-    for (y, line) in lines.flatten().enumerate() {
+    for (y, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
         if line.contains('L') || line.contains('R') {
-            let mut hacked_line = line.clone();
-            hacked_line = hacked_line.replace('L', ",L").replace('R', ",R");
-            let directions = hacked_line.split(&[',']);
-
-            for direction in directions {
-                let steps_str = direction.trim_start_matches(['L', 'R', 'N']);
-                let steps = steps_str.parse::<i32>().unwrap();
-                instructions.push(Instruction {
-                    steps,
-                    direction: if direction.contains('L') {
-                        Direction::Left
-                    } else if direction.contains('R') {
-                        Direction::Right
-                    } else {
-                        Direction::None
-                    },
-                });
+            // Each turn applies to the steps that follow it. The first steps have no turn.
+            let mut direction = Direction::None;
+            for token in tokens(line) {
+                match token {
+                    "L" => direction = Direction::Left,
+                    "R" => direction = Direction::Right,
+                    _ => {
+                        let steps = token.parse::<i32>().on_line(y)?;
+                        instructions.push(Instruction { steps, direction });
+                        direction = Direction::None;
+                    }
+                }
+            }
+            if !matches!(direction, Direction::None) {
+                return Err(AocError::parse(y, "expected steps after the last turn"));
             }
             continue;
         }
@@ -229,7 +209,13 @@ fn load_map(filename: &str) -> io::Result<(Map, Vec<Instruction>)> {
                 ' ' => None,
                 '.' => Some(MapSection::Floor),
                 '#' => Some(MapSection::Wall),
-                other => panic!("Unknown input {other}"),
+                other => {
+                    return Err(AocError::parse_at(
+                        y,
+                        x,
+                        format!("unknown map tile '{other}'"),
+                    ))
+                }
             };
             if let Some(section) = section_opt {
                 map_line.insert(x as i32, section);
@@ -240,21 +226,23 @@ fn load_map(filename: &str) -> io::Result<(Map, Vec<Instruction>)> {
     Ok((map, instructions))
 }
 
-fn calc_3d_map_size(map: &Map) -> i32 {
+fn calc_3d_map_size(map: &Map) -> AocResult<i32> {
     // calculate area of a single face
-    let area = map.iter().map(|(_, line)| line.len()).sum::<usize>() as i32 / 6;
+    let area = map.values().map(|line| line.len()).sum::<usize>() as i32 / 6;
 
     // integer square root we have at home
     for i in 1..=4096 {
         if i * i == area {
-            return i;
+            return Ok(i);
         }
     }
-    panic!("Unable to find a integer square area for map.");
+    Err(AocError::invalid_state(
+        "the map does not fold into a cube of whole tiles",
+    ))
 }
 
-fn build_graph(map: &Map) -> MapGraph {
-    let size = calc_3d_map_size(map);
+fn build_graph(map: &Map) -> AocResult<MapGraph> {
+    let size = calc_3d_map_size(map)?;
 
     // construct faces
     let mut faces: Vec<CubeFace> = Vec::new();
@@ -281,13 +269,14 @@ fn build_graph(map: &Map) -> MapGraph {
                         cube_face_id: up_face_id,
                         orientation: Orientation::Same,
                     };
-                    face.connections[NORTH_INDEX] = Some(connection);
+                    face.connections[Direction4::North as usize] = Some(connection);
 
                     let partner_connection = CubeFaceConnection {
                         cube_face_id: face.id,
                         orientation: Orientation::Same,
                     };
-                    faces[up_face_id].connections[SOUTH_INDEX] = Some(partner_connection);
+                    faces[up_face_id].connections[Direction4::South as usize] =
+                        Some(partner_connection);
                 }
 
                 if let Some(left_face_id) = left_face_id_opt {
@@ -295,13 +284,14 @@ fn build_graph(map: &Map) -> MapGraph {
                         cube_face_id: left_face_id,
                         orientation: Orientation::Same,
                     };
-                    face.connections[WEST_INDEX] = Some(connection);
+                    face.connections[Direction4::West as usize] = Some(connection);
 
                     let partner_connection = CubeFaceConnection {
                         cube_face_id: face.id,
                         orientation: Orientation::Same,
                     };
-                    faces[left_face_id].connections[EAST_INDEX] = Some(partner_connection);
+                    faces[left_face_id].connections[Direction4::East as usize] =
+                        Some(partner_connection);
                 }
 
                 faces.push(face);
@@ -321,100 +311,47 @@ fn build_graph(map: &Map) -> MapGraph {
                 continue;
             }
             let mut face_resolved = false;
-            for direction_index in 0..map_graph.faces[face_id].connections.len() {
-                if face_resolved || map_graph.faces[face_id].connections[direction_index].is_some()
-                {
+            for direction in Direction4::ALL {
+                if face_resolved || map_graph.faces[face_id].connection(direction).is_some() {
                     continue;
                 }
 
-                // look for faces next to the missing direction
-                let candidate_direction = normal_direction(direction_index);
-
-                // are either tiles linked?
-                let linked_directions = map_graph.faces[face_id]
-                    .connections
-                    .iter()
-                    .enumerate()
-                    .filter(|(i, c)| {
-                        (*c).is_some()
-                            && (*i == candidate_direction.0 || *i == candidate_direction.1)
+                // look for faces next to the missing direction, which are either side of it
+                let linked_connections = Direction4::ALL
+                    .into_iter()
+                    .filter(|linked| {
+                        *linked == direction.turn_left() || *linked == direction.turn_right()
                     })
-                    .map(|(i, _)| i);
-
-                for linked_direction_index in linked_directions {
-                    let linked_orientation = map_graph.faces[face_id].connections
-                        [linked_direction_index]
-                        .unwrap()
-                        .orientation;
-                    let resolved_index = match linked_orientation {
-                        Orientation::TwoClockwise => linked_orientation.resolve(direction_index),
-                        Orientation::Same => direction_index,
-                        _ => opposite_direction(linked_orientation.resolve(direction_index)),
+                    .filter_map(|linked| map_graph.faces[face_id].connection(linked));
+
+                for linked_connection in linked_connections {
+                    let linked_orientation = linked_connection.orientation;
+                    let resolved_direction = match linked_orientation {
+                        Orientation::TwoClockwise => linked_orientation.rotate(direction),
+                        Orientation::Same => direction,
+                        _ => linked_orientation.rotate(direction).opposite(),
                     };
 
-                    let via_id = map_graph.faces[face_id].connections[linked_direction_index]
-                        .unwrap()
-                        .cube_face_id;
+                    let via_id = linked_connection.cube_face_id;
 
                     if let Some(target_connection) =
-                        map_graph.faces[via_id].connections[resolved_index]
+                        map_graph.faces[via_id].connection(resolved_direction)
                     {
                         if face_id != target_connection.cube_face_id {
                             debug_assert_ne!(via_id, target_connection.cube_face_id);
                             debug_assert_ne!(via_id, face_id);
                             debug_assert_ne!(face_id, target_connection.cube_face_id);
 
-                            let mut dx = 0;
-                            let mut dy = 0;
-
                             let target_direction = map_graph.faces[via_id]
-                                .connections
-                                .iter()
-                                .enumerate()
-                                .filter(|(_, c)| {
-                                    c.is_some()
-                                        && c.unwrap().cube_face_id == target_connection.cube_face_id
-                                })
-                                .map(|(i, _)| i)
-                                .next()
-                                .unwrap();
-
-                            let source_direction = map_graph.faces[face_id]
-                                .connections
-                                .iter()
-                                .enumerate()
-                                .filter(|(_, c)| c.is_some() && c.unwrap().cube_face_id == via_id)
-                                .map(|(i, _)| i)
-                                .next()
-                                .unwrap();
-
-                            let resolved_target_direction = map_graph.faces[face_id].connections
-                                [linked_direction_index]
-                                .unwrap()
-                                .orientation
-                                .resolve(target_direction);
-                            let resolved_source_direction = source_direction;
-
-                            let directions =
-                                vec![resolved_target_direction, resolved_source_direction];
-
-                            for direction in directions {
-                                match direction {
-                                    NORTH_INDEX => {
-                                        dy -= 1;
-                                    }
-                                    SOUTH_INDEX => {
-                                        dy += 1;
-                                    }
-                                    EAST_INDEX => {
-                                        dx += 1;
-                                    }
-                                    WEST_INDEX => {
-                                        dx -= 1;
-                                    }
-                                    _ => panic!("Unknown direction {direction}"),
-                                }
-                            }
+                                .direction_to(target_connection.cube_face_id)?;
+                            let source_direction = map_graph.faces[face_id].direction_to(via_id)?;
+
+                            let resolved_target_direction =
+                                linked_orientation.rotate(target_direction);
+
+                            let delta = resolved_target_direction.offset::<i32>()
+                                + source_direction.offset();
+                            let (dx, dy) = (delta.x, delta.y);
 
                             debug_assert!(dx == -1 || dx == 1, "dx: {dx}");
                             debug_assert!(dy == -1 || dy == 1, "dy: {dy}");
@@ -424,7 +361,7 @@ fn build_graph(map: &Map) -> MapGraph {
                                 orientation = Orientation::ThreeClockwise;
                             }
 
-                            if (direction_index % 2) != 0 {
+                            if matches!(direction, Direction4::East | Direction4::West) {
                                 // is horizontal
 
                                 if orientation == Orientation::OneClockwise {
@@ -434,23 +371,17 @@ fn build_graph(map: &Map) -> MapGraph {
                                 }
                             }
 
-                            let source_orientation = map_graph.faces[face_id].connections
-                                [linked_direction_index]
-                                .unwrap()
-                                .orientation;
                             let orientation = orientation
-                                .combine(&source_orientation)
+                                .combine(&linked_orientation)
                                 .combine(&target_connection.orientation);
 
-                            debug_assert!(
-                                map_graph.faces[face_id].connections[direction_index].is_none()
-                            );
+                            debug_assert!(map_graph.faces[face_id].connection(direction).is_none());
 
                             let connection = CubeFaceConnection {
                                 cube_face_id: target_connection.cube_face_id,
                                 orientation,
                             };
-                            map_graph.faces[face_id].connections[direction_index] =
+                            map_graph.faces[face_id].connections[direction as usize] =
                                 Some(connection);
 
                             face_resolved = true;
@@ -462,54 +393,48 @@ fn build_graph(map: &Map) -> MapGraph {
         }
 
         if connection_count == map_graph.connection_count() {
-            panic!("No new connection. {connection_count}");
+            return Err(AocError::invalid_state(format!(
+                "the faces stop connecting after {connection_count} connections"
+            )));
         }
     }
 
-    map_graph
+    Ok(map_graph)
 }
 
-fn map_puzzle(filename: &str) -> io::Result<i32> {
-    let (map, instructions) = load_map(filename)?;
+fn map_puzzle(input: &str) -> AocResult<i32> {
+    let (map, instructions) = load_map(input)?;
 
     // start is left most top tile
 
     let mut x = map
         .get(&0)
-        .unwrap()
-        .keys()
-        .into_iter()
-        .min()
-        .unwrap()
+        .and_then(|line| line.keys().min())
+        .ok_or_else(|| AocError::invalid_state("the top row of the map is empty"))?
         .to_owned();
     let mut y = 0;
-    let mut facing = Facing::East;
+    let mut facing = Direction4::East;
 
     for instruction in instructions {
         facing = match instruction.direction {
-            Direction::Left => facing.rotate_left(),
-            Direction::Right => facing.rotate_right(),
+            Direction::Left => facing.turn_left(),
+            Direction::Right => facing.turn_right(),
             Direction::None => facing,
         };
 
         for _ in 0..instruction.steps {
-            let delta = match facing {
-                Facing::North => (0, -1),
-                Facing::East => (1, 0),
-                Facing::South => (0, 1),
-                Facing::West => (-1, 0),
-            };
-            let mut new_x = x + delta.0;
-            let mut new_y = y + delta.1;
+            let delta = facing.offset::<i32>();
+            let mut new_x = x + delta.x;
+            let mut new_y = y + delta.y;
 
             // do a wrap around
             if fetch_tile(&map, new_x, new_y).is_none() {
-                new_x = match delta.0.cmp(&0) {
+                new_x = match delta.x.cmp(&0) {
                     Ordering::Greater => min_x(&map, new_y),
                     Ordering::Less => max_x(&map, new_y),
                     Ordering::Equal => new_x,
                 };
-                new_y = match delta.1.cmp(&0) {
+                new_y = match delta.y.cmp(&0) {
                     Ordering::Greater => min_y(&map, new_x),
                     Ordering::Less => max_y(&map, new_x),
                     Ordering::Equal => new_y,
@@ -527,68 +452,69 @@ fn map_puzzle(filename: &str) -> io::Result<i32> {
                     }
                 }
             } else {
-                panic!("Could not fetch tile with wrapped coords {new_x}, {new_y}")
+                return Err(AocError::invalid_state(format!(
+                    "could not fetch tile with wrapped coords {new_x}, {new_y}"
+                )));
             }
         }
     }
 
     // The final password is the sum of 1000 times the row, 4 times the column, and the facing.
-    Ok(1000 * (y + 1) + 4 * (x + 1) + facing.fetch_digit())
+    Ok(1000 * (y + 1) + 4 * (x + 1) + fetch_facing_digit(facing))
 }
 
-fn cube_puzzle(file_path: &str) -> io::Result<i32> {
-    let (map, instructions) = load_map(file_path)?;
+fn cube_puzzle(input: &str) -> AocResult<i32> {
+    let (map, instructions) = load_map(input)?;
 
-    let cube = build_graph(&map);
+    let cube = build_graph(&map)?;
 
     // start location is top left face, which is always the first
     let mut x = cube.faces[0].x;
     let mut y = cube.faces[0].y;
 
-    let mut direction = EAST_INDEX;
+    let mut direction = Direction4::East;
     let mut face_id = cube.faces[0].id;
 
     for instruction in instructions {
-        if instruction.direction == Direction::Left {
-            direction = direction.wrapping_sub(1);
-        } else if instruction.direction == Direction::Right {
-            direction += 1;
-        }
-        direction %= 4;
-        let mut heading = Point::calc_heading(direction);
+        direction = match instruction.direction {
+            Direction::Left => direction.turn_left(),
+            Direction::Right => direction.turn_right(),
+            Direction::None => direction,
+        };
 
         for _ in 0..instruction.steps {
-            let mut new_x = x + heading.x;
-            let mut new_y = y + heading.y;
+            let mut new_position = Coordinate::new(x, y).step(direction, 1);
 
             let mut new_face_id = face_id;
             let mut new_direction = direction;
-            let mut new_heading = heading;
 
-            if Some(face_id) != cube.fetch_face_id_at_location(new_x, new_y) {
+            if Some(face_id) != cube.fetch_face_id_at_location(new_position.x, new_position.y) {
                 // just moved face. hold onto your butts
                 // FetchFaceAtLocation is not reliable until coords have been resolved
 
-                let connection = cube.faces[face_id].connections[direction].unwrap();
+                let connection = cube.faces[face_id].connection(direction).ok_or_else(|| {
+                    AocError::invalid_state(format!(
+                        "face {face_id} has no neighbour to the {direction:?}"
+                    ))
+                })?;
                 new_face_id = connection.cube_face_id;
 
-                let mut pre_rot_x = new_x - cube.faces[face_id].x;
-                let mut pre_rot_y = new_y - cube.faces[face_id].y;
+                let mut pre_rot_x = new_position.x - cube.faces[face_id].x;
+                let mut pre_rot_y = new_position.y - cube.faces[face_id].y;
 
                 match direction {
-                    NORTH_INDEX => {
+                    Direction4::North => {
                         pre_rot_y = cube.size - 1;
                     }
-                    SOUTH_INDEX => {
+                    Direction4::South => {
                         pre_rot_y = 0;
                     }
-                    WEST_INDEX => {
+                    Direction4::West => {
                         pre_rot_x = cube.size - 1;
                     }
-                    EAST_INDEX => {
+                    Direction4::East => {
                         pre_rot_x = 0;
                     }
-                    _ => panic!("Unexpected direction {direction}"),
                 }
                 debug_assert!(pre_rot_x >= 0 && pre_rot_x < cube.size);
                 debug_assert!(pre_rot_y >= 0 && pre_rot_y < cube.size);
@@ -597,8 +523,8 @@ fn cube_puzzle(file_path: &str) -> io::Result<i32> {
                 let mut rot_y = pre_rot_y;
 
                 // now do a rotation
-                // maybe plus 1
-                for _ in 0..((4 - connection.orientation.orientation_as_number()) % 4) {
+                let rotation = connection.orientation.inverse();
+                for _ in 0..rotation.orientation_as_number() {
                     let tx = rot_x;
                     let ty = rot_y;
 
@@ -609,22 +535,24 @@ fn cube_puzzle(file_path: &str) -> io::Result<i32> {
                 debug_assert!(rot_x >= 0 && rot_x < cube.size);
                 debug_assert!(rot_y >= 0 && rot_y < cube.size);
 
-                new_x = rot_x + cube.faces[new_face_id].x;
-                new_y = rot_y + cube.faces[new_face_id].y;
-
-                new_direction =
-                    (direction + (4 - connection.orientation.orientation_as_number())) % 4;
-                new_heading = Point::calc_heading(new_direction);
+                new_position = Coordinate::new(
+                    rot_x + cube.faces[new_face_id].x,
+                    rot_y + cube.faces[new_face_id].y,
+                );
+                new_direction = rotation.rotate(direction);
             }
 
-            let tile = *fetch_tile(&map, new_x, new_y).unwrap();
+            let tile = *fetch_tile(&map, new_position.x, new_position.y).ok_or_else(|| {
+                AocError::invalid_state(format!(
+                    "could not fetch tile with folded coords {new_position}"
+                ))
+            })?;
 
             if tile == MapSection::Floor {
-                x = new_x;
-                y = new_y;
+                x = new_position.x;
+                y = new_position.y;
 
                 direction = new_direction;
-                heading = new_heading;
                 face_id = new_face_id;
             } else {
                 break;
@@ -632,11 +560,7 @@ fn cube_puzzle(file_path: &str) -> io::Result<i32> {
         }
     }
 
-    // get score
-    // convert direction to right = 0
-    let score_direction = direction.wrapping_sub(1) % 4;
-    let score = score_direction as i32 + (4 * (x + 1)) + (1000 * (y + 1));
-    Ok(score)
+    Ok(1000 * (y + 1) + 4 * (x + 1) + fetch_facing_digit(direction))
 }
 
 fn fetch_tile(map: &Map, x: i32, y: i32) -> Option<&MapSection> {
@@ -647,23 +571,11 @@ fn fetch_tile(map: &Map, x: i32, y: i32) -> Option<&MapSection> {
 }
 
 fn min_x(map: &Map, y: i32) -> i32 {
-    map.get(&y)
-        .unwrap()
-        .keys()
-        .into_iter()
-        .min()
-        .unwrap()
-        .to_owned()
+    map.get(&y).unwrap().keys().min().unwrap().to_owned()
 }
 
 fn max_x(map: &Map, y: i32) -> i32 {
-    map.get(&y)
-        .unwrap()
-        .keys()
-        .into_iter()
-        .max()
-        .unwrap()
-        .to_owned()
+    map.get(&y).unwrap().keys().max().unwrap().to_owned()
 }
 
 fn min_y(map: &Map, x: i32) -> i32 {
@@ -693,28 +605,34 @@ mod tests {
 
     #[test]
     fn small_test() {
-        assert_eq!(map_puzzle("./inputs/day-22-input-test.txt").unwrap(), 6032);
+        assert_eq!(
+            map_puzzle(include_str!("../inputs/day-22-input-test.txt")).unwrap(),
+            6032
+        );
     }
 
     #[test]
     fn test() {
-        assert_eq!(map_puzzle("./inputs/day-22-input.txt").unwrap(), 103224);
+        assert_eq!(
+            map_puzzle(include_str!("../inputs/day-22-input.txt")).unwrap(),
+            103224
+        );
     }
 
     #[test]
     fn calc_3d_map_size_test() {
-        let (small_map, _) = load_map("./inputs/day-22-input-test.txt").unwrap();
-        let (map, _) = load_map("./inputs/day-22-input.txt").unwrap();
+        let (small_map, _) = load_map(include_str!("../inputs/day-22-input-test.txt")).unwrap();
+        let (map, _) = load_map(include_str!("../inputs/day-22-input.txt")).unwrap();
 
-        assert_eq!(4, calc_3d_map_size(&small_map));
-        assert_eq!(50, calc_3d_map_size(&map));
+        assert_eq!(4, calc_3d_map_size(&small_map).unwrap());
+        assert_eq!(50, calc_3d_map_size(&map).unwrap());
     }
 
     #[test]
     fn build_graph_test() {
-        let (small_map, _) = load_map("./inputs/day-22-input-test.txt").unwrap();
+        let (small_map, _) = load_map(include_str!("../inputs/day-22-input-test.txt")).unwrap();
 
-        let graph = build_graph(&small_map);
+        let graph = build_graph(&small_map).unwrap();
 
         assert_eq!(4, graph.size);
         assert_eq!(6, graph.faces.len());
@@ -867,17 +785,23 @@ mod tests {
 
     #[test]
     fn part_2_small_test() {
-        assert_eq!(5031, cube_puzzle("./inputs/day-22-input-test.txt").unwrap());
+        assert_eq!(
+            5031,
+            cube_puzzle(include_str!("../inputs/day-22-input-test.txt")).unwrap()
+        );
     }
 
     #[test]
     fn part_2_test() {
-        assert_eq!(189097, cube_puzzle("./inputs/day-22-input.txt").unwrap());
+        assert_eq!(
+            189097,
+            cube_puzzle(include_str!("../inputs/day-22-input.txt")).unwrap()
+        );
     }
 
     #[test]
     fn instruction_parse_test() {
-        let (_, instructions) = load_map("./inputs/day-22-input-test.txt").unwrap();
+        let (_, instructions) = load_map(include_str!("../inputs/day-22-input-test.txt")).unwrap();
 
         // 10R5L5R10L4R5L5
         assert_eq!(7, instructions.len());
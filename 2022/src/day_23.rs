@@ -1,131 +1,186 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    io,
+use std::collections::HashSet;
+
+use utils::{
+    automaton::{Automaton, Movers},
+    coordinate::{Coordinate, Direction4},
+    error::{AocError, AocResult},
+    simulation::Simulation,
+    solution::{Answer, Solution},
 };
 
-use crate::file_utils::read_lines;
+type Map = HashSet<Coordinate>;
 
-type Map = HashSet<(i32, i32)>;
+type ElfMovers = Movers<fn(Coordinate, &Map, usize) -> Coordinate>;
 
-fn load_map(filename: &str) -> io::Result<Map> {
+fn load_map(input: &str) -> AocResult<Map> {
     let mut map = Map::new();
 
-    let lines = read_lines(filename)?;
-    for (y, line) in lines.flatten().enumerate() {
+    for (y, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
         for (x, location) in line.chars().enumerate() {
-            if location == '#' {
-                map.insert((x as i32, y as i32));
+            match location {
+                '#' => {
+                    map.insert(Coordinate::new(x as i32, y as i32));
+                }
+                '.' => {}
+                other => return Err(AocError::parse_at(y, x, format!("unknown tile '{other}'"))),
             }
         }
     }
+    if map.is_empty() {
+        return Err(AocError::invalid_state("there are no elves"));
+    }
 
     Ok(map)
 }
 
-fn calculate_elves(steps: i32, filename: &str) -> io::Result<(i32, i32)> {
-    let mut map = load_map(filename)?;
-
-    let mut direction_order = VecDeque::from(vec![(0, -1), (0, 1), (-1, 0), (1, 0)]);
-
-    let mut steps_completed = 0;
-
-    for _ in 0..(steps as usize) {
-        steps_completed += 1;
-        let mut position_candidates: HashMap<(i32, i32), (i32, i32)> = HashMap::new();
-        let mut changes = 0;
-        for elf in &map {
-            let mut candidate = *elf;
-
-            // check surrounds
-            let mut surrounding_elves =
-                map.contains(&(elf.0 - 1, elf.1)) || map.contains(&(elf.0 + 1, elf.1));
-            if !surrounding_elves {
-                for x in elf.0 - 1..=elf.0 + 1 {
-                    if map.contains(&(x, elf.1 - 1)) || map.contains(&(x, elf.1 + 1)) {
-                        surrounding_elves = true;
-                        break;
-                    }
-                }
-            }
+/**
+ * Directions the elves consider moving in, in the order they consider them on the first round.
+ * Each round starts one further along.
+ */
+const DIRECTION_ORDER: [Direction4; 4] = [
+    Direction4::North,
+    Direction4::South,
+    Direction4::West,
+    Direction4::East,
+];
+
+fn propose_move(elf: Coordinate, elves: &HashSet<Coordinate>, round: usize) -> Coordinate {
+    if elf.surround().iter().all(|c| !elves.contains(c)) {
+        return elf;
+    }
 
-            if surrounding_elves {
-                for direction in &direction_order {
-                    let new_position = (elf.0 + direction.0, elf.1 + direction.1);
-                    let side_1;
-                    let side_2;
-
-                    if direction.0 == 0 {
-                        side_1 = (new_position.0 - 1, new_position.1);
-                        side_2 = (new_position.0 + 1, new_position.1);
-                    } else {
-                        side_1 = (new_position.0, new_position.1 - 1);
-                        side_2 = (new_position.0, new_position.1 + 1);
-                    };
-
-                    let found = map.contains(&new_position)
-                        || map.contains(&side_1)
-                        || map.contains(&side_2);
-
-                    if !found {
-                        changes += 1;
-                        candidate = new_position;
-                        break;
-                    }
-                }
-            }
+    for i in 0..DIRECTION_ORDER.len() {
+        let direction = DIRECTION_ORDER[(round + i) % DIRECTION_ORDER.len()];
+        let new_position = elf.step(direction, 1);
+        let side_1 = new_position.step(direction.turn_left(), 1);
+        let side_2 = new_position.step(direction.turn_right(), 1);
+
+        if [new_position, side_1, side_2]
+            .iter()
+            .all(|c| !elves.contains(c))
+        {
+            return new_position;
+        }
+    }
+    elf
+}
 
-            if let Some(existing) = position_candidates.remove(&candidate) {
-                debug_assert_ne!(existing, candidate);
+fn calculate_elves(steps: i32, input: &str) -> AocResult<(i32, i32)> {
+    let map = load_map(input)?;
 
-                position_candidates.insert(existing, existing);
-                position_candidates.insert(*elf, *elf);
-                changes -= 1;
-            } else {
-                position_candidates.insert(candidate, *elf);
-            }
-        }
+    let mut automaton = Movers::new(map, propose_move);
+    let steps_completed = automaton
+        .run_until_stable(steps as usize, |_| {})
+        .unwrap_or(steps as usize);
+    Ok((empty_ground(automaton.cells()), steps_completed as i32))
+}
 
-        if changes == 0 {
-            break;
-        }
+/**
+ * Gets the smallest rectangle holding every elf, as its top left and bottom right corners.
+ */
+fn bounds(map: &Map) -> (Coordinate, Coordinate) {
+    let min_x = map.iter().map(|c| c.x).min().unwrap();
+    let max_x = map.iter().map(|c| c.x).max().unwrap();
+
+    let min_y = map.iter().map(|c| c.y).min().unwrap();
+    let max_y = map.iter().map(|c| c.y).max().unwrap();
+
+    (Coordinate::new(min_x, min_y), Coordinate::new(max_x, max_y))
+}
 
-        debug_assert!(changes >= 0);
+/**
+ * Counts the tiles in the smallest rectangle holding every elf that don't have an elf on them.
+ */
+fn empty_ground(map: &Map) -> i32 {
+    let (min, max) = bounds(map);
+    let width = max.x - min.x + 1;
+    let height = max.y - min.y + 1;
+    width * height - map.len() as i32
+}
+
+/**
+ * The elves spreading out a round at a time, until the part's number of rounds is up or no elf
+ * moves.
+ */
+struct Elves {
+    automaton: ElfMovers,
+    rounds: usize,
+    settled: bool,
+}
 
-        map.clear();
-        for (position_candidate, _) in position_candidates {
-            map.insert(position_candidate);
+impl Simulation for Elves {
+    fn step(&mut self) -> bool {
+        if self.settled || self.automaton.generation() >= self.rounds {
+            return false;
         }
+        self.settled = !self.automaton.step();
+        true
+    }
 
-        let f = direction_order.pop_front().unwrap();
-        direction_order.push_back(f);
+    fn render(&self) -> Vec<String> {
+        let map = self.automaton.cells();
+        let (min, max) = bounds(map);
+        (min.y..=max.y)
+            .map(|y| {
+                (min.x..=max.x)
+                    .map(|x| {
+                        if map.contains(&Coordinate::new(x, y)) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
     }
 
-    // find smallest rectangle
-    let min_x = map.iter().map(|(x, _)| x).min().unwrap().to_owned();
-    let max_x = map.iter().map(|(x, _)| x).max().unwrap().to_owned() + 1;
+    fn metrics(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("Round", self.automaton.generation().to_string()),
+            ("Elves", self.automaton.cells().len().to_string()),
+            (
+                "Empty ground",
+                empty_ground(self.automaton.cells()).to_string(),
+            ),
+            ("Settled", self.settled.to_string()),
+        ]
+    }
+}
 
-    let min_y = map.iter().map(|(_, y)| y).min().unwrap().to_owned();
-    let max_y = map.iter().map(|(_, y)| y).max().unwrap().to_owned() + 1;
+pub struct Day23;
 
-    let width = max_x - min_x;
-    let height = max_y - min_y;
-    let empty = width * height - map.len() as i32;
+impl Solution for Day23 {
+    fn year(&self) -> u32 {
+        2022
+    }
 
-    Ok((empty, steps_completed))
-}
+    fn day(&self) -> u32 {
+        23
+    }
 
-pub fn day_23() -> io::Result<i32> {
-    let (result, _) = calculate_elves(10, "./inputs/day-23-input.txt")?;
-    Ok(result)
-}
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        let (result, _) = calculate_elves(10, input)?;
+        Ok(Answer::from(result))
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        let (_, steps) = calculate_elves(100_000, input)?;
+        Ok(Answer::from(steps))
+    }
 
-pub fn day_23_part_2() -> io::Result<i32> {
-    let (_, steps) = calculate_elves(100_000, "./inputs/day-23-input.txt")?;
-    Ok(steps)
+    fn simulate(&self, part: u32, input: &str) -> AocResult<Box<dyn Simulation>> {
+        let automaton: ElfMovers = Movers::new(load_map(input)?, propose_move);
+        Ok(Box::new(Elves {
+            automaton,
+            rounds: if part == 1 { 10 } else { 100_000 },
+            settled: false,
+        }))
+    }
 }
 
 #[cfg(test)]
@@ -135,25 +190,41 @@ mod tests {
 
     #[test]
     fn small_test() {
-        let (result, _) = calculate_elves(10, "./inputs/day-23-input-test.txt").unwrap();
+        let (result, _) =
+            calculate_elves(10, include_str!("../inputs/day-23-input-test.txt")).unwrap();
         assert_eq!(110, result);
     }
 
     #[test]
     fn test() {
-        let (result, _) = calculate_elves(10, "./inputs/day-23-input.txt").unwrap();
+        let (result, _) = calculate_elves(10, include_str!("../inputs/day-23-input.txt")).unwrap();
         assert_eq!(4236, result);
     }
 
+    #[test]
+    fn simulate_test() {
+        let input = include_str!("../inputs/day-23-input-test.txt");
+        let mut simulation = Day23.simulate(1, input).unwrap();
+        assert_eq!(simulation.render()[0], "....#..");
+        while simulation.step() {}
+        assert_eq!(simulation.metrics()[2], ("Empty ground", "110".to_string()));
+
+        let mut simulation = Day23.simulate(2, input).unwrap();
+        while simulation.step() {}
+        assert_eq!(simulation.metrics()[0], ("Round", "20".to_string()));
+    }
+
     #[test]
     fn part_2_small_test() {
-        let (_, steps) = calculate_elves(100, "./inputs/day-23-input-test.txt").unwrap();
+        let (_, steps) =
+            calculate_elves(100, include_str!("../inputs/day-23-input-test.txt")).unwrap();
         assert_eq!(20, steps);
     }
 
     #[test]
     fn part_2_test() {
-        let (_, steps) = calculate_elves(100_000, "./inputs/day-23-input.txt").unwrap();
+        let (_, steps) =
+            calculate_elves(100_000, include_str!("../inputs/day-23-input.txt")).unwrap();
         assert_eq!(1023, steps);
     }
 }
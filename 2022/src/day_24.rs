@@ -1,57 +1,43 @@
-use std::{
-    collections::{HashMap, HashSet},
-    io,
+use std::collections::{HashMap, HashSet};
+
+use utils::{
+    coordinate::{Coordinate, Direction4},
+    error::{AocError, AocResult},
+    math::lcm,
+    search::bfs,
+    simulation::Simulation,
+    solution::{Answer, ParsedSolution},
 };
 
-use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
-
-use crate::file_utils::read_lines;
-
+#[derive(Clone, Copy)]
 enum Destination {
     Beginning,
     End,
 }
 
-#[derive(Clone, Debug, EnumIter)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-    None,
-}
-
-impl Direction {
-    fn calculate_position(&self, x: i32, y: i32) -> (i32, i32) {
-        let (dx, dy) = match self {
-            Direction::North => (0, -1),
-            Direction::East => (1, 0),
-            Direction::South => (0, 1),
-            Direction::West => (-1, 0),
-            Direction::None => (0, 0),
-        };
-        (x + dx, y + dy)
-    }
-}
+/**
+ * Part 2's trip, going back for the snacks.
+ */
+const THERE_AND_BACK_AGAIN: [Destination; 3] =
+    [Destination::End, Destination::Beginning, Destination::End];
 
 #[derive(Clone, Debug)]
 struct Blizzard {
     x: i32,
     y: i32,
-    direction: Direction,
+    direction: Direction4,
 }
 
 impl Blizzard {
     pub fn process(&mut self) {
-        let (x, y) = self.direction.calculate_position(self.x, self.y);
-        self.x = x;
-        self.y = y;
+        let position = Coordinate::new(self.x, self.y).step(self.direction, 1);
+        self.x = position.x;
+        self.y = position.y;
     }
 }
 
 #[derive(Clone, Debug)]
-struct Map {
+pub struct Map {
     width: i32,
     height: i32,
     minute: i32,
@@ -88,69 +74,94 @@ impl Map {
         self.minute += 1;
     }
 
-    fn is_free(&self, x: i32, y: i32) -> bool {
-        x >= 0
-            && x < self.width
-            && y >= 0
-            && y < self.height
-            && !self.occupied_space.contains(&(x, y))
+    /**
+     * Gets the gap in the wall above the top left of the valley.
+     */
+    fn entrance(&self) -> (i32, i32) {
+        (0, -1)
     }
 
-    #[allow(dead_code)]
-    fn print(&self) {
-        let mut taken: HashMap<(i32, i32), (i32, Direction)> = HashMap::new();
-        for blizzard in &self.blizzards {
-            let coords = (blizzard.x, blizzard.y);
-            let mut v = 1;
-            if taken.contains_key(&coords) {
-                let (o_v, _) = taken[&coords];
-                v = o_v + 1;
-            }
+    /**
+     * Gets the gap in the wall below the bottom right of the valley.
+     */
+    fn exit(&self) -> (i32, i32) {
+        (self.width - 1, self.height)
+    }
 
-            taken.insert(coords, (v, blizzard.direction.clone()));
+    fn position(&self, destination: Destination) -> (i32, i32) {
+        match destination {
+            Destination::Beginning => self.entrance(),
+            Destination::End => self.exit(),
         }
+    }
 
-        println!("Minute {t}:", t = self.minute);
+    /**
+     * Draws the valley with its walls, showing each blizzard's direction, or how many there are
+     * where they overlap, and marking the expedition's possible positions with `E`.
+     */
+    fn draw(&self, expedition: &HashSet<(i32, i32)>) -> Vec<String> {
+        let mut taken: HashMap<(i32, i32), Vec<Direction4>> = HashMap::new();
+        for blizzard in &self.blizzards {
+            taken
+                .entry((blizzard.x, blizzard.y))
+                .or_default()
+                .push(blizzard.direction);
+        }
+
+        let gap = |position| {
+            if expedition.contains(&position) {
+                'E'
+            } else {
+                '.'
+            }
+        };
+        let wall = "#".repeat(self.width as usize);
+        let mut rows = vec![format!("#{gap}{wall}", gap = gap(self.entrance()))];
         for y in 0..self.height {
-            for x in 0..self.width {
-                let mut glyph = ".".to_string();
-                if let Some((count, direction)) = taken.get(&(x, y)) {
-                    if *count == 1 {
-                        let g = match direction {
-                            Direction::North => "^",
-                            Direction::East => ">",
-                            Direction::South => "v",
-                            Direction::West => "<",
-                            Direction::None => ".",
-                        };
-                        glyph = g.to_string();
-                    } else {
-                        debug_assert!(*count < 10);
-                        glyph = count.to_string();
+            let row = (0..self.width)
+                .map(|x| match taken.get(&(x, y)).map(Vec::as_slice) {
+                    Some([direction]) => match direction {
+                        Direction4::North => '^',
+                        Direction4::East => '>',
+                        Direction4::South => 'v',
+                        Direction4::West => '<',
+                    },
+                    Some(directions) => {
+                        char::from_digit(directions.len() as u32, 10).unwrap_or('*')
                     }
-                }
-                print!("{glyph}");
-            }
-            println!();
+                    None if expedition.contains(&(x, y)) => 'E',
+                    None => '.',
+                })
+                .collect::<String>();
+            rows.push(format!("#{row}#"));
+        }
+        rows.push(format!("{wall}{gap}#", gap = gap(self.exit())));
+        rows
+    }
+
+    #[allow(dead_code)]
+    fn print(&self) {
+        println!("Minute {t}:", t = self.minute);
+        for row in self.draw(&HashSet::new()) {
+            println!("{row}");
         }
         println!();
     }
 }
 
-fn load_map(filename: &str) -> io::Result<Map> {
+fn load_map(input: &str) -> AocResult<Map> {
     let mut width_opt = None;
     let mut height_opt = None;
 
     let mut blizzards = Vec::new();
 
-    let lines = read_lines(filename)?;
-    for (y, line) in lines.flatten().enumerate() {
+    for (y, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
         // start or end
-        if line.chars().into_iter().filter(|c| *c == '#').count() > 2 {
+        if line.chars().filter(|c| *c == '#').count() > 2 {
             if width_opt.is_none() {
                 width_opt = Some(line.chars().count() as i32 - 2);
             } else {
@@ -163,11 +174,8 @@ fn load_map(filename: &str) -> io::Result<Map> {
             let direction_opt = match location {
                 '#' => None,
                 '.' => None,
-                '>' => Some(Direction::East),
-                '<' => Some(Direction::West),
-                '^' => Some(Direction::North),
-                'v' => Some(Direction::South),
-                c => panic!("Unknown input: {c}"),
+                '>' | '<' | '^' | 'v' => Direction4::try_from(location).ok(),
+                c => return Err(AocError::parse_at(y, x, format!("unknown tile '{c}'"))),
             };
 
             if let Some(direction) = direction_opt {
@@ -180,14 +188,17 @@ fn load_map(filename: &str) -> io::Result<Map> {
         }
     }
 
-    debug_assert!(width_opt.is_some());
-    debug_assert!(height_opt.is_some());
-    debug_assert!(width_opt.unwrap() > 0);
-    debug_assert!(height_opt.unwrap() > 0);
+    let (Some(width), Some(height)) = (width_opt, height_opt) else {
+        return Err(AocError::invalid_state(
+            "the valley needs a wall at the top and bottom",
+        ));
+    };
+    debug_assert!(width > 0);
+    debug_assert!(height > 0);
 
     let mut map = Map {
-        width: width_opt.unwrap(),
-        height: height_opt.unwrap(),
+        width,
+        height,
         minute: 0,
         blizzards,
         occupied_space: HashSet::new(),
@@ -196,68 +207,208 @@ fn load_map(filename: &str) -> io::Result<Map> {
     Ok(map)
 }
 
-fn calculate(destination: Destination, mut map: Map) -> Map {
-    let start = match destination {
-        Destination::Beginning => (map.width - 1, map.height - 1),
-        Destination::End => (0, 0),
-    };
+/**
+ * Where the blizzards are each minute. They are back where they started after a whole number of
+ * trips across and down the valley, so only that many minutes are kept.
+ */
+struct Forecast {
+    width: i32,
+    height: i32,
+    entrance: (i32, i32),
+    exit: (i32, i32),
+    occupied: Vec<HashSet<(i32, i32)>>,
+}
 
-    let target = match destination {
-        Destination::Beginning => (0, 0),
-        Destination::End => (map.width - 1, map.height - 1),
-    };
+impl Forecast {
+    /**
+     * Forecasts the blizzards from the map's minute onwards. Minutes given to the forecast are
+     * counted from the same start as the map's.
+     */
+    fn new(map: &Map) -> Forecast {
+        let period = lcm(map.width as i64, map.height as i64) as usize;
+        let mut map = map.clone();
+        let mut occupied = vec![HashSet::new(); period];
+        for _ in 0..period {
+            occupied[map.minute as usize % period] = map.occupied_space.clone();
+            map.tick();
+        }
+        Forecast {
+            width: map.width,
+            height: map.height,
+            entrance: map.entrance(),
+            exit: map.exit(),
+            occupied,
+        }
+    }
 
-    // player can only move down at first. wait until that option is available
-    while !map.is_free(start.0, start.1) {
-        map.tick();
+    fn period(&self) -> usize {
+        self.occupied.len()
     }
 
-    let mut candidates: HashSet<(i32, i32)> = HashSet::from_iter(vec![start]);
+    /**
+     * Gets whether the expedition can be at the position at the given minute.
+     */
+    fn is_free(&self, (x, y): (i32, i32), minute: usize) -> bool {
+        (x, y) == self.entrance
+            || (x, y) == self.exit
+            || (x >= 0
+                && x < self.width
+                && y >= 0
+                && y < self.height
+                && !self.occupied[minute % self.period()].contains(&(x, y)))
+    }
 
-    let mut destination_reached = false;
+    /**
+     * Finds the quickest way from one position to another, setting off at the given minute. The
+     * route has the position at every minute, starting with the one setting off from.
+     */
+    fn route(&self, from: (i32, i32), to: (i32, i32), minute: usize) -> AocResult<Vec<(i32, i32)>> {
+        // Blizzards repeat, so the minute only matters as a point in the cycle.
+        let (_, route) = bfs(
+            (from, minute % self.period()),
+            |&(position, minute)| {
+                let next = (minute + 1) % self.period();
+                let position = Coordinate::from(position);
+                Direction4::ALL
+                    .map(|direction| position.step(direction, 1))
+                    .into_iter()
+                    // waiting where they are is also a move.
+                    .chain([position])
+                    .map(|Coordinate { x, y }| (x, y))
+                    .filter(|&position| self.is_free(position, next))
+                    .map(|position| (position, next))
+                    .collect::<Vec<_>>()
+            },
+            |&(position, _)| position == to,
+        )
+        .ok_or_else(|| AocError::invalid_state("the destination is never reached"))?;
+        Ok(route.into_iter().map(|(position, _)| position).collect())
+    }
+}
 
-    while !destination_reached {
-        map.tick();
+/**
+ * The expedition making each leg of the part's trip in turn, following the quickest route.
+ */
+struct Trip {
+    map: Map,
+    /**
+     * The expedition's position every minute, starting from the entrance at the map's minute.
+     */
+    route: Vec<(i32, i32)>,
+    /**
+     * The minute each destination is reached.
+     */
+    arrivals: Vec<i32>,
+    departure: i32,
+}
 
-        if map.minute > 10_000 {
-            panic!("Fail, too many steps.");
-        }
+impl Trip {
+    fn position(&self) -> (i32, i32) {
+        self.route[(self.map.minute - self.departure) as usize]
+    }
+}
 
-        let mut new_candidates = HashSet::new();
-        // there is always an option to start again.
-        new_candidates.insert(start);
+/**
+ * Finds the quickest trip through each destination in turn, starting from the entrance at the
+ * map's minute.
+ */
+fn plan_trip(map: &Map, legs: &[Destination]) -> AocResult<Trip> {
+    let forecast = Forecast::new(map);
+    let mut route = vec![map.entrance()];
+    let mut arrivals = Vec::new();
+    for &destination in legs {
+        let from = route[route.len() - 1];
+        let minute = map.minute as usize + route.len() - 1;
+        let leg = forecast.route(from, map.position(destination), minute)?;
+        route.extend_from_slice(&leg[1..]);
+        arrivals.push(map.minute + route.len() as i32 - 1);
+    }
+    Ok(Trip {
+        map: map.clone(),
+        route,
+        arrivals,
+        departure: map.minute,
+    })
+}
 
-        for (candidate_x, candidate_y) in &candidates {
-            for direction in Direction::iter() {
-                let (x, y) = direction.calculate_position(*candidate_x, *candidate_y);
+fn calculate(map: &Map, legs: &[Destination]) -> AocResult<i32> {
+    let trip = plan_trip(map, legs)?;
+    Ok(trip.arrivals[trip.arrivals.len() - 1])
+}
 
-                if map.is_free(x, y) {
-                    if (x, y) == target {
-                        map.tick();
-                        destination_reached = true;
-                    }
-                    new_candidates.insert((x, y));
-                }
-            }
+impl Simulation for Trip {
+    fn step(&mut self) -> bool {
+        if (self.map.minute - self.departure) as usize + 1 >= self.route.len() {
+            return false;
         }
-        candidates = new_candidates;
+        self.map.tick();
+        true
     }
 
-    map
-}
+    fn render(&self) -> Vec<String> {
+        self.map.draw(&HashSet::from([self.position()]))
+    }
 
-pub fn day_24() -> io::Result<i32> {
-    let map = load_map("./inputs/day-24-input.txt")?;
-    let result = calculate(Destination::End, map);
-    Ok(result.minute)
+    fn metrics(&self) -> Vec<(&'static str, String)> {
+        let minute = self.map.minute;
+        let leg = self
+            .arrivals
+            .iter()
+            .filter(|&&arrival| arrival < minute)
+            .count();
+        vec![
+            ("Minute", minute.to_string()),
+            (
+                "Leg",
+                format!(
+                    "{} of {}",
+                    (leg + 1).min(self.arrivals.len()),
+                    self.arrivals.len()
+                ),
+            ),
+            ("Position", format!("{:?}", self.position())),
+            (
+                "Destination reached",
+                self.arrivals.contains(&minute).to_string(),
+            ),
+        ]
+    }
 }
 
-pub fn day_24_part_2() -> io::Result<i32> {
-    let map = load_map("./inputs/day-24-input.txt")?;
-    let leg_1 = calculate(Destination::End, map);
-    let leg_2 = calculate(Destination::Beginning, leg_1);
-    let leg_3 = calculate(Destination::End, leg_2);
-    Ok(leg_3.minute)
+pub struct Day24;
+
+impl ParsedSolution for Day24 {
+    type Input = Map;
+
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        24
+    }
+
+    fn parse(&self, input: &str) -> AocResult<Map> {
+        load_map(input)
+    }
+
+    fn part_1(&self, map: &Map) -> AocResult<Answer> {
+        let result = calculate(map, &[Destination::End])?;
+        Ok(Answer::from(result))
+    }
+
+    fn part_2(&self, map: &Map) -> AocResult<Answer> {
+        let result = calculate(map, &THERE_AND_BACK_AGAIN)?;
+        Ok(Answer::from(result))
+    }
+
+    fn simulate(&self, part: u32, map: &Map) -> AocResult<Box<dyn Simulation>> {
+        let legs = match part {
+            1 => &[Destination::End][..],
+            _ => &THERE_AND_BACK_AGAIN,
+        };
+        Ok(Box::new(plan_trip(map, legs)?))
+    }
 }
 
 #[cfg(test)]
@@ -267,44 +418,56 @@ mod tests {
 
     #[test]
     fn small_test() {
-        let map = load_map("./inputs/day-24-input-test.txt").unwrap();
-        let result = calculate(Destination::End, map);
-        assert_eq!(18, result.minute);
+        let map = load_map(include_str!("../inputs/day-24-input-test.txt")).unwrap();
+        let result = calculate(&map, &[Destination::End]).unwrap();
+        assert_eq!(18, result);
     }
 
     #[test]
     fn test() {
-        let map = load_map("./inputs/day-24-input.txt").unwrap();
-        let result = calculate(Destination::End, map);
-        assert_eq!(281, result.minute);
+        let map = load_map(include_str!("../inputs/day-24-input.txt")).unwrap();
+        let result = calculate(&map, &[Destination::End]).unwrap();
+        assert_eq!(281, result);
     }
 
     #[test]
-    fn part_2_small_test() {
-        let map = load_map("./inputs/day-24-input-test.txt").unwrap();
-
-        println!("start");
-
-        let leg_1 = calculate(Destination::End, map);
-        assert_eq!(18, leg_1.minute);
-
-        println!("leg 1 done");
-
-        let leg_2 = calculate(Destination::Beginning, leg_1);
-        assert_eq!(41, leg_2.minute);
+    fn simulate_test() {
+        let map = load_map(include_str!("../inputs/day-24-input-test.txt")).unwrap();
+        let mut simulation = Day24.simulate(2, &map).unwrap();
+        assert_eq!(simulation.render()[0], "#E######");
+        // The expedition moves down in the first minute, as in the puzzle's example.
+        simulation.step();
+        assert_eq!(simulation.render()[1], "#E>3.<.#");
+        while simulation.step() {}
+        assert_eq!(simulation.metrics()[0], ("Minute", "54".to_string()));
+        assert_eq!(simulation.metrics()[1], ("Leg", "3 of 3".to_string()));
+        assert_eq!(simulation.render()[5], "######E#");
+    }
 
-        println!("leg 2 done");
+    #[test]
+    fn blocked_test() {
+        // The two blizzards swap places every minute, so the valley is never clear.
+        let map = load_map("#.##\n#><#\n##.#\n").unwrap();
+        assert!(matches!(
+            Day24.simulate(1, &map),
+            Err(AocError::InvalidState(_))
+        ));
+        assert!(Day24.part_1(&map).is_err());
+    }
 
-        let leg_3 = calculate(Destination::End, leg_2);
-        assert_eq!(54, leg_3.minute);
+    #[test]
+    fn part_2_small_test() {
+        let map = load_map(include_str!("../inputs/day-24-input-test.txt")).unwrap();
+        let trip = plan_trip(&map, &THERE_AND_BACK_AGAIN).unwrap();
+        assert_eq!(vec![18, 41, 54], trip.arrivals);
+        assert_eq!(trip.route.len(), 55);
+        assert_eq!(trip.route[41], map.entrance());
     }
 
     #[test]
     fn part_2_test() {
-        let map = load_map("./inputs/day-24-input.txt").unwrap();
-        let leg_1 = calculate(Destination::End, map);
-        let leg_2 = calculate(Destination::Beginning, leg_1);
-        let leg_3 = calculate(Destination::End, leg_2);
-        assert_eq!(807, leg_3.minute);
+        let map = load_map(include_str!("../inputs/day-24-input.txt")).unwrap();
+        let result = calculate(&map, &THERE_AND_BACK_AGAIN).unwrap();
+        assert_eq!(807, result);
     }
 }
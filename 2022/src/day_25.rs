@@ -1,6 +1,7 @@
-use std::io;
-
-use crate::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult},
+    solution::{Answer, Solution},
+};
 
 fn convert_snafu(snafu: &str) -> i64 {
     let mut decimal = 0;
@@ -24,19 +25,24 @@ fn max_value_from_place(place: usize) -> i64 {
     (0..=place).map(|n| 5_i64.pow(n as u32)).sum::<i64>() * 2
 }
 
-fn convert_to_snafu(decimal: i64) -> String {
-    let snafu = snafu_target(decimal, 20_usize);
+fn convert_to_snafu(decimal: i64) -> AocResult<String> {
+    if decimal == 0 {
+        return Ok("0".to_string());
+    }
+    let snafu = snafu_target(decimal, 20_usize)?;
     let snafu = snafu.trim_start_matches(['0']).to_string();
 
     let dec = convert_snafu(snafu.as_str());
     if dec != decimal {
-        panic!("Snafu for {decimal} is wrong. Calculated {snafu} but that value is actually {dec}.")
+        return Err(AocError::invalid_state(format!(
+            "Snafu for {decimal} is wrong. Calculated {snafu} but that value is actually {dec}."
+        )));
     }
 
-    snafu
+    Ok(snafu)
 }
 
-fn snafu_target(target: i64, max_position: usize) -> String {
+fn snafu_target(target: i64, max_position: usize) -> AocResult<String> {
     // find left most number closest
     // and we're doing it the dumb way
 
@@ -60,11 +66,15 @@ fn snafu_target(target: i64, max_position: usize) -> String {
         }
     }
 
-    closest.sort_by(|a, b| a.3.cmp(&b.3));
+    closest.sort_by_key(|a| a.3);
 
     // consider attempting all of these in order - many combinations do seem possible when trying the "optimal" route
 
-    let (diff, digit, place, _) = closest[0];
+    let &(diff, digit, place, _) = closest.first().ok_or_else(|| {
+        AocError::invalid_state(format!(
+            "{target} can't be written in {max_position} places"
+        ))
+    })?;
     let snafu_char = match digit {
         2 => "2",
         1 => "1",
@@ -79,28 +89,53 @@ fn snafu_target(target: i64, max_position: usize) -> String {
     snafu.push_str(&"0".repeat(place));
 
     if diff != 0 {
-        let sub_snafu = snafu_target(diff, place - 1);
+        let sub_snafu = snafu_target(diff, place - 1)?;
         snafu.replace_range((snafu.len() - sub_snafu.len())..snafu.len(), &sub_snafu);
     }
-    snafu
+    Ok(snafu)
 }
 
-fn sum_snafu_file(filename: &str) -> io::Result<String> {
+fn sum_snafu_file(input: &str) -> AocResult<String> {
     let mut sum_dec = 0;
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
-        sum_dec += convert_snafu(line.as_str());
+        if let Some((column, c)) = line
+            .char_indices()
+            .find(|(_, c)| !matches!(c, '=' | '-' | '0' | '1' | '2'))
+        {
+            return Err(AocError::parse_at(
+                i,
+                column,
+                format!("illegal digit '{c}'"),
+            ));
+        }
+        sum_dec += convert_snafu(line);
     }
-    let sum_snafu = convert_to_snafu(sum_dec);
-    Ok(sum_snafu)
+
+    convert_to_snafu(sum_dec)
 }
 
-pub fn day_25() -> io::Result<String> {
-    let sum = sum_snafu_file("./inputs/day-25-input.txt")?;
-    Ok(sum)
+pub struct Day25;
+
+impl Solution for Day25 {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        25
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        let sum = sum_snafu_file(input)?;
+        Ok(Answer::from(sum))
+    }
+
+    fn has_part_2(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(test)]
@@ -127,31 +162,32 @@ mod tests {
 
     #[test]
     fn convert_to_snafu_test() {
-        assert_eq!("-2", convert_to_snafu(-3));
-        assert_eq!("1=-0-2", convert_to_snafu(1747));
-        assert_eq!("12111", convert_to_snafu(906));
-        assert_eq!("2=0=", convert_to_snafu(198));
-        assert_eq!("21", convert_to_snafu(11));
-        assert_eq!("2=01", convert_to_snafu(201));
-        assert_eq!("111", convert_to_snafu(31));
-        assert_eq!("20012", convert_to_snafu(1257));
-        assert_eq!("112", convert_to_snafu(32));
-        assert_eq!("1=-1=", convert_to_snafu(353));
-        assert_eq!("1-12", convert_to_snafu(107));
-        assert_eq!("12", convert_to_snafu(7));
-        assert_eq!("1=", convert_to_snafu(3));
-        assert_eq!("122", convert_to_snafu(37));
+        assert_eq!("0", convert_to_snafu(0).unwrap());
+        assert_eq!("-2", convert_to_snafu(-3).unwrap());
+        assert_eq!("1=-0-2", convert_to_snafu(1747).unwrap());
+        assert_eq!("12111", convert_to_snafu(906).unwrap());
+        assert_eq!("2=0=", convert_to_snafu(198).unwrap());
+        assert_eq!("21", convert_to_snafu(11).unwrap());
+        assert_eq!("2=01", convert_to_snafu(201).unwrap());
+        assert_eq!("111", convert_to_snafu(31).unwrap());
+        assert_eq!("20012", convert_to_snafu(1257).unwrap());
+        assert_eq!("112", convert_to_snafu(32).unwrap());
+        assert_eq!("1=-1=", convert_to_snafu(353).unwrap());
+        assert_eq!("1-12", convert_to_snafu(107).unwrap());
+        assert_eq!("12", convert_to_snafu(7).unwrap());
+        assert_eq!("1=", convert_to_snafu(3).unwrap());
+        assert_eq!("122", convert_to_snafu(37).unwrap());
     }
 
     #[test]
     fn small_test() {
-        let sum = sum_snafu_file("./inputs/day-25-input-test.txt").unwrap();
+        let sum = sum_snafu_file(include_str!("../inputs/day-25-input-test.txt")).unwrap();
         assert_eq!("2=-1=0", sum);
     }
 
     #[test]
     fn test() {
-        let sum = sum_snafu_file("./inputs/day-25-input.txt").unwrap();
+        let sum = sum_snafu_file(include_str!("../inputs/day-25-input.txt")).unwrap();
         assert_eq!("2-2--02=1---1200=0-1", sum);
     }
 }
@@ -1,31 +1,48 @@
-use std::io::{self};
-
 use itertools::Itertools;
 use slice_group_by::GroupBy;
 
-use crate::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult},
+    solution::{Answer, Solution},
+};
 
-pub fn day_3() -> io::Result<i32> {
-    fetch_item_priorities("./inputs/day-3-input.txt")
-}
+pub struct Day3;
 
-pub fn day_3_part_2() -> io::Result<i32> {
-    fetch_group_priorities("./inputs/day-3-input.txt")
+impl Solution for Day3 {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        fetch_item_priorities(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        fetch_group_priorities(input).map(Answer::from)
+    }
 }
 
-fn fetch_item_priorities(filename: &str) -> io::Result<i32> {
+fn fetch_item_priorities(input: &str) -> AocResult<i32> {
     let mut priority_sum = 0;
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
+        check_items(i, line)?;
+        if line.len() % 2 != 0 {
+            return Err(AocError::parse(
+                i,
+                "a rucksack must split into two equal compartments",
+            ));
+        }
         let split_position = line.len() / 2;
         let (first, second) = line.split_at(split_position);
 
-        assert_eq!(first.len(), second.len());
-
         for letter in first.chars().unique() {
             if second.contains(letter) {
                 priority_sum += fetch_priority(letter);
@@ -35,17 +52,21 @@ fn fetch_item_priorities(filename: &str) -> io::Result<i32> {
     Ok(priority_sum)
 }
 
-fn fetch_group_priorities(filename: &str) -> io::Result<i32> {
+fn fetch_group_priorities(input: &str) -> AocResult<i32> {
     let mut priority_sum = 0;
 
-    let lines = read_lines(filename)?;
     let mut letters = Vec::new();
     let mut group_count = 0;
-    for line in lines.flatten() {
+    let mut group_start = 0;
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
+        check_items(i, line)?;
+        if group_count == 0 {
+            group_start = i;
+        }
         letters.extend(line.chars().unique());
         group_count += 1;
         if group_count < 3 {
@@ -56,7 +77,12 @@ fn fetch_group_priorities(filename: &str) -> io::Result<i32> {
         let mut badge = None;
         for group in letters.linear_group_by(|a, b| a == b) {
             if group.len() == 3 {
-                assert!(badge.is_none());
+                if badge.is_some() {
+                    return Err(AocError::invalid_state(format!(
+                        "the group starting on line {} has more than one badge",
+                        group_start + 1
+                    )));
+                }
                 badge = Some(group[0]);
             }
         }
@@ -64,7 +90,7 @@ fn fetch_group_priorities(filename: &str) -> io::Result<i32> {
         if let Some(group_letter) = badge {
             priority_sum += fetch_priority(group_letter);
         } else {
-            panic!("No group found.");
+            return Err(AocError::parse(i, "the group has no common badge"));
         }
 
         letters.clear();
@@ -73,6 +99,20 @@ fn fetch_group_priorities(filename: &str) -> io::Result<i32> {
     Ok(priority_sum)
 }
 
+/**
+ * Rejects rucksacks holding anything other than letters.
+ */
+fn check_items(index: usize, line: &str) -> AocResult<()> {
+    match line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        Some((column, c)) => Err(AocError::parse_at(
+            index,
+            column,
+            format!("unknown item '{c}'"),
+        )),
+        None => Ok(()),
+    }
+}
+
 fn fetch_priority(letter: char) -> i32 {
     if letter.is_ascii_uppercase() {
         (letter as i32) - 38
@@ -89,7 +129,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            fetch_item_priorities("./inputs/day-3-input-test.txt").unwrap(),
+            fetch_item_priorities(include_str!("../inputs/day-3-input-test.txt")).unwrap(),
             157
         );
     }
@@ -97,7 +137,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            fetch_group_priorities("./inputs/day-3-input-test.txt").unwrap(),
+            fetch_group_priorities(include_str!("../inputs/day-3-input-test.txt")).unwrap(),
             70
         );
     }
@@ -109,4 +149,10 @@ mod tests {
         assert_eq!(fetch_priority('A'), 27);
         assert_eq!(fetch_priority('Z'), 52);
     }
+
+    #[test]
+    fn two_badges_test() {
+        let error = fetch_group_priorities("abc\nab\nad\nxa\nxb\nxc\nabc\nabc\nabc\n").unwrap_err();
+        assert!(error.to_string().contains("line 7"), "{error}");
+    }
 }
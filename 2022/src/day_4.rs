@@ -1,83 +1,86 @@
-use std::io::{self};
-
 use itertools::Itertools;
 
-use crate::file_utils::read_lines;
-
-struct ElfRange {
-    pub start: i32,
-    pub end: i32,
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    interval::Interval,
+    solution::{Answer, Solution},
+};
+
+/**
+ * Parses the sections an elf is assigned, such as `2-4`, which includes both ends.
+ */
+fn parse_elf_range(str: &str) -> Result<Interval<i32>, String> {
+    if let Some((a, b)) = str.split('-').collect_tuple() {
+        let start = a
+            .parse::<i32>()
+            .map_err(|err| format!("invalid section '{a}': {err}"))?;
+        let end = b
+            .parse::<i32>()
+            .map_err(|err| format!("invalid section '{b}': {err}"))?;
+
+        Ok(Interval::inclusive(start, end))
+    } else {
+        Err(format!("expected a range 'start-end', found '{str}'"))
+    }
 }
 
-impl ElfRange {
-    pub fn from_str(str: &str) -> ElfRange {
-        if let Some((a, b)) = str.split('-').collect_tuple() {
-            let start = a.parse::<i32>().unwrap();
-            let end = b.parse::<i32>().unwrap();
+pub struct Day4;
 
-            ElfRange { start, end }
-        } else {
-            panic!("irregular hyphen");
-        }
+impl Solution for Day4 {
+    fn year(&self) -> u32 {
+        2022
     }
 
-    pub fn contains(&self, other: &Self) -> bool {
-        self.start <= other.start && self.end >= other.end
+    fn day(&self) -> u32 {
+        4
     }
 
-    pub fn overlap(&self, other: &Self) -> bool {
-        (self.start >= other.start && self.start <= other.end)
-            || (self.end >= other.start && self.end <= other.end)
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        fetch_overlapping_pairs(input).map(Answer::from)
     }
-}
-
-pub fn day_4() -> io::Result<i32> {
-    fetch_overlapping_pairs("./inputs/day-4-input.txt")
-}
 
-pub fn day_4_part_2() -> io::Result<i32> {
-    fetch_overlapping_ranges("./inputs/day-4-input.txt")
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        fetch_overlapping_ranges(input).map(Answer::from)
+    }
 }
 
-fn fetch_overlapping_pairs(filename: &str) -> io::Result<i32> {
+fn fetch_overlapping_pairs(input: &str) -> AocResult<i32> {
     let mut overlapping_pairs = 0;
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
         if let Some((a, b)) = line.split(',').collect_tuple() {
-            let range_a = ElfRange::from_str(a);
-            let range_b = ElfRange::from_str(b);
+            let range_a = parse_elf_range(a).on_line(i)?;
+            let range_b = parse_elf_range(b).on_line(i)?;
 
             if range_a.contains(&range_b) || range_b.contains(&range_a) {
                 overlapping_pairs += 1;
             }
         } else {
-            panic!("irregular comma");
+            return Err(AocError::parse(i, "expected two ranges separated by ','"));
         }
     }
     Ok(overlapping_pairs)
 }
 
-fn fetch_overlapping_ranges(filename: &str) -> io::Result<i32> {
+fn fetch_overlapping_ranges(input: &str) -> AocResult<i32> {
     let mut overlapping_pairs = 0;
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
         if let Some((a, b)) = line.split(',').collect_tuple() {
-            let range_a = ElfRange::from_str(a);
-            let range_b = ElfRange::from_str(b);
+            let range_a = parse_elf_range(a).on_line(i)?;
+            let range_b = parse_elf_range(b).on_line(i)?;
 
-            if range_a.overlap(&range_b) || range_b.overlap(&range_a) {
+            if range_a.overlaps(&range_b) {
                 overlapping_pairs += 1;
             }
         } else {
-            panic!("irregular comma");
+            return Err(AocError::parse(i, "expected two ranges separated by ','"));
         }
     }
     Ok(overlapping_pairs)
@@ -91,7 +94,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            fetch_overlapping_pairs("./inputs/day-4-input-test.txt").unwrap(),
+            fetch_overlapping_pairs(include_str!("../inputs/day-4-input-test.txt")).unwrap(),
             2
         );
     }
@@ -99,7 +102,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            fetch_overlapping_pairs("./inputs/day-4-input.txt").unwrap(),
+            fetch_overlapping_pairs(include_str!("../inputs/day-4-input.txt")).unwrap(),
             540
         );
     }
@@ -107,7 +110,7 @@ mod tests {
     #[test]
     fn small_test_part_2() {
         assert_eq!(
-            fetch_overlapping_ranges("./inputs/day-4-input-test.txt").unwrap(),
+            fetch_overlapping_ranges(include_str!("../inputs/day-4-input-test.txt")).unwrap(),
             4
         );
     }
@@ -115,7 +118,7 @@ mod tests {
     #[test]
     fn test_part_2() {
         assert_eq!(
-            fetch_overlapping_ranges("./inputs/day-4-input.txt").unwrap(),
+            fetch_overlapping_ranges(include_str!("../inputs/day-4-input.txt")).unwrap(),
             872
         );
     }
@@ -1,46 +1,70 @@
-use std::{
-    collections::VecDeque,
-    io::{self},
-};
+use std::collections::VecDeque;
 
 use itertools::Itertools;
 
-use crate::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    solution::{Answer, Solution},
+};
 
 struct CraneStacks {
     pub stacks: Vec<VecDeque<char>>,
 }
 
 impl CraneStacks {
-    fn read_top_letters(&self) -> String {
+    fn read_top_letters(&self) -> AocResult<String> {
         let mut result = "".to_string();
-        for stack in &self.stacks {
-            result += &stack[0].to_string();
+        for (id, stack) in self.stacks.iter().enumerate() {
+            let top = stack.front().ok_or_else(|| {
+                AocError::invalid_state(format!("stack {} is empty at the end", id + 1))
+            })?;
+            result.push(*top);
         }
-        result
+        Ok(result)
     }
-}
 
-pub fn day_5() -> io::Result<String> {
-    let path = "./inputs/day-5-input.txt";
-    let mut crane_stacks = fetch_starting_stacks(path)?;
-    crane_stacks = execute_crane_instructions(path, crane_stacks)?;
+    fn stack(&mut self, id: usize) -> AocResult<&mut VecDeque<char>> {
+        self.stacks
+            .get_mut(id)
+            .ok_or_else(|| AocError::invalid_state(format!("there is no stack {}", id + 1)))
+    }
 
-    Ok(crane_stacks.read_top_letters())
+    fn pop(&mut self, id: usize) -> AocResult<char> {
+        self.stack(id)?
+            .pop_front()
+            .ok_or_else(|| AocError::invalid_state(format!("stack {} is empty", id + 1)))
+    }
 }
 
-pub fn day_5_part_2() -> io::Result<String> {
-    let path = "./inputs/day-5-input.txt";
-    let mut crane_stacks = fetch_starting_stacks(path)?;
-    crane_stacks = execute_bulk_crane_instructions(path, crane_stacks)?;
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn year(&self) -> u32 {
+        2022
+    }
 
-    Ok(crane_stacks.read_top_letters())
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        let mut crane_stacks = fetch_starting_stacks(input)?;
+        crane_stacks = execute_crane_instructions(input, crane_stacks)?;
+
+        crane_stacks.read_top_letters().map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        let mut crane_stacks = fetch_starting_stacks(input)?;
+        crane_stacks = execute_bulk_crane_instructions(input, crane_stacks)?;
+
+        crane_stacks.read_top_letters().map(Answer::from)
+    }
 }
 
-fn fetch_starting_stacks(filepath: &str) -> io::Result<CraneStacks> {
+fn fetch_starting_stacks(input: &str) -> AocResult<CraneStacks> {
     let mut stacks: Vec<VecDeque<char>> = Vec::new();
-    let lines = read_lines(filepath)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if !line.contains('[') {
             break;
         }
@@ -64,93 +88,64 @@ fn fetch_starting_stacks(filepath: &str) -> io::Result<CraneStacks> {
             let trim: &[_] = &['[', ']', ' '];
             let trimmed = stack_ele.trim_matches(trim).to_string();
 
-            assert!(!trimmed.is_empty());
-            let stack_opt = stacks.get_mut(current_stack);
-            stack_opt
-                .unwrap()
-                .push_back(trimmed.chars().next().unwrap());
+            let crane_crate = trimmed.chars().next().ok_or_else(|| {
+                AocError::parse_at(i, current_stack * 4, "expected a crate '[X]'")
+            })?;
+            stacks[current_stack].push_back(crane_crate);
         }
     }
     Ok(CraneStacks { stacks })
 }
 
+/**
+ * Reads a `move N from A to B` instruction as the crate count and zero-based stack indexes.
+ */
+fn parse_instruction(index: usize, line: &str) -> AocResult<(usize, usize, usize)> {
+    // eg move 1 from 2 to 1
+    let (crate_count, start_stack_id, end_stack_id) =
+        sscanf::sscanf!(line.trim(), "move {} from {} to {}", usize, usize, usize)
+            .on_line(index)?;
+    if start_stack_id == 0 || end_stack_id == 0 {
+        return Err(AocError::parse(index, "stacks are numbered from 1"));
+    }
+    Ok((crate_count, start_stack_id - 1, end_stack_id - 1))
+}
+
 fn execute_crane_instructions(
-    filepath: &str,
+    input: &str,
     mut crane_stacks: CraneStacks,
-) -> io::Result<CraneStacks> {
-    let lines = read_lines(filepath)?;
-    for line in lines.flatten() {
+) -> AocResult<CraneStacks> {
+    for (i, line) in input.lines().enumerate() {
         if !line.contains("move") {
             continue;
         }
 
-        // eg move 1 from 2 to 1
-        if let Some((_move, crate_count_s, _from, start_stack_id_s, _to, end_stack_id_s)) =
-            line.split_whitespace().collect_tuple()
-        {
-            let start_stack_id: usize = str::parse::<usize>(start_stack_id_s).unwrap() - 1;
-            let end_stack_id: usize = str::parse::<usize>(end_stack_id_s).unwrap() - 1;
-            let crate_count: i32 = str::parse(crate_count_s).unwrap();
-
-            for _ in 0..crate_count {
-                let crane_crate = crane_stacks
-                    .stacks
-                    .get_mut(start_stack_id)
-                    .unwrap()
-                    .pop_front()
-                    .unwrap();
-                crane_stacks
-                    .stacks
-                    .get_mut(end_stack_id)
-                    .unwrap()
-                    .push_front(crane_crate);
-            }
-        } else {
-            panic!("Unexpected string format: {line}");
+        let (crate_count, start_stack_id, end_stack_id) = parse_instruction(i, line)?;
+        for _ in 0..crate_count {
+            let crane_crate = crane_stacks.pop(start_stack_id)?;
+            crane_stacks.stack(end_stack_id)?.push_front(crane_crate);
         }
     }
     Ok(crane_stacks)
 }
 
 fn execute_bulk_crane_instructions(
-    filepath: &str,
+    input: &str,
     mut crane_stacks: CraneStacks,
-) -> io::Result<CraneStacks> {
-    let lines = read_lines(filepath)?;
-    for line in lines.flatten() {
+) -> AocResult<CraneStacks> {
+    for (i, line) in input.lines().enumerate() {
         if !line.contains("move") {
             continue;
         }
 
-        // eg move 1 from 2 to 1
-        if let Some((_move, crate_count_s, _from, start_stack_id_s, _to, end_stack_id_s)) =
-            line.split_whitespace().collect_tuple()
-        {
-            let start_stack_id: usize = str::parse::<usize>(start_stack_id_s).unwrap() - 1;
-            let end_stack_id: usize = str::parse::<usize>(end_stack_id_s).unwrap() - 1;
-            let crate_count: i32 = str::parse(crate_count_s).unwrap();
-
-            let mut temp_stack = Vec::new();
-            for _ in 0..crate_count {
-                let crane_crate = crane_stacks
-                    .stacks
-                    .get_mut(start_stack_id)
-                    .unwrap()
-                    .pop_front()
-                    .unwrap();
-
-                temp_stack.push(crane_crate);
-            }
-            temp_stack.reverse();
-            for crane_crate in temp_stack {
-                crane_stacks
-                    .stacks
-                    .get_mut(end_stack_id)
-                    .unwrap()
-                    .push_front(crane_crate);
-            }
-        } else {
-            panic!("Unexpected string format: {line}");
+        let (crate_count, start_stack_id, end_stack_id) = parse_instruction(i, line)?;
+        let mut temp_stack = Vec::new();
+        for _ in 0..crate_count {
+            temp_stack.push(crane_stacks.pop(start_stack_id)?);
+        }
+        temp_stack.reverse();
+        for crane_crate in temp_stack {
+            crane_stacks.stack(end_stack_id)?.push_front(crane_crate);
         }
     }
     Ok(crane_stacks)
@@ -164,42 +159,66 @@ mod tests {
     #[test]
     fn fetch_starting_stacks_small_test() {
         assert_eq!(
-            fetch_starting_stacks("./inputs/day-5-input-test.txt")
+            fetch_starting_stacks(include_str!("../inputs/day-5-input-test.txt"))
                 .unwrap()
-                .read_top_letters(),
+                .read_top_letters()
+                .unwrap(),
             "NDP"
         );
     }
 
     #[test]
     fn execute_crane_instructions_small_test() {
-        let mut crane_stacks = fetch_starting_stacks("./inputs/day-5-input-test.txt").unwrap();
-        crane_stacks =
-            execute_crane_instructions("./inputs/day-5-input-test.txt", crane_stacks).unwrap();
-        assert_eq!(crane_stacks.read_top_letters(), "CMZ");
+        let mut crane_stacks =
+            fetch_starting_stacks(include_str!("../inputs/day-5-input-test.txt")).unwrap();
+        crane_stacks = execute_crane_instructions(
+            include_str!("../inputs/day-5-input-test.txt"),
+            crane_stacks,
+        )
+        .unwrap();
+        assert_eq!(crane_stacks.read_top_letters().unwrap(), "CMZ");
     }
 
     #[test]
     fn execute_crane_instructions_test() {
-        let mut crane_stacks = fetch_starting_stacks("./inputs/day-5-input.txt").unwrap();
+        let mut crane_stacks =
+            fetch_starting_stacks(include_str!("../inputs/day-5-input.txt")).unwrap();
         crane_stacks =
-            execute_crane_instructions("./inputs/day-5-input.txt", crane_stacks).unwrap();
-        assert_eq!(crane_stacks.read_top_letters(), "FRDSQRRCD");
+            execute_crane_instructions(include_str!("../inputs/day-5-input.txt"), crane_stacks)
+                .unwrap();
+        assert_eq!(crane_stacks.read_top_letters().unwrap(), "FRDSQRRCD");
     }
 
     #[test]
     fn execute_bulk_crane_instructions_small_test() {
-        let mut crane_stacks = fetch_starting_stacks("./inputs/day-5-input-test.txt").unwrap();
-        crane_stacks =
-            execute_bulk_crane_instructions("./inputs/day-5-input-test.txt", crane_stacks).unwrap();
-        assert_eq!(crane_stacks.read_top_letters(), "MCD");
+        let mut crane_stacks =
+            fetch_starting_stacks(include_str!("../inputs/day-5-input-test.txt")).unwrap();
+        crane_stacks = execute_bulk_crane_instructions(
+            include_str!("../inputs/day-5-input-test.txt"),
+            crane_stacks,
+        )
+        .unwrap();
+        assert_eq!(crane_stacks.read_top_letters().unwrap(), "MCD");
     }
 
     #[test]
     fn execute_bulk_crane_instructions_test() {
-        let mut crane_stacks = fetch_starting_stacks("./inputs/day-5-input.txt").unwrap();
-        crane_stacks =
-            execute_bulk_crane_instructions("./inputs/day-5-input.txt", crane_stacks).unwrap();
-        assert_eq!(crane_stacks.read_top_letters(), "HRFTQVWNN");
+        let mut crane_stacks =
+            fetch_starting_stacks(include_str!("../inputs/day-5-input.txt")).unwrap();
+        crane_stacks = execute_bulk_crane_instructions(
+            include_str!("../inputs/day-5-input.txt"),
+            crane_stacks,
+        )
+        .unwrap();
+        assert_eq!(crane_stacks.read_top_letters().unwrap(), "HRFTQVWNN");
+    }
+
+    #[test]
+    fn empty_stack_test() {
+        let input = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 3 to 1\n";
+        let crane_stacks = fetch_starting_stacks(input).unwrap();
+        let crane_stacks = execute_crane_instructions(input, crane_stacks).unwrap();
+        let error = crane_stacks.read_top_letters().unwrap_err();
+        assert!(error.to_string().contains("stack 3"), "{error}");
     }
 }
@@ -1,33 +1,42 @@
-use std::io::{self};
-
 use itertools::Itertools;
 
-use crate::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult},
+    solution::{Answer, Solution},
+};
 
-pub fn day_6() -> io::Result<i32> {
-    fetch_pattern_position(4, "./inputs/day-6-input.txt")
-}
+pub struct Day6;
 
-pub fn day_6_part_2() -> io::Result<i32> {
-    fetch_pattern_position(14, "./inputs/day-6-input.txt")
+impl Solution for Day6 {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        6
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        fetch_pattern_position(4, input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        fetch_pattern_position(14, input).map(Answer::from)
+    }
 }
 
-fn fetch_pattern_position(pattern_length: usize, filename: &str) -> io::Result<i32> {
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
-        for (n, _) in line
-            .chars()
-            .into_iter()
-            .skip(pattern_length - 1)
-            .enumerate()
-        {
+fn fetch_pattern_position(pattern_length: usize, input: &str) -> AocResult<i32> {
+    for line in input.lines() {
+        for (n, _) in line.chars().skip(pattern_length - 1).enumerate() {
             let sub = &line[n..(n + pattern_length)];
             if sub.chars().unique().count() == pattern_length {
                 return Ok((n + pattern_length) as i32);
             }
         }
     }
-    panic!("Pattern not found.");
+    Err(AocError::invalid_state(format!(
+        "there is no marker of {pattern_length} distinct characters"
+    )))
 }
 
 #[cfg(test)]
@@ -38,7 +47,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            fetch_pattern_position(4, "./inputs/day-6-input-test.txt").unwrap(),
+            fetch_pattern_position(4, include_str!("../inputs/day-6-input-test.txt")).unwrap(),
             7
         );
     }
@@ -46,7 +55,7 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            fetch_pattern_position(14, "./inputs/day-6-input-test.txt").unwrap(),
+            fetch_pattern_position(14, include_str!("../inputs/day-6-input-test.txt")).unwrap(),
             19
         );
     }
@@ -54,7 +63,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            fetch_pattern_position(4, "./inputs/day-6-input.txt").unwrap(),
+            fetch_pattern_position(4, include_str!("../inputs/day-6-input.txt")).unwrap(),
             1804
         );
     }
@@ -62,7 +71,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            fetch_pattern_position(14, "./inputs/day-6-input.txt").unwrap(),
+            fetch_pattern_position(14, include_str!("../inputs/day-6-input.txt")).unwrap(),
             2508
         );
     }
@@ -1,12 +1,14 @@
 use std::{
     cell::{Ref, RefCell},
-    io::{self},
     rc::Rc,
 };
 
 use itertools::Itertools;
 
-use crate::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    solution::{Answer, Solution},
+};
 
 struct ElfFile {
     pub name: String,
@@ -48,15 +50,29 @@ impl ElfFile {
     }
 }
 
-pub fn day_7() -> io::Result<usize> {
-    let elf_file = fetch_file_tree("./inputs/day-7-input.txt")?;
-    let b = elf_file.borrow();
-    Ok(directory_under(100_000, b))
-}
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        let elf_file = fetch_file_tree(input)?;
+        let b = elf_file.borrow();
+        Ok(Answer::from(directory_under(100_000, b)))
+    }
 
-pub fn day_7_part_2() -> io::Result<usize> {
-    let elf_file = fetch_file_tree("./inputs/day-7-input.txt").unwrap();
-    Ok(directory_to_delete(70000000, 30000000, elf_file.borrow()).unwrap())
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        let elf_file = fetch_file_tree(input)?;
+        directory_to_delete(70000000, 30000000, elf_file.borrow())
+            .map(Answer::from)
+            .ok_or_else(|| AocError::invalid_state("no directory frees enough space"))
+    }
 }
 
 fn directory_to_delete(
@@ -64,7 +80,8 @@ fn directory_to_delete(
     needed_space: usize,
     elf_file: Ref<ElfFile>,
 ) -> Option<usize> {
-    let space_to_free = needed_space - (total_disk_space - elf_file.total_size());
+    let space_to_free =
+        needed_space.saturating_sub(total_disk_space.saturating_sub(elf_file.total_size()));
     search_directory_to_delete(space_to_free, elf_file)
 }
 
@@ -98,13 +115,12 @@ fn directory_under(max_directory_size: usize, elf_file: Ref<ElfFile>) -> usize {
     size
 }
 
-fn fetch_file_tree(filename: &str) -> io::Result<Rc<RefCell<ElfFile>>> {
+fn fetch_file_tree(input: &str) -> AocResult<Rc<RefCell<ElfFile>>> {
     let root = Rc::new(RefCell::new(ElfFile::root()));
 
     let mut current_dir = root.clone();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         // cd command
         if line.starts_with("$ cd") {
             let change_dir = line.replace("$ cd ", "");
@@ -113,7 +129,7 @@ fn fetch_file_tree(filename: &str) -> io::Result<Rc<RefCell<ElfFile>>> {
                 if let Some(parent) = parent_opt {
                     current_dir = parent;
                 } else {
-                    panic!("Trying to get parent of root");
+                    return Err(AocError::parse(i, "the root directory has no parent"));
                 }
             } else if change_dir == "/" {
                 current_dir = root.clone();
@@ -124,13 +140,16 @@ fn fetch_file_tree(filename: &str) -> io::Result<Rc<RefCell<ElfFile>>> {
                 if let Some(found_dir) = found_dir_opt {
                     current_dir = found_dir;
                 } else {
-                    panic!("Trying to cd to '{change_dir}'.");
+                    return Err(AocError::parse(
+                        i,
+                        format!("there is no directory '{change_dir}'"),
+                    ));
                 }
             }
         } else if line == "$ ls" {
             // do nothing I guess
         } else if line.starts_with('$') {
-            panic!("unrecognised command");
+            return Err(AocError::parse(i, format!("unrecognised command '{line}'")));
         } else if line.starts_with("dir ") {
             let name = line.replace("dir ", "");
 
@@ -147,7 +166,7 @@ fn fetch_file_tree(filename: &str) -> io::Result<Rc<RefCell<ElfFile>>> {
         }
         // parse some actual files
         else if let Some((size_s, name)) = line.split(' ').collect_tuple() {
-            let size: usize = size_s.parse().unwrap();
+            let size: usize = size_s.parse().on_line(i)?;
 
             current_dir
                 .as_ref()
@@ -160,7 +179,7 @@ fn fetch_file_tree(filename: &str) -> io::Result<Rc<RefCell<ElfFile>>> {
                     sub_files: Vec::new(),
                 })));
         } else {
-            panic!("Unknown file input.");
+            return Err(AocError::parse(i, format!("unknown listing '{line}'")));
         }
     }
     Ok(root)
@@ -174,7 +193,7 @@ mod tests {
     #[test]
     fn total_size_small_test() {
         assert_eq!(
-            fetch_file_tree("./inputs/day-7-input-test.txt")
+            fetch_file_tree(include_str!("../inputs/day-7-input-test.txt"))
                 .unwrap()
                 .borrow()
                 .total_size(),
@@ -184,18 +203,23 @@ mod tests {
 
     #[test]
     fn small_test() {
-        let test_elf_file = fetch_file_tree("./inputs/day-7-input-test.txt").unwrap();
+        let test_elf_file =
+            fetch_file_tree(include_str!("../inputs/day-7-input-test.txt")).unwrap();
         assert_eq!(directory_under(100_000, test_elf_file.borrow()), 95437);
     }
 
     #[test]
     fn test() {
-        assert_eq!(day_7().unwrap(), 1989474,)
+        assert_eq!(
+            Day7.part_1(include_str!("../inputs/day-7-input.txt"))
+                .unwrap(),
+            Answer::Integer(1989474)
+        )
     }
 
     #[test]
     fn part_2_test() {
-        let test_elf_file = fetch_file_tree("./inputs/day-7-input.txt").unwrap();
+        let test_elf_file = fetch_file_tree(include_str!("../inputs/day-7-input.txt")).unwrap();
         assert_eq!(
             directory_to_delete(70000000, 30000000, test_elf_file.borrow()).unwrap(),
             1111607
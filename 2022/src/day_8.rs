@@ -1,26 +1,38 @@
-use std::{
-    collections::HashSet,
-    io::{self},
+use std::collections::HashSet;
+
+use utils::{
+    coordinate::Coordinate,
+    error::AocResult,
+    grid::Grid,
+    solution::{Answer, Solution},
 };
 
-use itertools::Itertools;
+pub struct Day8;
 
-use crate::file_utils::read_lines;
+impl Solution for Day8 {
+    fn year(&self) -> u32 {
+        2022
+    }
 
-pub fn day_8() -> io::Result<i32> {
-    let visible_trees = fetch_visible_trees("./inputs/day-8-input.txt")?;
-    Ok(visible_trees)
-}
+    fn day(&self) -> u32 {
+        8
+    }
 
-pub fn day_8_part_2() -> io::Result<i32> {
-    let score = fetch_best_score("./inputs/day-8-input.txt")?;
-    Ok(score)
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        let visible_trees = fetch_visible_trees(input)?;
+        Ok(Answer::from(visible_trees))
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        let score = fetch_best_score(input)?;
+        Ok(Answer::from(score))
+    }
 }
 
-fn fetch_best_score(filename: &str) -> io::Result<i32> {
+fn fetch_best_score(input: &str) -> AocResult<i32> {
     let mut highest_score = 0;
 
-    let trees = load_trees(filename)?;
+    let trees = load_trees(input)?;
 
     let row_count = fetch_row_count(&trees);
     let column_count = fetch_column_count(&trees);
@@ -88,10 +100,10 @@ fn fetch_best_score(filename: &str) -> io::Result<i32> {
     Ok(highest_score)
 }
 
-fn fetch_visible_trees(filename: &str) -> io::Result<i32> {
+fn fetch_visible_trees(input: &str) -> AocResult<i32> {
     let mut visible_tree_count = HashSet::new();
 
-    let trees = load_trees(filename)?;
+    let trees = load_trees(input)?;
 
     // left perspective
     for y in 0..fetch_row_count(&trees) {
@@ -141,38 +153,24 @@ fn fetch_visible_trees(filename: &str) -> io::Result<i32> {
     Ok(visible_tree_count.len() as i32)
 }
 
-/**
- * Returns all trees as a vector of rows, eg [y, x].
- */
-fn load_trees(filename: &str) -> io::Result<Vec<Vec<i32>>> {
-    let mut tree_rows = Vec::new();
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
-        let tree_row = line
-            .chars()
-            .map(|c| {
-                if let Ok(tree) = c.to_string().parse::<i32>() {
-                    tree
-                } else {
-                    panic!("Could not parse row.");
-                }
-            })
-            .collect_vec();
-        tree_rows.push(tree_row);
-    }
-    Ok(tree_rows)
+fn load_trees(input: &str) -> AocResult<Grid<i32>> {
+    Grid::parse(input, |c| {
+        c.to_digit(10)
+            .map(|tree| tree as i32)
+            .ok_or_else(|| format!("expected a tree height, found '{c}'"))
+    })
 }
 
-fn fetch_tree(trees: &[Vec<i32>], x: i32, y: i32) -> i32 {
-    trees[y as usize][x as usize]
+fn fetch_tree(trees: &Grid<i32>, x: i32, y: i32) -> i32 {
+    trees[Coordinate::new(x, y)]
 }
 
-fn fetch_row_count(trees: &[Vec<i32>]) -> i32 {
-    trees.len() as i32
+fn fetch_row_count(trees: &Grid<i32>) -> i32 {
+    trees.height() as i32
 }
 
-fn fetch_column_count(trees: &[Vec<i32>]) -> i32 {
-    trees[0].len() as i32
+fn fetch_column_count(trees: &Grid<i32>) -> i32 {
+    trees.width() as i32
 }
 
 #[cfg(test)]
@@ -183,7 +181,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            fetch_visible_trees("./inputs/day-8-input-test.txt").unwrap(),
+            fetch_visible_trees(include_str!("../inputs/day-8-input-test.txt")).unwrap(),
             21
         );
     }
@@ -191,7 +189,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            fetch_visible_trees("./inputs/day-8-input.txt").unwrap(),
+            fetch_visible_trees(include_str!("../inputs/day-8-input.txt")).unwrap(),
             1546
         );
     }
@@ -199,7 +197,7 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            fetch_best_score("./inputs/day-8-input-test.txt").unwrap(),
+            fetch_best_score(include_str!("../inputs/day-8-input-test.txt")).unwrap(),
             8
         );
     }
@@ -207,7 +205,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            fetch_best_score("./inputs/day-8-input.txt").unwrap(),
+            fetch_best_score(include_str!("../inputs/day-8-input.txt")).unwrap(),
             519064
         );
     }
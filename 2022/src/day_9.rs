@@ -1,8 +1,12 @@
-use std::{collections::HashSet, fmt::Display, io};
+use std::{collections::HashSet, fmt::Display};
 
 use itertools::Itertools;
 
-use crate::file_utils::read_lines;
+use utils::{
+    coordinate::Direction4,
+    error::{AocError, AocResult, LineContext},
+    solution::{Answer, Solution},
+};
 
 #[derive(PartialEq, Eq, Clone, Hash, Debug, Copy, Ord, PartialOrd)]
 struct Point {
@@ -62,20 +66,9 @@ impl Rope {
         Rope { knots }
     }
 
-    pub fn move_up(self) -> Rope {
-        self.move_by(Point::new(0, -1))
-    }
-
-    pub fn move_down(self) -> Rope {
-        self.move_by(Point::new(0, 1))
-    }
-
-    pub fn move_left(self) -> Rope {
-        self.move_by(Point::new(-1, 0))
-    }
-
-    pub fn move_right(self) -> Rope {
-        self.move_by(Point::new(1, 0))
+    pub fn move_towards(self, direction: Direction4) -> Rope {
+        let offset = direction.offset();
+        self.move_by(Point::new(offset.x, offset.y))
     }
 
     pub fn move_by(mut self, point: Point) -> Rope {
@@ -101,67 +94,69 @@ impl Rope {
     }
 }
 
-pub fn day_9() -> io::Result<i32> {
-    let positions = fetch_tail_position_count("./inputs/day-9-input.txt")?;
-    Ok(positions)
-}
+pub struct Day9;
+
+impl Solution for Day9 {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        9
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        let positions = fetch_tail_position_count(input)?;
+        Ok(Answer::from(positions))
+    }
 
-pub fn day_9_part_2() -> io::Result<i32> {
-    let positions = fetch_long_tail_position_count(10, "./inputs/day-9-input.txt")?;
-    Ok(positions)
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        let positions = fetch_long_tail_position_count(10, input)?;
+        Ok(Answer::from(positions))
+    }
 }
 
-fn fetch_tail_position_count(filename: &str) -> io::Result<i32> {
+fn fetch_tail_position_count(input: &str) -> AocResult<i32> {
     let mut positions = HashSet::new();
     let mut rope = Rope::new(2);
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
-        if let Some((direction, number_s)) = line.split(' ').collect_tuple() {
-            if let Ok(number) = number_s.parse::<i32>() {
-                for _ in 0..number {
-                    rope = match direction {
-                        "U" => rope.move_up(),
-                        "L" => rope.move_left(),
-                        "D" => rope.move_down(),
-                        "R" => rope.move_right(),
-                        d => panic!("Unknown movement type '{d}'."),
-                    };
-                    positions.insert(*rope.knots.last().unwrap());
-                }
-            } else {
-                panic!("Bad number input");
-            }
+        let (direction, number_s) = line
+            .split(' ')
+            .collect_tuple()
+            .ok_or_else(|| AocError::parse(i, "expected a direction and a distance"))?;
+        let direction = direction
+            .parse::<Direction4>()
+            .map_err(|err| AocError::parse(i, err))?;
+        let number = number_s.parse::<i32>().on_line(i)?;
+        for _ in 0..number {
+            rope = rope.move_towards(direction);
+            positions.insert(*rope.knots.last().unwrap());
         }
     }
     Ok(positions.len() as i32)
 }
 
-fn fetch_long_tail_position_count(length: i32, filename: &str) -> io::Result<i32> {
+fn fetch_long_tail_position_count(length: i32, input: &str) -> AocResult<i32> {
     let mut positions = HashSet::new();
     let mut rope = Rope::new(length);
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
-        if let Some((direction, number_s)) = line.split(' ').collect_tuple() {
-            if let Ok(number) = number_s.parse::<i32>() {
-                for _ in 0..number {
-                    rope = match direction {
-                        "U" => rope.move_up(),
-                        "L" => rope.move_left(),
-                        "D" => rope.move_down(),
-                        "R" => rope.move_right(),
-                        d => panic!("Unknown movement type '{d}'."),
-                    };
-                    positions.insert(*rope.knots.last().unwrap());
-                }
-            } else {
-                panic!("Bad number input");
-            }
+        let (direction, number_s) = line
+            .split(' ')
+            .collect_tuple()
+            .ok_or_else(|| AocError::parse(i, "expected a direction and a distance"))?;
+        let direction = direction
+            .parse::<Direction4>()
+            .map_err(|err| AocError::parse(i, err))?;
+        let number = number_s.parse::<i32>().on_line(i)?;
+        for _ in 0..number {
+            rope = rope.move_towards(direction);
+            positions.insert(*rope.knots.last().unwrap());
         }
     }
 
@@ -176,7 +171,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            fetch_tail_position_count("./inputs/day-9-input-test.txt").unwrap(),
+            fetch_tail_position_count(include_str!("../inputs/day-9-input-test.txt")).unwrap(),
             13
         );
     }
@@ -184,7 +179,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            fetch_tail_position_count("./inputs/day-9-input.txt").unwrap(),
+            fetch_tail_position_count(include_str!("../inputs/day-9-input.txt")).unwrap(),
             6266
         )
     }
@@ -192,7 +187,8 @@ mod tests {
     #[test]
     fn very_small_part_2_test() {
         assert_eq!(
-            fetch_long_tail_position_count(10, "./inputs/day-9-input-test.txt").unwrap(),
+            fetch_long_tail_position_count(10, include_str!("../inputs/day-9-input-test.txt"))
+                .unwrap(),
             1
         );
     }
@@ -200,7 +196,11 @@ mod tests {
     #[test]
     fn small_part_2_test() {
         assert_eq!(
-            fetch_long_tail_position_count(10, "./inputs/day-9-input-part-2-test.txt").unwrap(),
+            fetch_long_tail_position_count(
+                10,
+                include_str!("../inputs/day-9-input-part-2-test.txt")
+            )
+            .unwrap(),
             36
         );
     }
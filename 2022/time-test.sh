@@ -2,9 +2,8 @@
 
 set -e
 
-cargo build
-cargo build --release
-
-time target/debug/advent-of-code
-time target/release/advent-of-code
+cargo build -p aoc
+cargo build -p aoc --release
 
+time ../target/debug/aoc run --year 2022
+time ../target/release/aoc run --year 2022
@@ -1,40 +1,42 @@
-use std::io::{self};
-use utils::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult},
+    solution::{Answer, Solution},
+};
 
 const WORDS: [&str; 9] = [
     "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
 ];
 
-pub fn day_1() -> io::Result<i32> {
-    fetch_digits("./inputs/day-1-input.txt")
-}
-pub fn day_1_part_2() -> io::Result<i32> {
-    fetch_digits_and_words("./inputs/day-1-input.txt")
-}
+pub struct Day1;
 
-fn read_digits(line: &str) -> i32 {
-    let mut result = 0;
-    // read from left
-    for c in line.chars() {
-        if c.is_ascii_digit() {
-            result = c.to_string().parse::<i32>().unwrap() * 10;
-            break;
-        }
+impl Solution for Day1 {
+    fn year(&self) -> u32 {
+        2023
     }
 
-    // read from right
-    for c in line.chars().rev() {
-        if c.is_ascii_digit() {
-            result += c.to_string().parse::<i32>().unwrap();
-            break;
-        }
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        fetch_digits(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        fetch_digits_and_words(input).map(Answer::from)
     }
-    result
 }
 
-fn read_digits_and_words(line: &str) -> i32 {
-    let mut result = 0;
+/**
+ * Combines the first and last digit on the line, if it has any.
+ */
+fn read_digits(line: &str) -> Option<i32> {
+    let first = line.chars().find_map(|c| c.to_digit(10))?;
+    let last = line.chars().rev().find_map(|c| c.to_digit(10))?;
+    Some((first * 10 + last) as i32)
+}
 
+fn read_digits_and_words(line: &str) -> Option<i32> {
     let mut mod_line = line.to_string();
 
     for (i, w) in WORDS.iter().enumerate() {
@@ -46,46 +48,30 @@ fn read_digits_and_words(line: &str) -> i32 {
         mod_line = mod_line.replace(w, &rep);
     }
 
-    // read from left
-    for c in mod_line.chars() {
-        if c.is_ascii_digit() {
-            result = c.to_string().parse::<i32>().unwrap() * 10;
-            break;
-        }
-    }
-
-    // read from right
-    for c in mod_line.chars().rev() {
-        if c.is_ascii_digit() {
-            result += c.to_string().parse::<i32>().unwrap();
-            break;
-        }
-    }
-    result
+    read_digits(&mod_line)
 }
 
-fn fetch_digits(filename: &str) -> io::Result<i32> {
+fn fetch_digits(input: &str) -> AocResult<i32> {
     let mut result = 0;
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
-        result += read_digits(line.as_str());
+        result += read_digits(line).ok_or_else(|| AocError::parse(i, "the line has no digits"))?;
     }
     Ok(result)
 }
 
-fn fetch_digits_and_words(filename: &str) -> io::Result<i32> {
+fn fetch_digits_and_words(input: &str) -> AocResult<i32> {
     let mut result = 0;
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
-        result += read_digits_and_words(line.as_str());
+        result += read_digits_and_words(line)
+            .ok_or_else(|| AocError::parse(i, "the line has no digits"))?;
     }
     Ok(result)
 }
@@ -97,18 +83,24 @@ mod tests {
 
     #[test]
     fn small_test() {
-        assert_eq!(fetch_digits("./inputs/day-1-input-test.txt").unwrap(), 142);
+        assert_eq!(
+            fetch_digits(include_str!("../inputs/day-1-input-test.txt")).unwrap(),
+            142
+        );
     }
 
     #[test]
     fn test() {
-        assert_eq!(fetch_digits("./inputs/day-1-input.txt").unwrap(), 54644);
+        assert_eq!(
+            fetch_digits(include_str!("../inputs/day-1-input.txt")).unwrap(),
+            54644
+        );
     }
 
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            fetch_digits_and_words("./inputs/day-1-input-test-2.txt").unwrap(),
+            fetch_digits_and_words(include_str!("../inputs/day-1-input-test-2.txt")).unwrap(),
             281
         );
     }
@@ -116,7 +108,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            fetch_digits_and_words("./inputs/day-1-input.txt").unwrap(),
+            fetch_digits_and_words(include_str!("../inputs/day-1-input.txt")).unwrap(),
             53348
         );
     }
@@ -1,6 +1,8 @@
 use itertools::Itertools;
-use std::io::{self};
-use utils::file_utils::read_lines;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    solution::{Answer, Solution},
+};
 
 struct Game {
     id: i32,
@@ -13,15 +15,28 @@ struct Hint {
     blue: i32,
 }
 
-pub fn day_2() -> io::Result<i32> {
-    fetch_possible_games("./inputs/day-2-input.txt")
-}
-pub fn day_2_part_2() -> io::Result<i32> {
-    fetch_power_of_games("./inputs/day-2-input.txt")
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        fetch_possible_games(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        fetch_power_of_games(input).map(Answer::from)
+    }
 }
 
-fn fetch_possible_games(filename: &str) -> io::Result<i32> {
-    let games = fetch_games(filename)?;
+fn fetch_possible_games(input: &str) -> AocResult<i32> {
+    let games = fetch_games(input)?;
 
     let mut result = 0;
 
@@ -42,8 +57,8 @@ fn fetch_possible_games(filename: &str) -> io::Result<i32> {
     Ok(result)
 }
 
-fn fetch_power_of_games(filename: &str) -> io::Result<i32> {
-    let games = fetch_games(filename)?;
+fn fetch_power_of_games(input: &str) -> AocResult<i32> {
+    let games = fetch_games(input)?;
 
     let mut result = 0;
 
@@ -61,17 +76,16 @@ fn fetch_power_of_games(filename: &str) -> io::Result<i32> {
     Ok(result)
 }
 
-fn fetch_games(filename: &str) -> io::Result<Vec<Game>> {
+fn fetch_games(input: &str) -> AocResult<Vec<Game>> {
     let mut games = Vec::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
         if let Some((game_id_str, game_info_str)) = line.split(':').collect_tuple() {
-            let game_id = sscanf::sscanf!(game_id_str, "Game {}", i32).unwrap();
+            let game_id = sscanf::sscanf!(game_id_str, "Game {}", i32).on_line(i)?;
 
             let mut hints = Vec::new();
             for game_info in game_info_str.split(';') {
@@ -85,7 +99,7 @@ fn fetch_games(filename: &str) -> io::Result<Vec<Game>> {
                         .chars()
                         .filter(|c| c.is_ascii_digit())
                         .collect::<String>();
-                    let number = number_str.parse::<i32>().unwrap();
+                    let number = number_str.parse::<i32>().on_line(i)?;
 
                     if colour.contains("red") {
                         hint.red = number;
@@ -94,7 +108,10 @@ fn fetch_games(filename: &str) -> io::Result<Vec<Game>> {
                     } else if colour.contains("blue") {
                         hint.blue = number;
                     } else {
-                        panic!("Unknown colour. {}", colour);
+                        return Err(AocError::parse(
+                            i,
+                            format!("unknown colour '{}'", colour.trim()),
+                        ));
                     }
                 }
                 hints.push(hint);
@@ -103,7 +120,7 @@ fn fetch_games(filename: &str) -> io::Result<Vec<Game>> {
             let game = Game { id: game_id, hints };
             games.push(game);
         } else {
-            panic!("Unexpected parts after colon split.");
+            return Err(AocError::parse(i, "expected 'Game N: hints'"));
         }
     }
     Ok(games)
@@ -117,7 +134,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            fetch_possible_games("./inputs/day-2-input-test.txt").unwrap(),
+            fetch_possible_games(include_str!("../inputs/day-2-input-test.txt")).unwrap(),
             8
         );
     }
@@ -125,7 +142,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            fetch_possible_games("./inputs/day-2-input.txt").unwrap(),
+            fetch_possible_games(include_str!("../inputs/day-2-input.txt")).unwrap(),
             2563
         );
     }
@@ -133,7 +150,7 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            fetch_power_of_games("./inputs/day-2-input-test.txt").unwrap(),
+            fetch_power_of_games(include_str!("../inputs/day-2-input-test.txt")).unwrap(),
             2286
         );
     }
@@ -141,7 +158,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            fetch_power_of_games("./inputs/day-2-input.txt").unwrap(),
+            fetch_power_of_games(include_str!("../inputs/day-2-input.txt")).unwrap(),
             70768
         );
     }
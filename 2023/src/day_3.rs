@@ -1,37 +1,48 @@
 use std::{
     collections::{HashMap, HashSet},
-    io::{self},
     option,
 };
-use utils::{coordinate::Coordinate, file_utils::read_lines};
+use utils::{
+    coordinate::Coordinate,
+    error::AocResult,
+    solution::{Answer, Solution},
+};
 
 struct Schematic {
     symbol_coords: HashMap<Coordinate, char>,
     digit_coords: HashMap<Coordinate, i32>,
 }
 
-pub fn day_3() -> io::Result<i32> {
-    fetch_part_numbers("./inputs/day-3-input.txt")
-}
-pub fn day_3_part_2() -> io::Result<i32> {
-    fetch_gear_ratios("./inputs/day-3-input.txt")
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        Ok(Answer::from(fetch_part_numbers(input)))
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        Ok(Answer::from(fetch_gear_ratios(input)))
+    }
 }
 
 fn fetch_digit_from_vec(vec: &[char]) -> option::Option<i32> {
     let res = vec.iter().collect::<String>().parse::<i32>();
-    if let Ok(digit) = res {
-        Some(digit)
-    } else {
-        None
-    }
+    res.ok()
 }
 
-fn fetch_schematic(filename: &str) -> io::Result<Schematic> {
+fn fetch_schematic(input: &str) -> Schematic {
     let mut symbol_coords = HashMap::new();
     let mut digit_coords = HashMap::new();
 
-    let lines = read_lines(filename)?;
-    for (y, line) in lines.flatten().enumerate() {
+    for (y, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
@@ -61,15 +72,15 @@ fn fetch_schematic(filename: &str) -> io::Result<Schematic> {
             }
         }
     }
-    let schematic = Schematic {
+
+    Schematic {
         digit_coords,
         symbol_coords,
-    };
-    Ok(schematic)
+    }
 }
 
-fn fetch_part_numbers(filename: &str) -> io::Result<i32> {
-    let schematic = fetch_schematic(filename)?;
+fn fetch_part_numbers(input: &str) -> i32 {
+    let schematic = fetch_schematic(input);
 
     let mut part_number_sum = 0;
 
@@ -83,11 +94,11 @@ fn fetch_part_numbers(filename: &str) -> io::Result<i32> {
         }
         part_number_sum += found_parts.iter().sum::<i32>();
     }
-    Ok(part_number_sum)
+    part_number_sum
 }
 
-fn fetch_gear_ratios(filename: &str) -> io::Result<i32> {
-    let schematic = fetch_schematic(filename)?;
+fn fetch_gear_ratios(input: &str) -> i32 {
+    let schematic = fetch_schematic(input);
 
     let mut part_number_sum = 0;
 
@@ -108,7 +119,7 @@ fn fetch_gear_ratios(filename: &str) -> io::Result<i32> {
         }
         part_number_sum += found_parts.iter().product::<i32>();
     }
-    Ok(part_number_sum)
+    part_number_sum
 }
 
 #[cfg(test)]
@@ -119,7 +130,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            fetch_part_numbers("./inputs/day-3-input-test.txt").unwrap(),
+            fetch_part_numbers(include_str!("../inputs/day-3-input-test.txt")),
             4361
         );
     }
@@ -127,7 +138,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            fetch_part_numbers("./inputs/day-3-input.txt").unwrap(),
+            fetch_part_numbers(include_str!("../inputs/day-3-input.txt")),
             528799
         );
     }
@@ -135,7 +146,7 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            fetch_gear_ratios("./inputs/day-3-input-test.txt").unwrap(),
+            fetch_gear_ratios(include_str!("../inputs/day-3-input-test.txt")),
             467835
         );
     }
@@ -143,7 +154,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            fetch_gear_ratios("./inputs/day-3-input.txt").unwrap(),
+            fetch_gear_ratios(include_str!("../inputs/day-3-input.txt")),
             84907174
         );
     }
@@ -1,9 +1,9 @@
 use itertools::Itertools;
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    io::{self},
+use std::collections::HashSet;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    solution::{Answer, Solution},
 };
-use utils::file_utils::read_lines;
 
 struct ScratchCard {
     card_id: i32,
@@ -11,62 +11,108 @@ struct ScratchCard {
     numbers: HashSet<i32>,
 }
 
-pub fn day_4() -> io::Result<i32> {
-    fetch_scores("./inputs/day-4-input.txt")
+impl ScratchCard {
+    fn match_count(&self) -> usize {
+        self.numbers
+            .iter()
+            .filter(|x| self.winning_numbers.contains(x))
+            .count()
+    }
 }
-pub fn day_4_part_2() -> io::Result<i32> {
-    fetch_winning_cards_amount("./inputs/day-4-input.txt")
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        fetch_scores(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        fetch_winning_cards_amount(input).map(Answer::from)
+    }
 }
 
-fn fetch_scratch_cards(filename: &str) -> io::Result<Vec<ScratchCard>> {
-    let mut scratch_cards = Vec::new();
+fn fetch_scratch_cards(input: &str) -> AocResult<Vec<ScratchCard>> {
+    let mut scratch_cards: Vec<ScratchCard> = Vec::new();
+    let mut card_lines = Vec::new();
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
 
         if let Some((card_str, numbers_str)) = line.split(':').collect_tuple() {
             let card_id_str = card_str.replace("Card ", "");
-            let card_id = card_id_str.trim().parse::<i32>().unwrap();
+            let card_id = card_id_str.trim().parse::<i32>().on_line(i)?;
+            let expected_id = scratch_cards.len() as i32 + 1;
+            if card_id != expected_id {
+                return Err(AocError::parse(
+                    i,
+                    format!("expected card {expected_id} but found card {card_id}"),
+                ));
+            }
 
             if let Some((winning_str, game_numbers_str)) = numbers_str.split('|').collect_tuple() {
                 let winning_numbers = winning_str
                     .split(' ')
                     .filter(|x| !x.is_empty())
-                    .map(|x| x.parse::<i32>().unwrap())
-                    .collect_vec();
+                    .map(|x| x.parse::<i32>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .on_line(i)?;
                 let numbers = game_numbers_str
                     .split(' ')
                     .filter(|x| !x.is_empty())
-                    .map(|x| x.trim().parse::<i32>().unwrap());
+                    .map(|x| x.trim().parse::<i32>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .on_line(i)?;
                 scratch_cards.push(ScratchCard {
                     card_id,
                     winning_numbers: HashSet::from_iter(winning_numbers),
                     numbers: HashSet::from_iter(numbers),
-                })
+                });
+                card_lines.push(i);
             } else {
-                panic!("Unexpected number format");
+                return Err(AocError::parse(
+                    i,
+                    "expected winning numbers and numbers separated by '|'",
+                ));
             }
         } else {
-            panic!("Unexpected line format.")
+            return Err(AocError::parse(i, "expected 'Card N: numbers'"));
+        }
+    }
+
+    // cards only win copies of the cards after them, so none can win past the end of the table
+    for (card, i) in scratch_cards.iter().zip(card_lines) {
+        let last_card = card.card_id as usize + card.match_count();
+        if last_card > scratch_cards.len() {
+            return Err(AocError::parse(
+                i,
+                format!(
+                    "card {} wins a copy of missing card {last_card}",
+                    card.card_id
+                ),
+            ));
         }
     }
     Ok(scratch_cards)
 }
 
-fn fetch_scores(filename: &str) -> io::Result<i32> {
-    let cards = fetch_scratch_cards(filename)?;
+fn fetch_scores(input: &str) -> AocResult<i32> {
+    let cards = fetch_scratch_cards(input)?;
 
     let mut score = 0;
 
     for card in cards {
-        let count = card
-            .numbers
-            .iter()
-            .filter(|x| card.winning_numbers.contains(x))
-            .count();
+        let count = card.match_count();
         if count > 0 {
             score += 2_i32.pow(count as u32 - 1_u32);
         }
@@ -74,34 +120,29 @@ fn fetch_scores(filename: &str) -> io::Result<i32> {
     Ok(score)
 }
 
-fn fetch_winning_cards_amount(filename: &str) -> io::Result<i32> {
-    let cards = fetch_scratch_cards(filename)?;
-    let mut score_map = HashMap::new();
-    let mut card_stack = VecDeque::new();
-
-    // pre-calc winnings
-    for card in cards {
-        let count = card
-            .numbers
-            .iter()
-            .filter(|x| card.winning_numbers.contains(x))
-            .count() as i32;
-
-        score_map.insert(card.card_id, count);
-        card_stack.push_back(card.card_id);
-    }
-
-    let mut winning_cards = 0;
-
-    while let Some(id) = card_stack.pop_front() {
-        winning_cards += 1;
-
-        let score = score_map.get(&id).unwrap();
-        for n in (id + 1)..=(id + score) {
-            card_stack.push_back(n);
+fn fetch_winning_cards_amount(input: &str) -> AocResult<i32> {
+    let cards = fetch_scratch_cards(input)?;
+    let mut copies = vec![1_i32; cards.len()];
+
+    // cards only win copies of later cards, so each count is final by the time we reach it
+    for (index, card) in cards.iter().enumerate() {
+        let count = copies[index];
+        let won = (index + 1)..=(index + card.match_count());
+        for (won_index, copy) in copies[won].iter_mut().enumerate() {
+            *copy = copy.checked_add(count).ok_or_else(|| {
+                AocError::invalid_state(format!(
+                    "too many copies of card {}",
+                    index + won_index + 2
+                ))
+            })?;
         }
     }
-    Ok(winning_cards)
+
+    copies.iter().try_fold(0_i32, |total, count| {
+        total
+            .checked_add(*count)
+            .ok_or_else(|| AocError::invalid_state("too many cards to count"))
+    })
 }
 
 #[cfg(test)]
@@ -111,18 +152,24 @@ mod tests {
 
     #[test]
     fn small_test() {
-        assert_eq!(fetch_scores("./inputs/day-4-input-test.txt").unwrap(), 13);
+        assert_eq!(
+            fetch_scores(include_str!("../inputs/day-4-input-test.txt")).unwrap(),
+            13
+        );
     }
 
     #[test]
     fn test() {
-        assert_eq!(fetch_scores("./inputs/day-4-input.txt").unwrap(), 22193);
+        assert_eq!(
+            fetch_scores(include_str!("../inputs/day-4-input.txt")).unwrap(),
+            22193
+        );
     }
 
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            fetch_winning_cards_amount("./inputs/day-4-input-test.txt").unwrap(),
+            fetch_winning_cards_amount(include_str!("../inputs/day-4-input-test.txt")).unwrap(),
             30
         );
     }
@@ -130,8 +177,20 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            fetch_winning_cards_amount("./inputs/day-4-input.txt").unwrap(),
+            fetch_winning_cards_amount(include_str!("../inputs/day-4-input.txt")).unwrap(),
             5625994
         );
     }
+
+    #[test]
+    fn invalid_test() {
+        let input = include_str!("../inputs/day-4-input-test.txt");
+        let error = fetch_winning_cards_amount(&input.replacen("Card 2", "Card 1", 1)).unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: 2, .. }), "{error}");
+
+        // card 6 is the last card, so any match wins a copy of a card that doesn't exist
+        let mutated = input.replacen("74 77 10 23 35", "31 18 13 56 72", 1);
+        let error = fetch_winning_cards_amount(&mutated).unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: 6, .. }), "{error}");
+    }
 }
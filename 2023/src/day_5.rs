@@ -1,22 +1,12 @@
 use itertools::Itertools;
-use std::{
-    collections::VecDeque,
-    io::{self},
+use std::collections::VecDeque;
+use utils::{
+    error::{AocError, AocResult, LineContext},
+    interval::{Interval, IntervalSet, Mapping},
+    solution::{Answer, Solution},
 };
-use utils::file_utils::read_lines;
 
-#[derive(Debug)]
-struct AlmanacRange {
-    start: i64,
-    length: i64,
-}
-
-#[derive(Default)]
-struct AlmanacMap {
-    destination_range_start: i64,
-    source_range_start: i64,
-    range_length: i64,
-}
+type AlmanacMap = Mapping<i64>;
 
 struct Almanac {
     seeds: Vec<i64>,
@@ -29,14 +19,27 @@ struct Almanac {
     humidity_to_location_map: Vec<AlmanacMap>,
 }
 
-pub fn day_5() -> io::Result<i64> {
-    fetch_lowest_location("./inputs/day-5-input.txt")
-}
-pub fn day_5_part_2() -> io::Result<i64> {
-    fetch_seed_range_lowest_location("./inputs/day-5-input.txt")
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        fetch_lowest_location(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        fetch_seed_range_lowest_location(input).map(Answer::from)
+    }
 }
 
-fn fetch_almanac(filename: &str) -> io::Result<Almanac> {
+fn fetch_almanac(input: &str) -> AocResult<Almanac> {
     let mut almananc_maps = VecDeque::new();
     for _ in 0..7 {
         almananc_maps.push_back(Vec::new());
@@ -44,8 +47,7 @@ fn fetch_almanac(filename: &str) -> io::Result<Almanac> {
     let mut map_index = -1_i32;
     let mut seeds = None;
 
-    let lines = read_lines(filename)?;
-    for line in lines.flatten() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
@@ -56,8 +58,9 @@ fn fetch_almanac(filename: &str) -> io::Result<Almanac> {
                     .skip(1)
                     .map(|p| p.trim())
                     .filter(|p| !p.is_empty())
-                    .map(|p| p.parse::<i64>().unwrap())
-                    .collect_vec(),
+                    .map(|p| p.parse::<i64>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .on_line(i)?,
             );
             continue;
         }
@@ -71,19 +74,26 @@ fn fetch_almanac(filename: &str) -> io::Result<Almanac> {
             .split(' ')
             .map(|p| p.trim())
             .filter(|p| !p.is_empty())
-            .map(|p| p.parse::<i64>().unwrap())
+            .map(|p| p.parse::<i64>().on_line(i))
             .collect_tuple()
-            .unwrap();
-
-        let maps = almananc_maps.get_mut(map_index as usize).unwrap();
-        maps.push(AlmanacMap {
-            destination_range_start: destination,
-            source_range_start: source,
-            range_length: range,
-        });
+            .ok_or_else(|| AocError::parse(i, "expected a destination, source and range length"))?;
+
+        let maps = usize::try_from(map_index)
+            .ok()
+            .and_then(|index| almananc_maps.get_mut(index))
+            .ok_or_else(|| AocError::parse(i, "the range is not part of a known map"))?;
+        maps.push(AlmanacMap::new(
+            Interval::from_length(source?, range?),
+            destination?,
+        ));
     }
-    let almanac = Almanac {
-        seeds: seeds.unwrap(),
+
+    let seeds = seeds
+        .filter(|seeds| !seeds.is_empty())
+        .ok_or_else(|| AocError::invalid_state("there are no seeds"))?;
+
+    Ok(Almanac {
+        seeds,
         seed_to_soil_map: almananc_maps.pop_front().unwrap(),
         soil_to_fertilizer_map: almananc_maps.pop_front().unwrap(),
         fertilizer_to_water_map: almananc_maps.pop_front().unwrap(),
@@ -91,97 +101,17 @@ fn fetch_almanac(filename: &str) -> io::Result<Almanac> {
         light_to_temperature_map: almananc_maps.pop_front().unwrap(),
         temperature_to_humidity_map: almananc_maps.pop_front().unwrap(),
         humidity_to_location_map: almananc_maps.pop_front().unwrap(),
-    };
-    Ok(almanac)
-}
-
-fn resolve_map_value(maps: &Vec<AlmanacMap>, value: i64) -> i64 {
-    for map in maps {
-        if value >= map.source_range_start && value < map.source_range_start + map.range_length {
-            return (value - map.source_range_start) + map.destination_range_start;
-        }
-    }
-
-    value
+    })
 }
 
-fn resolve_map_ranges(maps: &Vec<AlmanacMap>, ranges: Vec<AlmanacRange>) -> Vec<AlmanacRange> {
-    let mut ranges_to_process = VecDeque::new();
-    for r in ranges {
-        debug_assert!(r.start >= 0);
-        debug_assert!(r.length > 0);
-        ranges_to_process.push_back(r);
-    }
-
-    let mut result_ranges = Vec::new();
-
-    while let Some(range) = ranges_to_process.pop_front() {
-        let mut map_found = false;
-        for map in maps {
-            if range.start >= map.source_range_start
-                && range.start < map.source_range_start + map.range_length
-            {
-                let dest_range_start =
-                    (range.start - map.source_range_start) + map.destination_range_start;
-
-                // does range fit entirely in map?
-                if range.length <= map.range_length {
-                    result_ranges.push(AlmanacRange {
-                        start: dest_range_start,
-                        length: range.length,
-                    });
-                } else {
-                    let r1_start = dest_range_start;
-                    let r1_length = (map.source_range_start + map.range_length) - range.start;
-                    let r2_start = map.source_range_start + map.range_length;
-                    let r2_length = range.length - r1_length;
-
-                    result_ranges.push(AlmanacRange {
-                        start: r1_start,
-                        length: r1_length,
-                    });
-                    ranges_to_process.push_back(AlmanacRange {
-                        start: r2_start,
-                        length: r2_length,
-                    });
-                }
-                map_found = true;
-                break;
-            } else if range.start + range.length >= map.source_range_start
-                && range.start + range.length < map.source_range_start + map.range_length
-            {
-
-                let r1_start = range.start;
-                let r1_length = (map.source_range_start - range.start) - 1;                
-                let r2_start = map.destination_range_start;
-                let r2_length = range.length - r1_length;
-                if range.start == r1_start && range.length == r1_length {
-                    panic!("Duped range: {:?}", range);
-                }
-
-                ranges_to_process.push_back(AlmanacRange {
-                    start: r1_start,
-                    length: r1_length,
-                });
-                result_ranges.push(AlmanacRange {
-                    start: r2_start,
-                    length: r2_length,
-                });
-
-                map_found = true;
-                break;
-            }
-        }
-        if !map_found {
-            result_ranges.push(range);
-        }
-    }
-
-    result_ranges
+fn resolve_map_value(maps: &[AlmanacMap], value: i64) -> i64 {
+    maps.iter()
+        .find_map(|map| map.translate(value))
+        .unwrap_or(value)
 }
 
-fn fetch_lowest_location(filename: &str) -> io::Result<i64> {
-    let almanac = fetch_almanac(filename)?;
+fn fetch_lowest_location(input: &str) -> AocResult<i64> {
+    let almanac = fetch_almanac(input)?;
 
     let mut locations = Vec::new();
 
@@ -199,36 +129,27 @@ fn fetch_lowest_location(filename: &str) -> io::Result<i64> {
     Ok(locations.first().unwrap().to_owned())
 }
 
-fn fetch_seed_range_lowest_location(filename: &str) -> io::Result<i64> {
-    let almanac = fetch_almanac(filename)?;
+fn fetch_seed_range_lowest_location(input: &str) -> AocResult<i64> {
+    let almanac = fetch_almanac(input)?;
 
-    let mut seed_ranges = Vec::new();
+    let mut seed_ranges = IntervalSet::new();
     for chunk in almanac.seeds.chunks(2) {
-        seed_ranges.push(AlmanacRange {
-            start: chunk.first().unwrap().to_owned(),
-            length: chunk.get(1).unwrap().to_owned(),
-        });
+        let [start, length] = chunk else {
+            return Err(AocError::invalid_state("seed ranges must come in pairs"));
+        };
+        seed_ranges.insert(Interval::from_length(*start, *length));
     }
 
-    let mut locations = Vec::new();
-
-    let soil = resolve_map_ranges(&almanac.seed_to_soil_map, seed_ranges);
-    let fertilizer = resolve_map_ranges(&almanac.soil_to_fertilizer_map, soil);
-    let water = resolve_map_ranges(&almanac.fertilizer_to_water_map, fertilizer);
-    let light = resolve_map_ranges(&almanac.water_to_light_map, water);
-    let temperature = resolve_map_ranges(&almanac.light_to_temperature_map, light);
-    let humidity = resolve_map_ranges(&almanac.temperature_to_humidity_map, temperature);
-    let location_ranges = resolve_map_ranges(&almanac.humidity_to_location_map, humidity);
-    for location_range in location_ranges {
-        locations.push(location_range.start);
-        //for location in location_range.start..(location_range.start + location_range.length) {
-        //    locations.push(location)
-        //}
-    }
-
-    locations.sort();
-    //println!("debug: {:?}", locations);
-    Ok(locations.first().unwrap().to_owned())
+    let soil = seed_ranges.translate(&almanac.seed_to_soil_map);
+    let fertilizer = soil.translate(&almanac.soil_to_fertilizer_map);
+    let water = fertilizer.translate(&almanac.fertilizer_to_water_map);
+    let light = water.translate(&almanac.water_to_light_map);
+    let temperature = light.translate(&almanac.light_to_temperature_map);
+    let humidity = temperature.translate(&almanac.temperature_to_humidity_map);
+    let locations = humidity.translate(&almanac.humidity_to_location_map);
+    locations
+        .min()
+        .ok_or_else(|| AocError::invalid_state("there are no seeds in the seed ranges"))
 }
 
 #[cfg(test)]
@@ -239,7 +160,7 @@ mod tests {
     #[test]
     fn small_test() {
         assert_eq!(
-            fetch_lowest_location("./inputs/day-5-input-test.txt").unwrap(),
+            fetch_lowest_location(include_str!("../inputs/day-5-input-test.txt")).unwrap(),
             35
         );
     }
@@ -247,7 +168,7 @@ mod tests {
     #[test]
     fn test() {
         assert_eq!(
-            fetch_lowest_location("./inputs/day-5-input.txt").unwrap(),
+            fetch_lowest_location(include_str!("../inputs/day-5-input.txt")).unwrap(),
             227653707
         );
     }
@@ -255,7 +176,8 @@ mod tests {
     #[test]
     fn part_2_small_test() {
         assert_eq!(
-            fetch_seed_range_lowest_location("./inputs/day-5-input-test.txt").unwrap(),
+            fetch_seed_range_lowest_location(include_str!("../inputs/day-5-input-test.txt"))
+                .unwrap(),
             46
         );
     }
@@ -263,7 +185,7 @@ mod tests {
     #[test]
     fn part_2_test() {
         assert_eq!(
-            fetch_seed_range_lowest_location("./inputs/day-5-input.txt").unwrap(),
+            fetch_seed_range_lowest_location(include_str!("../inputs/day-5-input.txt")).unwrap(),
             78775051
         );
     }
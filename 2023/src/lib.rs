@@ -0,0 +1,45 @@
+pub mod day_1;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+
+use utils::solution::Registry;
+
+/**
+ * Directory of this crate. Puzzle inputs are kept in its `inputs` directory.
+ */
+pub const DIRECTORY: &str = env!("CARGO_MANIFEST_DIR");
+
+/**
+ * Registers every 2023 solution.
+ */
+pub fn register(registry: &mut Registry) {
+    registry.register(day_1::Day1);
+    registry.register(day_2::Day2);
+    registry.register(day_3::Day3);
+    registry.register(day_4::Day4);
+    registry.register(day_5::Day5);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn register_test() {
+        let mut registry = Registry::new();
+        register(&mut registry);
+
+        assert_eq!(registry.len(), 5);
+        assert!(registry.iter().all(|s| s.year() == 2023));
+        assert!(registry
+            .iter()
+            .map(|s| s.day())
+            .tuple_windows()
+            .all(|(a, b)| a < b));
+    }
+}
@@ -0,0 +1,142 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+use serde::Deserialize;
+use utils::solution::Answer;
+
+use crate::input::input_filename;
+
+/**
+ * Name of the file, kept in each year's inputs directory, listing the expected answers.
+ */
+pub const ANSWERS_FILENAME: &str = "answers.toml";
+
+/**
+ * The expected answers for one year's inputs.
+ */
+#[derive(Deserialize, Debug, Default)]
+pub struct Manifest {
+    #[serde(default, rename = "answer")]
+    pub answers: Vec<ExpectedAnswer>,
+}
+
+/**
+ * The answer a day's part should give for an input.
+ */
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedAnswer {
+    pub day: u32,
+    pub part: u32,
+    /**
+     * Input file name within the inputs directory. Defaults to the day's usual input.
+     */
+    pub input: Option<String>,
+    pub answer: String,
+}
+
+impl ExpectedAnswer {
+    /**
+     * Gets the input file name this answer is for.
+     */
+    pub fn input_filename(&self) -> String {
+        self.input
+            .clone()
+            .unwrap_or_else(|| input_filename(self.day))
+    }
+
+    /**
+     * Whether the given answer matches. Surrounding whitespace is ignored so that art can be
+     * written as a multi-line string.
+     */
+    pub fn matches(&self, answer: &Answer) -> bool {
+        answer.to_string().trim() == self.answer.trim()
+    }
+}
+
+impl Manifest {
+    pub fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    /**
+     * Loads a manifest from a file. A missing file is treated as an empty manifest.
+     */
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Manifest::default()),
+            Err(err) => return Err(err),
+        };
+        Manifest::parse(&text).map_err(|err| {
+            io::Error::new(
+                ErrorKind::InvalidData,
+                format!("could not parse {path}: {err}", path = path.display()),
+            )
+        })
+    }
+
+    /**
+     * Gets the expected answers for a day's part, in the order they are listed.
+     */
+    pub fn expected(&self, day: u32, part: u32) -> Vec<&ExpectedAnswer> {
+        self.answers
+            .iter()
+            .filter(|expected| expected.day == day && expected.part == part)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const MANIFEST: &str = r#"
+[[answer]]
+day = 1
+part = 1
+answer = "24000"
+
+[[answer]]
+day = 1
+part = 1
+input = "day-1-input-test.txt"
+answer = "7"
+
+[[answer]]
+day = 10
+part = 2
+answer = '''
+#..#
+####
+'''
+"#;
+
+    #[test]
+    fn parse_test() {
+        let manifest = Manifest::parse(MANIFEST).unwrap();
+        assert_eq!(manifest.answers.len(), 3);
+
+        let expected = manifest.expected(1, 1);
+        assert_eq!(expected.len(), 2);
+        assert_eq!(expected[0].input_filename(), "day-1-input.txt");
+        assert_eq!(expected[1].input_filename(), "day-1-input-test.txt");
+        assert!(expected[0].matches(&Answer::from(24000)));
+        assert!(!expected[1].matches(&Answer::from(24000)));
+
+        assert!(manifest.expected(1, 2).is_empty());
+        assert!(manifest.expected(10, 2)[0].matches(&Answer::Art("#..#\n####".to_string())));
+    }
+
+    #[test]
+    fn invalid_test() {
+        assert!(Manifest::parse("[[answer]]\nday = 1\n").is_err());
+        assert!(Manifest::load(Path::new("/nonexistent/answers.toml"))
+            .unwrap()
+            .answers
+            .is_empty());
+    }
+}
@@ -0,0 +1,283 @@
+use std::{
+    fs, io,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use utils::{
+    error::AocResult,
+    memo::{self, MemoStats},
+    solution::Answer,
+};
+
+use crate::puzzle::Puzzle;
+
+/**
+ * Minimum, median and maximum of repeated timings, in nanoseconds.
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Stats {
+    /**
+     * Summarises the samples, which must not be empty. The median of an even number of samples
+     * is the higher of the middle two.
+     */
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut nanos = samples
+            .iter()
+            .map(|d| d.as_nanos().try_into().unwrap_or(u64::MAX))
+            .collect::<Vec<u64>>();
+        nanos.sort_unstable();
+        Stats {
+            min_ns: nanos[0],
+            median_ns: nanos[nanos.len() / 2],
+            max_ns: nanos[nanos.len() - 1],
+        }
+    }
+}
+
+/**
+ * Timings for one part of a day's puzzle.
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Benchmark {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    /**
+     * Time spent parsing, when the solution parses apart from solving. Otherwise parsing is
+     * counted in the solve time.
+     */
+    pub parse: Option<Stats>,
+    pub solve: Stats,
+    /**
+     * How often the named memo caches used by the last run found what they were asked for. These
+     * aren't timings, so they aren't saved in baselines.
+     */
+    #[serde(skip)]
+    pub memos: Vec<(&'static str, MemoStats)>,
+}
+
+impl Benchmark {
+    /**
+     * Gets the median time for parsing and solving together.
+     */
+    pub fn median_ns(&self) -> u64 {
+        self.parse.map_or(0, |p| p.median_ns) + self.solve.median_ns
+    }
+}
+
+/**
+ * Solves a puzzle the given number of times, timing parsing and solving separately where the
+ * solution allows it, and collecting the statistics of any named memo caches it uses.
+ */
+pub fn benchmark(puzzle: &Puzzle, input: &str, runs: usize) -> AocResult<(Answer, Benchmark)> {
+    let solution = puzzle.solution;
+    let mut parse_samples = Vec::with_capacity(runs);
+    let mut solve_samples = Vec::with_capacity(runs);
+    let mut answer = None;
+    let mut memos = Vec::new();
+    memo::take_reports();
+
+    for _ in 0..runs.max(1) {
+        if solution.parses_separately() {
+            let start = Instant::now();
+            let parsed = solution.parse(input)?;
+            parse_samples.push(start.elapsed());
+
+            let start = Instant::now();
+            answer = Some(solution.solve_parsed(puzzle.part, &parsed)?);
+            solve_samples.push(start.elapsed());
+        } else {
+            let start = Instant::now();
+            answer = Some(puzzle.solve(input)?);
+            solve_samples.push(start.elapsed());
+        }
+        memos = memo::take_reports();
+    }
+
+    let benchmark = Benchmark {
+        year: puzzle.year(),
+        day: puzzle.day(),
+        part: puzzle.part,
+        parse: (!parse_samples.is_empty()).then(|| Stats::from_samples(&parse_samples)),
+        solve: Stats::from_samples(&solve_samples),
+        memos,
+    };
+    Ok((answer.expect("at least one run"), benchmark))
+}
+
+/**
+ * Earlier benchmark results, stored as JSON, that new results are compared against.
+ */
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Baseline {
+    pub benchmarks: Vec<Benchmark>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        serde_json::from_str(&text).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("could not parse {path}: {err}", path = path.display()),
+            )
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let text = serde_json::to_string_pretty(self)?;
+        fs::write(path, text + "\n")
+    }
+
+    pub fn get(&self, year: u32, day: u32, part: u32) -> Option<&Benchmark> {
+        self.benchmarks
+            .iter()
+            .find(|b| b.year == year && b.day == day && b.part == part)
+    }
+
+    /**
+     * Gets how much slower the benchmark is than its baseline, as a fraction of the baseline's
+     * median. Faster results are negative.
+     */
+    pub fn change(&self, benchmark: &Benchmark) -> Option<f64> {
+        let baseline = self
+            .get(benchmark.year, benchmark.day, benchmark.part)?
+            .median_ns();
+        (baseline > 0).then(|| (benchmark.median_ns() as f64 - baseline as f64) / baseline as f64)
+    }
+
+    /**
+     * Replaces the stored results for each of the benchmarks, keeping results for other puzzles.
+     */
+    pub fn update(&mut self, benchmarks: &[Benchmark]) {
+        for benchmark in benchmarks {
+            self.benchmarks.retain(|b| {
+                (b.year, b.day, b.part) != (benchmark.year, benchmark.day, benchmark.part)
+            });
+            self.benchmarks.push(benchmark.clone());
+        }
+        self.benchmarks.sort_by_key(|b| (b.year, b.day, b.part));
+    }
+}
+
+/**
+ * Formats nanoseconds with a unit suited to their size.
+ */
+pub fn format_duration(ns: u64) -> String {
+    match ns {
+        0..=999 => format!("{ns}ns"),
+        1_000..=999_999 => format!("{:.1}µs", ns as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.1}ms", ns as f64 / 1e6),
+        _ => format!("{:.2}s", ns as f64 / 1e9),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::puzzle::registry;
+
+    use super::*;
+
+    fn benchmark_of(day: u32, parse: Option<u64>, solve: u64) -> Benchmark {
+        let stats = |ns| Stats {
+            min_ns: ns,
+            median_ns: ns,
+            max_ns: ns,
+        };
+        Benchmark {
+            year: 2022,
+            day,
+            part: 1,
+            parse: parse.map(stats),
+            solve: stats(solve),
+            memos: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn stats_test() {
+        let samples = [5, 1, 3, 2]
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect::<Vec<_>>();
+        let stats = Stats::from_samples(&samples);
+        assert_eq!(stats.min_ns, 1_000_000);
+        assert_eq!(stats.median_ns, 3_000_000);
+        assert_eq!(stats.max_ns, 5_000_000);
+    }
+
+    #[test]
+    fn benchmark_test() {
+        let registry = registry();
+        let puzzle = Puzzle {
+            solution: registry.get(2022, 16).unwrap(),
+            part: 1,
+        };
+        let input = include_str!("../../2022/inputs/day-16-input-test.txt");
+        let (answer, timings) = benchmark(&puzzle, input, 3).unwrap();
+        assert_eq!(answer, Answer::Integer(1651));
+        assert!(timings.parse.is_some());
+        // Only the last run's cache is reported.
+        let [(name, stats)] = timings.memos[..] else {
+            panic!("expected one memo, found {:?}", timings.memos);
+        };
+        assert_eq!(name, "most_pressure");
+        assert!(stats.hits > 0 && stats.misses > 0);
+
+        let puzzle = Puzzle {
+            solution: registry.get(2022, 1).unwrap(),
+            part: 1,
+        };
+        let (answer, timings) = benchmark(&puzzle, "1\n2\n\n4\n\n", 3).unwrap();
+        assert_eq!(answer, Answer::Integer(4));
+        assert!(timings.parse.is_none());
+        assert!(timings.solve.min_ns <= timings.solve.max_ns);
+        assert!(timings.memos.is_empty());
+    }
+
+    #[test]
+    fn baseline_test() {
+        let mut baseline = Baseline::default();
+        baseline.update(&[
+            benchmark_of(19, Some(100), 900),
+            benchmark_of(16, None, 500),
+        ]);
+        assert_eq!(baseline.benchmarks[0].day, 16);
+
+        assert_eq!(
+            baseline.change(&benchmark_of(19, Some(200), 1300)),
+            Some(0.5)
+        );
+        assert_eq!(baseline.change(&benchmark_of(16, None, 250)), Some(-0.5));
+        assert_eq!(baseline.change(&benchmark_of(24, None, 250)), None);
+
+        baseline.update(&[benchmark_of(16, None, 250)]);
+        assert_eq!(baseline.benchmarks.len(), 2);
+        assert_eq!(baseline.get(2022, 16, 1).unwrap().median_ns(), 250);
+
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("baseline.json");
+        baseline.save(&path).unwrap();
+        assert_eq!(
+            Baseline::load(&path).unwrap().benchmarks,
+            baseline.benchmarks
+        );
+    }
+
+    #[test]
+    fn format_duration_test() {
+        assert_eq!(format_duration(999), "999ns");
+        assert_eq!(format_duration(1_500), "1.5µs");
+        assert_eq!(format_duration(20_250_000), "20.2ms");
+        assert_eq!(format_duration(3_000_000_000), "3.00s");
+    }
+}
@@ -0,0 +1,215 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+/**
+ * Site that inputs are downloaded from by default.
+ */
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/**
+ * Makes the HTTP requests used to download inputs, so tests can stand in for the real site.
+ */
+pub trait HttpClient {
+    /**
+     * Gets the body of the page at the URL, sending the session token as a cookie. Responses
+     * other than success are errors.
+     */
+    fn get(&self, url: &str, session: &str) -> io::Result<String>;
+}
+
+/**
+ * Client making real requests.
+ */
+pub struct UreqClient {
+    agent: ureq::Agent,
+}
+
+impl UreqClient {
+    pub fn new() -> Self {
+        UreqClient {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(concat!("aoc/", env!("CARGO_PKG_VERSION")))
+                .build(),
+        }
+    }
+}
+
+impl Default for UreqClient {
+    fn default() -> Self {
+        UreqClient::new()
+    }
+}
+
+impl HttpClient for UreqClient {
+    fn get(&self, url: &str, session: &str) -> io::Result<String> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={session}"))
+            .call()
+            .map_err(|err| match err {
+                ureq::Error::Status(status, _) => {
+                    io::Error::other(format!("{url} responded with status {status}"))
+                }
+                ureq::Error::Transport(transport) => {
+                    io::Error::other(format!("could not get {url}: {transport}"))
+                }
+            })?;
+        response.into_string()
+    }
+}
+
+/**
+ * Whether an input had to be downloaded.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded,
+    Cached,
+}
+
+/**
+ * Downloads inputs into an inputs directory, skipping any that are already there.
+ */
+pub struct Fetcher<C: HttpClient> {
+    pub client: C,
+    pub base_url: String,
+    /**
+     * Session cookie of a logged in user. Only needed when something has to be downloaded.
+     */
+    pub session: Option<String>,
+}
+
+impl<C: HttpClient> Fetcher<C> {
+    /**
+     * Gets the URL of a day's input.
+     */
+    pub fn url(&self, year: u32, day: u32) -> String {
+        format!(
+            "{base}/{year}/day/{day}/input",
+            base = self.base_url.trim_end_matches('/')
+        )
+    }
+
+    /**
+     * Makes sure the day's input exists at the path, downloading it if it doesn't.
+     */
+    pub fn fetch(&self, year: u32, day: u32, path: &Path) -> io::Result<Fetched> {
+        if path.exists() {
+            return Ok(Fetched::Cached);
+        }
+        let session = self.session.as_deref().ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::NotFound,
+                "a session token is needed to download inputs, set AOC_SESSION or pass --session",
+            )
+        })?;
+
+        let input = self.client.get(&self.url(year, day), session)?;
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        // Written alongside then renamed, so an interrupted download isn't taken as cached.
+        let partial = partial_path(path);
+        fs::write(&partial, input)?;
+        fs::rename(&partial, path)?;
+        Ok(Fetched::Downloaded)
+    }
+}
+
+fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".partial");
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::{cell::RefCell, collections::HashMap};
+
+    use super::*;
+
+    /**
+     * Serves pages from a map, recording what was requested.
+     */
+    #[derive(Default)]
+    struct FakeClient {
+        pages: HashMap<String, String>,
+        requests: RefCell<Vec<(String, String)>>,
+    }
+
+    impl HttpClient for FakeClient {
+        fn get(&self, url: &str, session: &str) -> io::Result<String> {
+            self.requests
+                .borrow_mut()
+                .push((url.to_string(), session.to_string()));
+            self.pages
+                .get(url)
+                .cloned()
+                .ok_or_else(|| io::Error::other("404"))
+        }
+    }
+
+    fn fetcher(session: Option<&str>) -> Fetcher<FakeClient> {
+        let mut client = FakeClient::default();
+        client.pages.insert(
+            "http://localhost:8080/2022/day/1/input".to_string(),
+            "1000\n2000\n".to_string(),
+        );
+        Fetcher {
+            client,
+            base_url: "http://localhost:8080/".to_string(),
+            session: session.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn fetch_test() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("2022").join("day-1-input.txt");
+        let fetcher = fetcher(Some("abc"));
+
+        assert_eq!(fetcher.fetch(2022, 1, &path).unwrap(), Fetched::Downloaded);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+        assert!(!partial_path(&path).exists());
+        assert_eq!(
+            *fetcher.client.requests.borrow(),
+            vec![(
+                "http://localhost:8080/2022/day/1/input".to_string(),
+                "abc".to_string()
+            )]
+        );
+
+        assert_eq!(fetcher.fetch(2022, 1, &path).unwrap(), Fetched::Cached);
+        assert_eq!(fetcher.client.requests.borrow().len(), 1);
+    }
+
+    #[test]
+    fn cached_without_session_test() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("day-1-input.txt");
+        fs::write(&path, "already here").unwrap();
+
+        let fetcher = fetcher(None);
+        assert_eq!(fetcher.fetch(2022, 1, &path).unwrap(), Fetched::Cached);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "already here");
+        assert!(fetcher.client.requests.borrow().is_empty());
+    }
+
+    #[test]
+    fn failed_fetch_test() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("day-2-input.txt");
+
+        assert_eq!(
+            fetcher(None).fetch(2022, 2, &path).unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+        assert!(fetcher(Some("abc")).fetch(2022, 2, &path).is_err());
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,89 @@
+use clap::Args;
+
+use crate::puzzle::Puzzle;
+
+/**
+ * Restricts which puzzles are selected. Unset fields match everything.
+ */
+#[derive(Args, Debug, Default, Clone)]
+pub struct Filter {
+    /// Only select puzzles from this year.
+    #[arg(short, long)]
+    pub year: Option<u32>,
+
+    /// Only select puzzles from this day.
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    pub day: Option<u32>,
+
+    /// Only select this part of each day.
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=2))]
+    pub part: Option<u32>,
+}
+
+impl Filter {
+    pub fn matches(&self, puzzle: &Puzzle) -> bool {
+        self.year.is_none_or(|year| year == puzzle.year())
+            && self.day.is_none_or(|day| day == puzzle.day())
+            && self.part.is_none_or(|part| part == puzzle.part)
+    }
+
+    /**
+     * Selects the matching puzzles, keeping their order.
+     */
+    pub fn select<'a>(&self, puzzles: Vec<Puzzle<'a>>) -> Vec<Puzzle<'a>> {
+        puzzles.into_iter().filter(|p| self.matches(p)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::puzzle::{puzzles, registry};
+
+    use super::*;
+
+    #[test]
+    fn select_all_test() {
+        let registry = registry();
+        let all = puzzles(&registry).len();
+        assert_eq!(Filter::default().select(puzzles(&registry)).len(), all);
+    }
+
+    #[test]
+    fn select_day_part_test() {
+        let filter = Filter {
+            year: Some(2022),
+            day: Some(16),
+            part: Some(2),
+        };
+        let registry = registry();
+        let selected = filter.select(puzzles(&registry));
+        assert_eq!(selected.len(), 1);
+        assert_eq!(
+            (selected[0].year(), selected[0].day(), selected[0].part),
+            (2022, 16, 2)
+        );
+    }
+
+    #[test]
+    fn select_year_test() {
+        let filter = Filter {
+            year: Some(2023),
+            ..Default::default()
+        };
+        let registry = registry();
+        let selected = filter.select(puzzles(&registry));
+        assert!(!selected.is_empty());
+        assert!(selected.iter().all(|p| p.year() == 2023));
+    }
+
+    #[test]
+    fn select_missing_test() {
+        let filter = Filter {
+            year: Some(2022),
+            day: Some(25),
+            part: Some(2),
+        };
+        assert!(filter.select(puzzles(&registry())).is_empty());
+    }
+}
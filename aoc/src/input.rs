@@ -0,0 +1,189 @@
+use std::{
+    fs,
+    io::{self, ErrorKind, Read},
+    path::PathBuf,
+};
+
+use clap::Args;
+
+/**
+ * Command line options choosing where puzzle inputs are read from.
+ */
+#[derive(Args, Debug, Default, Clone)]
+pub struct InputArgs {
+    /// Read the input for the selected day from this file, or from stdin if `-`.
+    #[arg(short, long, conflicts_with = "inputs")]
+    pub input: Option<PathBuf>,
+
+    /// Directory of inputs laid out as `<year>/day-<day>-input.txt`. Defaults to the inputs kept
+    /// alongside each year's solutions.
+    #[arg(long, env = "AOC_INPUTS")]
+    pub inputs: Option<PathBuf>,
+}
+
+/**
+ * Source of puzzle inputs.
+ */
+pub enum Inputs {
+    /**
+     * A directory of inputs for every year. When not set, each year's own `inputs` directory is
+     * used.
+     */
+    Directory(Option<PathBuf>),
+    /**
+     * A single file, used for whichever day is being solved.
+     */
+    File(PathBuf),
+    /**
+     * Input that has already been read, such as from stdin.
+     */
+    Text(String),
+}
+
+impl Inputs {
+    /**
+     * Creates the input source from command line options. Stdin is read immediately.
+     */
+    pub fn from_args(args: &InputArgs) -> io::Result<Self> {
+        match &args.input {
+            Some(path) if path.as_os_str() == "-" => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text)?;
+                Ok(Inputs::Text(text))
+            }
+            Some(path) => Ok(Inputs::File(path.clone())),
+            None => Ok(Inputs::Directory(args.inputs.clone())),
+        }
+    }
+
+    /**
+     * Whether the same input is used no matter which day is solved.
+     */
+    pub fn is_single(&self) -> bool {
+        !matches!(self, Inputs::Directory(_))
+    }
+
+    /**
+     * Gets the path of the input file for the given day, if it comes from a file.
+     */
+    pub fn path(&self, year: u32, day: u32) -> Option<PathBuf> {
+        match self {
+            Inputs::Directory(_) => self
+                .directory(year)
+                .map(|directory| directory.join(input_filename(day))),
+            Inputs::File(path) => Some(path.clone()),
+            Inputs::Text(_) => None,
+        }
+    }
+
+    /**
+     * Gets the directory holding the given year's inputs, if inputs come from a directory.
+     */
+    pub fn directory(&self, year: u32) -> Option<PathBuf> {
+        match self {
+            Inputs::Directory(Some(directory)) => Some(directory.join(year.to_string())),
+            Inputs::Directory(None) => default_directory(year),
+            Inputs::File(_) | Inputs::Text(_) => None,
+        }
+    }
+
+    /**
+     * Describes where the input for the given day comes from, for use in messages.
+     */
+    pub fn describe(&self, year: u32, day: u32) -> String {
+        match self.path(year, day) {
+            Some(path) => path.display().to_string(),
+            None => "stdin".to_string(),
+        }
+    }
+
+    /**
+     * Reads the input for the given day.
+     */
+    pub fn read(&self, year: u32, day: u32) -> io::Result<String> {
+        if let Inputs::Text(text) = self {
+            return Ok(text.clone());
+        }
+        let path = self
+            .path(year, day)
+            .ok_or_else(|| io::Error::new(ErrorKind::NotFound, format!("no inputs for {year}")))?;
+        fs::read_to_string(&path).map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("could not read {path}: {err}", path = path.display()),
+            )
+        })
+    }
+}
+
+/**
+ * Gets the file name used for a day's input.
+ */
+pub fn input_filename(day: u32) -> String {
+    format!("day-{day}-input.txt")
+}
+
+/**
+ * Gets the inputs directory kept alongside a year's solutions.
+ */
+pub fn default_directory(year: u32) -> Option<PathBuf> {
+    crate_directory(year).map(|directory| directory.join("inputs"))
+}
+
+/**
+ * Gets the directory of a year's crate.
+ */
+pub fn crate_directory(year: u32) -> Option<PathBuf> {
+    let directory = match year {
+        2021 => advent_of_code_2021::DIRECTORY,
+        2022 => advent_of_code_2022::DIRECTORY,
+        2023 => advent_of_code_2023::DIRECTORY,
+        _ => return None,
+    };
+    Some(PathBuf::from(directory))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn default_directory_test() {
+        let inputs = Inputs::Directory(None);
+        let path = inputs.path(2022, 16).unwrap();
+        assert!(path.ends_with("2022/inputs/day-16-input.txt"));
+        assert!(inputs.read(2022, 16).unwrap().starts_with("Valve"));
+    }
+
+    #[test]
+    fn directory_test() {
+        let inputs = Inputs::Directory(Some(PathBuf::from("/tmp/aoc")));
+        assert_eq!(
+            inputs.path(2021, 3).unwrap(),
+            PathBuf::from("/tmp/aoc/2021/day-3-input.txt")
+        );
+        assert_eq!(
+            inputs.directory(2021).unwrap(),
+            PathBuf::from("/tmp/aoc/2021")
+        );
+    }
+
+    #[test]
+    fn missing_year_test() {
+        let inputs = Inputs::Directory(None);
+        assert!(inputs.path(2015, 1).is_none());
+        assert_eq!(
+            inputs.read(2015, 1).unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn text_test() {
+        let inputs = Inputs::Text("1\n2\n".to_string());
+        assert!(inputs.is_single());
+        assert_eq!(inputs.read(2022, 1).unwrap(), "1\n2\n");
+        assert_eq!(inputs.describe(2022, 1), "stdin");
+    }
+}
@@ -0,0 +1,10 @@
+pub mod answers;
+pub mod bench;
+pub mod fetch;
+pub mod filter;
+pub mod input;
+pub mod puzzle;
+pub mod report;
+pub mod scaffold;
+pub mod verify;
+pub mod visualise;
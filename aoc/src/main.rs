@@ -0,0 +1,553 @@
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use aoc::{
+    bench::{benchmark, format_duration, Baseline, Stats},
+    fetch::{Fetched, Fetcher, UreqClient, DEFAULT_BASE_URL},
+    filter::Filter,
+    input::{crate_directory, InputArgs, Inputs},
+    puzzle::{puzzles, registry, Puzzle},
+    report::{
+        solve_all, write_records, write_text_record, write_year_heading, Format, Record, Status,
+    },
+    scaffold::scaffold,
+    verify::{verify, Check, Outcome},
+    visualise::view,
+};
+use clap::{Args, Parser, Subcommand};
+use itertools::Itertools;
+use utils::memo::MemoStats;
+
+#[derive(Parser)]
+#[command(about = "Runs Advent of Code solutions.")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs the selected puzzles. This is the default command.
+    Run(RunArgs),
+    /// Lists the implemented puzzles.
+    List(Filter),
+    /// Times the selected puzzles over repeated runs, optionally comparing against a baseline.
+    ///
+    /// Parse time is only timed apart from solve time for days that parse their input up front,
+    /// such as 2022 days 16, 19 and 24. The other days parse in each part, so their parsing is
+    /// counted in the solve time and the parse column shows "in solve".
+    Bench(BenchArgs),
+    /// Downloads a day's input into the inputs directory, unless it is already there.
+    Fetch(FetchArgs),
+    /// Creates a new day's module, registers it and adds empty input files.
+    New(NewArgs),
+    /// Checks the selected puzzles against the answers listed in each year's `answers.toml`.
+    Verify(VerifyArgs),
+}
+
+#[derive(Args, Default)]
+struct RunArgs {
+    #[command(flatten)]
+    filter: Filter,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// How to write the results.
+    #[arg(short, long, value_enum, default_value_t)]
+    format: Format,
+
+    /// Number of puzzles to solve at once, or 0 for one per CPU. Results are still written in
+    /// order.
+    #[arg(short, long, default_value_t = 1)]
+    jobs: usize,
+
+    /// Step through the selected part's simulation in the terminal instead of solving it. Only
+    /// days that simulate something step by step support this.
+    #[arg(long, conflicts_with_all = ["format", "jobs"])]
+    visualise: bool,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[command(flatten)]
+    filter: Filter,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Number of times to solve each puzzle.
+    #[arg(short = 'n', long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    runs: u32,
+
+    /// Compare against results saved in this JSON file, flagging slower puzzles.
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// Save the results to this JSON file, replacing earlier results for the same puzzles.
+    #[arg(long)]
+    save_baseline: Option<PathBuf>,
+
+    /// Percentage a median time can grow by before it is flagged as a regression.
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    #[command(flatten)]
+    filter: Filter,
+
+    /// Directory of inputs laid out as `<year>/day-<day>-input.txt`, with each year's expected
+    /// answers in `<year>/answers.toml`. Defaults to the inputs kept alongside each year's
+    /// solutions.
+    #[arg(long, env = "AOC_INPUTS")]
+    inputs: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct FetchArgs {
+    /// Year of the puzzle.
+    #[arg(short, long)]
+    year: u32,
+
+    /// Day of the puzzle.
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: u32,
+
+    /// Directory of inputs laid out as `<year>/day-<day>-input.txt`. Defaults to the inputs kept
+    /// alongside each year's solutions.
+    #[arg(long, env = "AOC_INPUTS")]
+    inputs: Option<PathBuf>,
+
+    /// Session cookie of a logged in Advent of Code user.
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+
+    /// Site to download from, such as a local stand-in server.
+    #[arg(long, env = "AOC_BASE_URL", default_value = DEFAULT_BASE_URL)]
+    base_url: String,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    /// Year of the puzzle. Its crate must already exist.
+    #[arg(short, long)]
+    year: u32,
+
+    /// Day of the puzzle.
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: u32,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let registry = registry();
+    match cli
+        .command
+        .unwrap_or_else(|| Command::Run(RunArgs::default()))
+    {
+        Command::Run(args) => match Inputs::from_args(&args.input) {
+            Ok(inputs) if args.visualise => {
+                visualise(args.filter.select(puzzles(&registry)), &inputs)
+            }
+            Ok(inputs) => run(
+                args.filter.select(puzzles(&registry)),
+                &inputs,
+                args.format,
+                args.jobs,
+            ),
+            Err(err) => {
+                eprintln!("Could not read input: {err}");
+                ExitCode::FAILURE
+            }
+        },
+        Command::List(filter) => list(filter.select(puzzles(&registry))),
+        Command::Bench(args) => match Inputs::from_args(&args.input) {
+            Ok(inputs) => bench(args.filter.select(puzzles(&registry)), &inputs, &args),
+            Err(err) => {
+                eprintln!("Could not read input: {err}");
+                ExitCode::FAILURE
+            }
+        },
+        Command::Fetch(args) => fetch(args),
+        Command::New(args) => new_day(args.year, args.day),
+        Command::Verify(args) => verify_answers(
+            args.filter.select(puzzles(&registry)),
+            &Inputs::Directory(args.inputs),
+        ),
+    }
+}
+
+/**
+ * Checks that puzzles were selected and that a single input is only used for one day.
+ */
+fn check_selection(puzzles: &[Puzzle], inputs: &Inputs) -> bool {
+    if puzzles.is_empty() {
+        eprintln!("No puzzles match.");
+        return false;
+    }
+    if inputs.is_single() && !puzzles.iter().map(|p| (p.year(), p.day())).all_equal() {
+        eprintln!("A single input can only be used when one day is selected.");
+        return false;
+    }
+    true
+}
+
+fn run(puzzles: Vec<Puzzle>, inputs: &Inputs, format: Format, jobs: usize) -> ExitCode {
+    if !check_selection(&puzzles, inputs) {
+        return ExitCode::FAILURE;
+    }
+
+    let mut stdout = io::stdout().lock();
+    let mut records = Vec::new();
+    let mut result = Ok(());
+    let mut year = None;
+    // Text is written as each puzzle finishes, other formats once everything has run.
+    let solved = solve_all(&puzzles, inputs, jobs, |record| {
+        if format == Format::Text && result.is_ok() {
+            result = write_text(&record, &mut year, &mut stdout);
+        }
+        records.push(record);
+    });
+    if let Err(err) = solved {
+        eprintln!("Could not start threads: {err}");
+        return ExitCode::FAILURE;
+    }
+    if format == Format::Text {
+        result = result.and(writeln!(stdout));
+    } else {
+        result = write_records(&records, format, &mut stdout);
+    }
+
+    if let Err(err) = result {
+        eprintln!("Could not write results: {err}");
+        return ExitCode::FAILURE;
+    }
+    if records.iter().any(|r| r.status == Status::Failed) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn visualise(puzzles: Vec<Puzzle>, inputs: &Inputs) -> ExitCode {
+    let [puzzle] = puzzles[..] else {
+        eprintln!("Select a single part to visualise, such as with --year 2022 --day 24 --part 1.");
+        return ExitCode::FAILURE;
+    };
+    let (year, day, part) = (puzzle.year(), puzzle.day(), puzzle.part);
+    let simulation = match inputs.read(year, day).map(|input| puzzle.simulate(&input)) {
+        Ok(Ok(simulation)) => simulation,
+        Ok(Err(err)) => {
+            eprintln!("Could not visualise {year} day {day} part {part}: {err}");
+            return ExitCode::FAILURE;
+        }
+        Err(err) => {
+            eprintln!("Could not read input: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match view(format!("{year} day {day} part {part}"), simulation) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Could not show the simulation: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/**
+ * Writes a record as text, starting a new year's heading when the year changes.
+ */
+fn write_text(record: &Record, year: &mut Option<u32>, mut writer: impl Write) -> io::Result<()> {
+    if *year != Some(record.year) {
+        if year.is_some() {
+            writeln!(writer)?;
+        }
+        write_year_heading(record.year, &mut writer)?;
+        *year = Some(record.year);
+    }
+    write_text_record(record, writer)
+}
+
+fn list(puzzles: Vec<Puzzle>) -> ExitCode {
+    for ((year, day), parts) in &puzzles.iter().group_by(|p| (p.year(), p.day())) {
+        let parts = parts.map(|p| p.part).join(", ");
+        println!("{year} day {day:>2}: part {parts}");
+    }
+    ExitCode::SUCCESS
+}
+
+fn bench(puzzles: Vec<Puzzle>, inputs: &Inputs, args: &BenchArgs) -> ExitCode {
+    if !check_selection(&puzzles, inputs) {
+        return ExitCode::FAILURE;
+    }
+    let baseline = match args.baseline.as_deref().map(Baseline::load).transpose() {
+        Ok(baseline) => baseline,
+        Err(err) => {
+            eprintln!("Could not read baseline: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut rows = Vec::new();
+    let mut benchmarks = Vec::new();
+    for puzzle in &puzzles {
+        let result = inputs
+            .read(puzzle.year(), puzzle.day())
+            .map_err(|err| err.to_string())
+            .and_then(|input| {
+                benchmark(puzzle, &input, args.runs as usize).map_err(|err| {
+                    let source = inputs.describe(puzzle.year(), puzzle.day());
+                    format!("{source}: {err}")
+                })
+            });
+        let row = match result {
+            Ok((_, benchmark)) => {
+                let change = baseline.as_ref().and_then(|b| b.change(&benchmark));
+                let row = BenchRow {
+                    puzzle: *puzzle,
+                    result: Ok([
+                        benchmark.parse.map_or("in solve".to_string(), format_stats),
+                        format_stats(benchmark.solve),
+                        change.map_or("-".to_string(), |c| format!("{:+.1}%", c * 100.0)),
+                    ]),
+                    regressed: change.is_some_and(|c| c * 100.0 > args.threshold),
+                    memos: benchmark.memos.clone(),
+                };
+                benchmarks.push(benchmark);
+                row
+            }
+            Err(err) => BenchRow {
+                puzzle: *puzzle,
+                result: Err(err),
+                regressed: false,
+                memos: Vec::new(),
+            },
+        };
+        rows.push(row);
+    }
+
+    print_benchmarks(&rows);
+
+    let regressions = rows.iter().filter(|row| row.regressed).count();
+    if baseline.is_some() {
+        println!();
+        println!(
+            "{regressions} regression{s} over {threshold}%",
+            s = if regressions == 1 { "" } else { "s" },
+            threshold = args.threshold
+        );
+    }
+
+    if let Some(path) = &args.save_baseline {
+        let mut saved = match Baseline::load(path) {
+            Ok(saved) => saved,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Baseline::default(),
+            Err(err) => {
+                eprintln!("Could not read baseline: {err}");
+                return ExitCode::FAILURE;
+            }
+        };
+        saved.update(&benchmarks);
+        if let Err(err) = saved.save(path) {
+            eprintln!("Could not save baseline: {err}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if regressions > 0 || rows.iter().any(|row| row.result.is_err()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/**
+ * A row of the bench table, holding the parse, solve and change cells or why the puzzle failed.
+ */
+struct BenchRow<'a> {
+    puzzle: Puzzle<'a>,
+    result: Result<[String; 3], String>,
+    regressed: bool,
+    /**
+     * The statistics of the memo caches the puzzle used, shown under its timings.
+     */
+    memos: Vec<(&'static str, MemoStats)>,
+}
+
+fn format_stats(stats: Stats) -> String {
+    format!(
+        "{} / {} / {}",
+        format_duration(stats.min_ns),
+        format_duration(stats.median_ns),
+        format_duration(stats.max_ns)
+    )
+}
+
+fn print_benchmarks(rows: &[BenchRow]) {
+    let headers = ["Parse min / median / max", "Solve min / median / max"];
+    // Widths are counted in chars, as `{:width$}` pads by chars and `µ` takes two bytes.
+    let widths = [0, 1].map(|column| {
+        rows.iter()
+            .filter_map(|row| row.result.as_ref().ok())
+            .map(|cells| cells[column].chars().count())
+            .chain([headers[column].len()])
+            .max()
+            .unwrap_or(0)
+    });
+
+    println!(
+        "Year  Day  Part  {:w0$}  {:w1$}  Change",
+        headers[0],
+        headers[1],
+        w0 = widths[0],
+        w1 = widths[1]
+    );
+    for row in rows {
+        let label = format!(
+            "{year}  {day:>3}  {part:>4}",
+            year = row.puzzle.year(),
+            day = row.puzzle.day(),
+            part = row.puzzle.part
+        );
+        match &row.result {
+            Ok([parse, solve, change]) => {
+                let flag = if row.regressed { "  REGRESSION" } else { "" };
+                println!(
+                    "{label}  {parse:w0$}  {solve:w1$}  {change}{flag}",
+                    w0 = widths[0],
+                    w1 = widths[1]
+                );
+            }
+            Err(err) => println!("{label}  failed: {err}"),
+        }
+        for (name, stats) in &row.memos {
+            println!(
+                "{:indent$}memo {name}: {stats}",
+                "",
+                indent = label.len() + 2
+            );
+        }
+    }
+}
+
+fn fetch(args: FetchArgs) -> ExitCode {
+    let (year, day) = (args.year, args.day);
+    let Some(path) = Inputs::Directory(args.inputs).path(year, day) else {
+        eprintln!("There is no inputs directory for {year}, pass --inputs to choose one.");
+        return ExitCode::FAILURE;
+    };
+    let fetcher = Fetcher {
+        client: UreqClient::new(),
+        base_url: args.base_url,
+        session: args.session,
+    };
+    match fetcher.fetch(year, day, &path) {
+        Ok(fetched) => {
+            let action = match fetched {
+                Fetched::Downloaded => "Downloaded",
+                Fetched::Cached => "Already have",
+            };
+            println!(
+                "{action} {year} day {day} input: {path}",
+                path = path.display()
+            );
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Could not fetch {year} day {day}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn new_day(year: u32, day: u32) -> ExitCode {
+    let Some(directory) = crate_directory(year) else {
+        eprintln!("There is no crate for {year}.");
+        return ExitCode::FAILURE;
+    };
+    match scaffold(&directory, year, day) {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {path}", path = path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Could not create {year} day {day}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn verify_answers(puzzles: Vec<Puzzle>, inputs: &Inputs) -> ExitCode {
+    if puzzles.is_empty() {
+        eprintln!("No puzzles match.");
+        return ExitCode::FAILURE;
+    }
+    let checks = match verify(&puzzles, inputs) {
+        Ok(checks) => checks,
+        Err(err) => {
+            eprintln!("Could not read answers: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let width = checks
+        .iter()
+        .filter_map(|check| check.input.as_ref().map(String::len))
+        .max()
+        .unwrap_or(0)
+        .max("Input".len());
+    println!("Year  Day  Part  {:width$}  Result", "Input");
+    for check in &checks {
+        print_check(check, width);
+    }
+
+    let passed = checks
+        .iter()
+        .filter(|c| matches!(c.outcome, Outcome::Pass))
+        .count();
+    let failed = checks.iter().filter(|c| c.outcome.is_failure()).count();
+    let missing = checks
+        .iter()
+        .filter(|c| matches!(c.outcome, Outcome::Missing))
+        .count();
+    println!();
+    println!("{passed} passed, {failed} failed, {missing} missing");
+
+    if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn print_check(check: &Check, width: usize) {
+    let row = format!(
+        "{year}  {day:>3}  {part:>4}  {input:width$}",
+        year = check.puzzle.year(),
+        day = check.puzzle.day(),
+        part = check.puzzle.part,
+        input = check.input.as_deref().unwrap_or("-"),
+    );
+    match &check.outcome {
+        Outcome::Pass => println!("{row}  pass"),
+        Outcome::Missing => println!("{row}  missing"),
+        Outcome::Error(err) => println!("{row}  ERROR: {err}"),
+        Outcome::Fail { expected, actual } => {
+            let actual = actual.to_string();
+            if expected.contains('\n') || actual.contains('\n') {
+                println!("{row}  FAIL");
+                println!("expected:\n{expected}\ngot:\n{actual}");
+            } else {
+                println!("{row}  FAIL: expected {expected}, got {actual}");
+            }
+        }
+    }
+}
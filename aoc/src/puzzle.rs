@@ -0,0 +1,106 @@
+use utils::{
+    error::AocResult,
+    simulation::Simulation,
+    solution::{Answer, Registry, Solution},
+};
+
+/**
+ * A single part of a day's puzzle.
+ */
+#[derive(Clone, Copy)]
+pub struct Puzzle<'a> {
+    pub solution: &'a dyn Solution,
+    pub part: u32,
+}
+
+impl Puzzle<'_> {
+    pub fn year(&self) -> u32 {
+        self.solution.year()
+    }
+
+    pub fn day(&self) -> u32 {
+        self.solution.day()
+    }
+
+    pub fn solve(&self, input: &str) -> AocResult<Answer> {
+        self.solution.solve(self.part, input)
+    }
+
+    pub fn simulate(&self, input: &str) -> AocResult<Box<dyn Simulation>> {
+        self.solution.simulate(self.part, input)
+    }
+}
+
+/**
+ * Builds a registry of every year's solutions.
+ */
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+    advent_of_code_2021::register(&mut registry);
+    advent_of_code_2022::register(&mut registry);
+    advent_of_code_2023::register(&mut registry);
+    registry
+}
+
+/**
+ * Gets every part of the registered solutions, ordered by year, day and part.
+ */
+pub fn puzzles(registry: &Registry) -> Vec<Puzzle<'_>> {
+    registry
+        .iter()
+        .flat_map(|solution| {
+            let parts = if solution.has_part_2() { 1..=2 } else { 1..=1 };
+            parts.map(move |part| Puzzle { solution, part })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::panic::{self, AssertUnwindSafe};
+
+    use utils::error::AocError;
+
+    use super::*;
+
+    #[test]
+    fn malformed_input_test() {
+        let registry = registry();
+        let puzzle = Puzzle {
+            solution: registry.get(2022, 22).unwrap(),
+            part: 1,
+        };
+        match puzzle.solve("..#\n..x\n\n10R5\n") {
+            Err(AocError::Parse { line, column, .. }) => {
+                assert_eq!(line, 2);
+                assert_eq!(column, Some(3));
+            }
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn empty_input_test() {
+        let registry = registry();
+        for puzzle in puzzles(&registry) {
+            let name = format!(
+                "{} day {} part {}",
+                puzzle.year(),
+                puzzle.day(),
+                puzzle.part
+            );
+            assert!(puzzle.solve("").is_err(), "{name} solved empty input");
+
+            // Each part copes with input it can't make sense of without panicking, even
+            // when called directly rather than through the empty input check.
+            for input in ["", "x\n", "1\n", "0\n0\n"] {
+                let result = panic::catch_unwind(AssertUnwindSafe(|| match puzzle.part {
+                    1 => puzzle.solution.part_1(input),
+                    _ => puzzle.solution.part_2(input),
+                }));
+                assert!(result.is_ok(), "{name} panicked on {input:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    sync::mpsc,
+    time::Instant,
+};
+
+use clap::ValueEnum;
+use itertools::Itertools;
+use rayon::{ThreadPoolBuildError, ThreadPoolBuilder};
+use serde::Serialize;
+use utils::solution::Answer;
+
+use crate::{input::Inputs, puzzle::Puzzle};
+
+/**
+ * How the runner writes its results.
+ */
+#[derive(ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Human readable answers, grouped by year.
+    #[default]
+    Text,
+    /// A JSON array with a record per part.
+    Json,
+    /// CSV with a header row and a record per part.
+    Csv,
+}
+
+/**
+ * Whether a puzzle produced an answer.
+ */
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Solved,
+    Failed,
+}
+
+/**
+ * The result of running one part of a day's puzzle.
+ */
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub status: Status,
+    pub answer: Option<String>,
+    /**
+     * One of `integer`, `big_integer`, `text` or `art`.
+     */
+    pub answer_type: Option<&'static str>,
+    /**
+     * Time taken to solve, not including reading the input.
+     */
+    pub duration_ns: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl Record {
+    /**
+     * Reads the puzzle's input and solves it, timing the solve.
+     */
+    pub fn solve(puzzle: &Puzzle, inputs: &Inputs) -> Self {
+        let mut record = Record {
+            year: puzzle.year(),
+            day: puzzle.day(),
+            part: puzzle.part,
+            status: Status::Failed,
+            answer: None,
+            answer_type: None,
+            duration_ns: None,
+            error: None,
+        };
+
+        let input = match inputs.read(puzzle.year(), puzzle.day()) {
+            Ok(input) => input,
+            Err(err) => {
+                record.error = Some(err.to_string());
+                return record;
+            }
+        };
+
+        let start = Instant::now();
+        let result = puzzle.solve(&input);
+        record.duration_ns = Some(start.elapsed().as_nanos().try_into().unwrap_or(u64::MAX));
+        match result {
+            Ok(answer) => {
+                record.status = Status::Solved;
+                record.answer_type = Some(answer_type(&answer));
+                record.answer = Some(answer.to_string());
+            }
+            Err(err) => {
+                let source = inputs.describe(puzzle.year(), puzzle.day());
+                record.error = Some(format!("{source}: {err}"));
+            }
+        }
+        record
+    }
+}
+
+/**
+ * Solves each puzzle using up to `jobs` threads, or one per CPU if `jobs` is 0. Records are passed
+ * to `on_record` on the calling thread, in the same order as the puzzles, as soon as they and
+ * every record before them are ready.
+ */
+pub fn solve_all(
+    puzzles: &[Puzzle],
+    inputs: &Inputs,
+    jobs: usize,
+    mut on_record: impl FnMut(Record),
+) -> Result<(), ThreadPoolBuildError> {
+    if jobs == 1 {
+        for puzzle in puzzles {
+            on_record(Record::solve(puzzle, inputs));
+        }
+        return Ok(());
+    }
+
+    let pool = ThreadPoolBuilder::new().num_threads(jobs).build()?;
+    pool.in_place_scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+        for (i, puzzle) in puzzles.iter().enumerate() {
+            let sender = sender.clone();
+            scope.spawn(move |_| {
+                // The receiver outlives the scope, so sending can't fail.
+                let _ = sender.send((i, Record::solve(puzzle, inputs)));
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+        for (i, record) in receiver {
+            pending.insert(i, record);
+            while let Some(record) = pending.remove(&next) {
+                on_record(record);
+                next += 1;
+            }
+        }
+    });
+    Ok(())
+}
+
+fn answer_type(answer: &Answer) -> &'static str {
+    match answer {
+        Answer::Integer(_) => "integer",
+        Answer::BigInteger(_) => "big_integer",
+        Answer::Text(_) => "text",
+        Answer::Art(_) => "art",
+    }
+}
+
+/**
+ * Writes the heading shown before a year's text results.
+ */
+pub fn write_year_heading(year: u32, mut writer: impl Write) -> io::Result<()> {
+    writeln!(
+        writer,
+        "Advent of Code {year}: https://adventofcode.com/{year}"
+    )?;
+    writeln!(writer)
+}
+
+/**
+ * Writes a record as text. Art is started on a new line so that it lines up.
+ */
+pub fn write_text_record(record: &Record, mut writer: impl Write) -> io::Result<()> {
+    let label = format!(
+        "Day {day:>2} part {part}",
+        day = record.day,
+        part = record.part
+    );
+    match (&record.answer, &record.error) {
+        (Some(answer), _) if record.answer_type == Some("art") => {
+            writeln!(writer, "{label} answer:\n{answer}")
+        }
+        (Some(answer), _) => writeln!(writer, "{label} answer: {answer}"),
+        (None, error) => writeln!(
+            writer,
+            "{label} failed: {error}",
+            error = error.as_deref().unwrap_or("unknown error")
+        ),
+    }
+}
+
+/**
+ * Writes the records in the given format.
+ */
+pub fn write_records(records: &[Record], format: Format, mut writer: impl Write) -> io::Result<()> {
+    match format {
+        Format::Text => {
+            for (year, year_records) in &records.iter().group_by(|r| r.year) {
+                write_year_heading(year, &mut writer)?;
+                for record in year_records {
+                    write_text_record(record, &mut writer)?;
+                }
+                writeln!(writer)?;
+            }
+            Ok(())
+        }
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut writer, records)?;
+            writeln!(writer)
+        }
+        Format::Csv => {
+            let mut csv = csv::Writer::from_writer(writer);
+            for record in records {
+                csv.serialize(record)?;
+            }
+            csv.flush()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::puzzle::{puzzles, registry};
+
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                year: 2022,
+                day: 5,
+                part: 1,
+                status: Status::Solved,
+                answer: Some("CMZ".to_string()),
+                answer_type: Some("text"),
+                duration_ns: Some(1200),
+                error: None,
+            },
+            Record {
+                year: 2022,
+                day: 10,
+                part: 2,
+                status: Status::Solved,
+                answer: Some("#.\n.#".to_string()),
+                answer_type: Some("art"),
+                duration_ns: Some(800),
+                error: None,
+            },
+            Record {
+                year: 2022,
+                day: 11,
+                part: 1,
+                status: Status::Failed,
+                answer: None,
+                answer_type: None,
+                duration_ns: None,
+                error: Some("could not read input".to_string()),
+            },
+        ]
+    }
+
+    fn write_string(format: Format) -> String {
+        let mut output = Vec::new();
+        write_records(&records(), format, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn solve_test() {
+        let registry = registry();
+        let puzzle = Puzzle {
+            solution: registry.get(2022, 1).unwrap(),
+            part: 1,
+        };
+        let record = Record::solve(&puzzle, &Inputs::Text("1\n2\n\n4\n\n".to_string()));
+        assert_eq!(record.status, Status::Solved);
+        assert_eq!(record.answer.as_deref(), Some("4"));
+        assert_eq!(record.answer_type, Some("integer"));
+        assert!(record.duration_ns.is_some());
+
+        let record = Record::solve(&puzzle, &Inputs::Text("x\n".to_string()));
+        assert_eq!(record.status, Status::Failed);
+        assert_eq!(
+            record.error.as_deref(),
+            Some("stdin: line 1: invalid digit found in string")
+        );
+    }
+
+    #[test]
+    fn solve_all_test() {
+        let registry = registry();
+        let puzzles = puzzles(&registry)
+            .into_iter()
+            .filter(|p| p.year() == 2022 && p.day() <= 8)
+            .collect_vec();
+        let inputs = Inputs::Directory(None);
+        let without_duration = |mut record: Record| {
+            record.duration_ns = None;
+            record
+        };
+
+        let mut sequential = Vec::new();
+        solve_all(&puzzles, &inputs, 1, |r| {
+            sequential.push(without_duration(r))
+        })
+        .unwrap();
+        let mut parallel = Vec::new();
+        solve_all(&puzzles, &inputs, 4, |r| parallel.push(without_duration(r))).unwrap();
+
+        assert_eq!(sequential.len(), 16);
+        assert_eq!(parallel, sequential);
+    }
+
+    #[test]
+    fn text_test() {
+        assert_eq!(
+            write_string(Format::Text),
+            "Advent of Code 2022: https://adventofcode.com/2022\n\n\
+             Day  5 part 1 answer: CMZ\n\
+             Day 10 part 2 answer:\n#.\n.#\n\
+             Day 11 part 1 failed: could not read input\n\n"
+        );
+    }
+
+    #[test]
+    fn json_test() {
+        let json: serde_json::Value = serde_json::from_str(&write_string(Format::Json)).unwrap();
+        assert_eq!(json[0]["answer"], "CMZ");
+        assert_eq!(json[1]["answer_type"], "art");
+        assert_eq!(json[2]["status"], "failed");
+        assert!(json[2]["answer"].is_null());
+    }
+
+    #[test]
+    fn csv_test() {
+        assert_eq!(
+            write_string(Format::Csv),
+            "year,day,part,status,answer,answer_type,duration_ns,error\n\
+             2022,5,1,solved,CMZ,text,1200,\n\
+             2022,10,2,solved,\"#.\n.#\",art,800,\n\
+             2022,11,1,failed,,,,could not read input\n"
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use crate::input::input_filename;
+
+/**
+ * Gets the source of a new day's module. The parts report that they aren't solved yet, and the
+ * tests are ignored until their expected answers are filled in.
+ */
+pub fn module_source(year: u32, day: u32) -> String {
+    format!(
+        r#"use utils::{{
+    error::{{AocError, AocResult, LineContext}},
+    solution::{{Answer, Solution}},
+}};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    fn year(&self) -> u32 {{
+        {year}
+    }}
+
+    fn day(&self) -> u32 {{
+        {day}
+    }}
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {{
+        solve(input).map(Answer::from)
+    }}
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {{
+        solve_part_2(input).map(Answer::from)
+    }}
+}}
+
+fn load_values(input: &str) -> AocResult<Vec<i64>> {{
+    let mut values = Vec::new();
+    for (i, line) in input.lines().enumerate() {{
+        if line.is_empty() {{
+            continue;
+        }}
+        values.push(line.trim().parse::<i64>().on_line(i)?);
+    }}
+    Ok(values)
+}}
+
+fn solve(input: &str) -> AocResult<i64> {{
+    let values = load_values(input)?;
+    Err(AocError::Unsupported(format!(
+        "part 1 is not solved yet, {{count}} values were loaded",
+        count = values.len()
+    )))
+}}
+
+fn solve_part_2(input: &str) -> AocResult<i64> {{
+    let values = load_values(input)?;
+    Err(AocError::Unsupported(format!(
+        "part 2 is not solved yet, {{count}} values were loaded",
+        count = values.len()
+    )))
+}}
+
+#[cfg(test)]
+mod tests {{
+
+    use super::*;
+
+    #[test]
+    #[ignore = "expected answer not filled in yet"]
+    fn small_test() {{
+        let result = solve(include_str!("../inputs/day-{day}-input-test.txt")).unwrap();
+        assert_eq!(result, 0);
+    }}
+
+    #[test]
+    #[ignore = "expected answer not filled in yet"]
+    fn test() {{
+        let result = solve(include_str!("../inputs/day-{day}-input.txt")).unwrap();
+        assert_eq!(result, 0);
+    }}
+
+    #[test]
+    #[ignore = "expected answer not filled in yet"]
+    fn part_2_small_test() {{
+        let result = solve_part_2(include_str!("../inputs/day-{day}-input-test.txt")).unwrap();
+        assert_eq!(result, 0);
+    }}
+
+    #[test]
+    #[ignore = "expected answer not filled in yet"]
+    fn part_2_test() {{
+        let result = solve_part_2(include_str!("../inputs/day-{day}-input.txt")).unwrap();
+        assert_eq!(result, 0);
+    }}
+}}
+"#
+    )
+}
+
+/**
+ * Adds a day to a year's `lib.rs`: declaring its module, registering its solution and updating
+ * the registered count checked by `register_test`. Modules are kept in the lexical order
+ * rustfmt sorts them into (`day_19` before `day_2`), and registrations in day order.
+ */
+pub fn add_to_lib(lib: &str, day: u32) -> Result<String, String> {
+    let lines = lib.lines().collect::<Vec<_>>();
+
+    let module_day = |line: &str| {
+        line.strip_prefix("pub mod day_")
+            .and_then(|rest| rest.strip_suffix(';'))
+            .and_then(|number| number.parse::<u32>().ok())
+    };
+    let register_day = |line: &str| {
+        let line = line.trim();
+        if !line.starts_with("registry.register(") {
+            return None;
+        }
+        let (_, rest) = line.split_once("day_")?;
+        let (number, _) = rest.split_once("::")?;
+        number.parse::<u32>().ok()
+    };
+
+    let modules = day_block(&lines, module_day).ok_or("no `pub mod day_N;` declarations")?;
+    let registers = day_block(&lines, register_day).ok_or("no `registry.register` calls")?;
+    if modules.iter().any(|&(_, d)| d == day) {
+        return Err(format!("day_{day} is already declared"));
+    }
+
+    let module_at = insert_position(&modules, |d| d.to_string() > day.to_string());
+    let register_at = insert_position(&registers, |d| d > day);
+    let mut output = Vec::new();
+    for i in 0..=lines.len() {
+        if i == module_at {
+            output.push(format!("pub mod day_{day};"));
+        }
+        if i == register_at {
+            output.push(format!("    registry.register(day_{day}::Day{day});"));
+        }
+        if let Some(line) = lines.get(i) {
+            output.push(update_count(line, modules.len()));
+        }
+    }
+    let mut output = output.join("\n");
+    if lib.ends_with('\n') {
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+/**
+ * Finds the contiguous lines matching a day, giving their line index and day.
+ */
+fn day_block(lines: &[&str], day_of: impl Fn(&str) -> Option<u32>) -> Option<Vec<(usize, u32)>> {
+    let start = lines.iter().position(|line| day_of(line).is_some())?;
+    Some(
+        lines[start..]
+            .iter()
+            .enumerate()
+            .map_while(|(offset, line)| day_of(line).map(|day| (start + offset, day)))
+            .collect(),
+    )
+}
+
+/**
+ * Gets the line index a new day goes at to keep the block in order: before the first day that
+ * sorts after it, or at the end if there is none.
+ */
+fn insert_position(block: &[(usize, u32)], sorts_after: impl Fn(u32) -> bool) -> usize {
+    block
+        .iter()
+        .find(|&&(_, d)| sorts_after(d))
+        .map_or(block[block.len() - 1].0 + 1, |&(i, _)| i)
+}
+
+fn update_count(line: &str, count: usize) -> String {
+    let old = format!("assert_eq!(registry.len(), {count});");
+    if line.trim() == old {
+        line.replace(&old, &format!("assert_eq!(registry.len(), {});", count + 1))
+    } else {
+        line.to_string()
+    }
+}
+
+/**
+ * Creates a new day in a year's crate, returning the files written. Inputs that already exist,
+ * such as ones that have been fetched, are kept.
+ */
+pub fn scaffold(crate_directory: &Path, year: u32, day: u32) -> io::Result<Vec<PathBuf>> {
+    let module = crate_directory.join("src").join(format!("day_{day}.rs"));
+    if module.exists() {
+        return Err(io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!("{path} already exists", path = module.display()),
+        ));
+    }
+    let lib_path = crate_directory.join("src").join("lib.rs");
+    let lib = add_to_lib(&fs::read_to_string(&lib_path)?, day).map_err(|err| {
+        io::Error::new(
+            ErrorKind::InvalidData,
+            format!("could not update {path}: {err}", path = lib_path.display()),
+        )
+    })?;
+
+    let inputs = crate_directory.join("inputs");
+    fs::create_dir_all(&inputs)?;
+    let input = inputs.join(input_filename(day));
+    let test_input = inputs.join(format!("day-{day}-input-test.txt"));
+
+    fs::write(&module, module_source(year, day))?;
+    fs::write(&lib_path, lib)?;
+    let mut written = vec![module, lib_path];
+    for path in [input, test_input] {
+        if !path.exists() {
+            fs::write(&path, "")?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const LIB: &str = "pub mod day_1;
+pub mod day_16;
+pub mod day_2;
+
+use utils::solution::{Parsed, Registry};
+
+pub fn register(registry: &mut Registry) {
+    registry.register(day_1::Day1);
+    registry.register(day_2::Day2);
+    registry.register(Parsed(day_16::Day16));
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn register_test() {
+        assert_eq!(registry.len(), 3);
+    }
+}
+";
+
+    #[test]
+    fn add_to_lib_test() {
+        let lib = add_to_lib(LIB, 3).unwrap();
+        assert!(
+            lib.starts_with("pub mod day_1;\npub mod day_16;\npub mod day_2;\npub mod day_3;\n")
+        );
+        assert!(lib.contains(
+            "    registry.register(day_2::Day2);\n    \
+             registry.register(day_3::Day3);\n    \
+             registry.register(Parsed(day_16::Day16));\n"
+        ));
+        assert!(lib.contains("assert_eq!(registry.len(), 4);"));
+        assert!(lib.ends_with("}\n"));
+
+        let lib = add_to_lib(LIB, 17).unwrap();
+        assert!(lib.contains("pub mod day_16;\npub mod day_17;\npub mod day_2;\n\n"));
+        assert!(lib.contains("Parsed(day_16::Day16));\n    registry.register(day_17::Day17);\n}"));
+
+        let lib = add_to_lib(LIB, 10).unwrap();
+        assert!(lib.starts_with("pub mod day_1;\npub mod day_10;\npub mod day_16;\n"));
+        assert!(lib.contains("day_2::Day2);\n    registry.register(day_10::Day10);\n"));
+
+        assert!(add_to_lib(LIB, 2).is_err());
+        assert!(add_to_lib("fn main() {}\n", 2).is_err());
+    }
+
+    #[test]
+    fn scaffold_test() {
+        let directory = tempfile::tempdir().unwrap();
+        let crate_directory = directory.path();
+        fs::create_dir_all(crate_directory.join("src")).unwrap();
+        fs::create_dir_all(crate_directory.join("inputs")).unwrap();
+        fs::write(crate_directory.join("src/lib.rs"), LIB).unwrap();
+        fs::write(crate_directory.join("inputs/day-3-input.txt"), "fetched").unwrap();
+
+        let written = scaffold(crate_directory, 2022, 3).unwrap();
+        assert_eq!(written.len(), 3);
+        assert!(fs::read_to_string(crate_directory.join("src/day_3.rs"))
+            .unwrap()
+            .contains("impl Solution for Day3"));
+        assert!(fs::read_to_string(crate_directory.join("src/lib.rs"))
+            .unwrap()
+            .contains("pub mod day_3;"));
+        assert_eq!(
+            fs::read_to_string(crate_directory.join("inputs/day-3-input.txt")).unwrap(),
+            "fetched"
+        );
+        assert!(crate_directory.join("inputs/day-3-input-test.txt").exists());
+
+        assert_eq!(
+            scaffold(crate_directory, 2022, 3).unwrap_err().kind(),
+            ErrorKind::AlreadyExists
+        );
+    }
+}
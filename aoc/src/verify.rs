@@ -0,0 +1,221 @@
+use std::{fs, io};
+
+use itertools::Itertools;
+use utils::solution::Answer;
+
+use crate::{
+    answers::{Manifest, ANSWERS_FILENAME},
+    input::Inputs,
+    puzzle::Puzzle,
+};
+
+/**
+ * Result of checking a puzzle against one expected answer.
+ */
+#[derive(Debug)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Answer,
+    },
+    /**
+     * The input could not be read or the solution returned an error.
+     */
+    Error(String),
+    /**
+     * No answer is expected for this part.
+     */
+    Missing,
+}
+
+impl Outcome {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Outcome::Fail { .. } | Outcome::Error(_))
+    }
+}
+
+/**
+ * A row of the verify table.
+ */
+pub struct Check<'a> {
+    pub puzzle: Puzzle<'a>,
+    /**
+     * Input file name the answer was checked with, if an answer was expected.
+     */
+    pub input: Option<String>,
+    pub outcome: Outcome,
+}
+
+/**
+ * Runs each puzzle against every answer listed for it in its year's manifest. Puzzles without an
+ * expected answer are reported as missing.
+ */
+pub fn verify<'a>(puzzles: &[Puzzle<'a>], inputs: &Inputs) -> io::Result<Vec<Check<'a>>> {
+    let mut checks = Vec::new();
+    for (year, year_puzzles) in &puzzles.iter().group_by(|p| p.year()) {
+        let directory = inputs.directory(year);
+        let manifest = match &directory {
+            Some(directory) => Manifest::load(&directory.join(ANSWERS_FILENAME))?,
+            None => Manifest::default(),
+        };
+
+        for &puzzle in year_puzzles {
+            let expected = manifest.expected(puzzle.day(), puzzle.part);
+            if expected.is_empty() {
+                checks.push(Check {
+                    puzzle,
+                    input: None,
+                    outcome: Outcome::Missing,
+                });
+            }
+            for expected in expected {
+                let filename = expected.input_filename();
+                let outcome = match &directory {
+                    Some(directory) => match fs::read_to_string(directory.join(&filename)) {
+                        Ok(input) => match puzzle.solve(&input) {
+                            Ok(answer) if expected.matches(&answer) => Outcome::Pass,
+                            Ok(answer) => Outcome::Fail {
+                                expected: expected.answer.trim().to_string(),
+                                actual: answer,
+                            },
+                            Err(err) => Outcome::Error(err.to_string()),
+                        },
+                        Err(err) => Outcome::Error(format!("could not read input: {err}")),
+                    },
+                    None => Outcome::Error(format!("no inputs for {year}")),
+                };
+                checks.push(Check {
+                    puzzle,
+                    input: Some(filename),
+                    outcome,
+                });
+            }
+        }
+    }
+    Ok(checks)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::path::Path;
+
+    use crate::{
+        filter::Filter,
+        puzzle::{puzzles, registry},
+    };
+
+    use super::*;
+
+    fn write_year(root: &Path, year: u32, files: &[(&str, &str)]) {
+        let directory = root.join(year.to_string());
+        fs::create_dir_all(&directory).unwrap();
+        for (name, contents) in files {
+            fs::write(directory.join(name), contents).unwrap();
+        }
+    }
+
+    #[test]
+    fn default_inputs_test() {
+        let registry = registry();
+        let filter = Filter {
+            year: Some(2022),
+            day: Some(1),
+            part: None,
+        };
+        let checks = verify(&filter.select(puzzles(&registry)), &Inputs::Directory(None)).unwrap();
+        assert_eq!(checks.len(), 2);
+        assert!(checks.iter().all(|c| matches!(c.outcome, Outcome::Pass)));
+
+        // The worked examples are checked alongside the real input.
+        let filter = Filter {
+            year: Some(2021),
+            day: Some(1),
+            part: None,
+        };
+        let checks = verify(&filter.select(puzzles(&registry)), &Inputs::Directory(None)).unwrap();
+        let inputs = checks.iter().map(|c| c.input.as_deref()).collect_vec();
+        assert_eq!(
+            inputs,
+            [
+                Some("day-1-input.txt"),
+                Some("day-1-input-test.txt"),
+                Some("day-1-input.txt"),
+                Some("day-1-input-test.txt")
+            ]
+        );
+        assert!(checks.iter().all(|c| matches!(c.outcome, Outcome::Pass)));
+    }
+
+    #[test]
+    fn outcomes_test() {
+        let root = tempfile::tempdir().unwrap();
+        write_year(
+            root.path(),
+            2022,
+            &[
+                ("day-1-input.txt", "1\n2\n\n4\n\n"),
+                ("day-1-input-test.txt", "1\n\n2\n\n"),
+                (
+                    ANSWERS_FILENAME,
+                    "[[answer]]\nday = 1\npart = 1\nanswer = \"4\"\n\n\
+                     [[answer]]\nday = 1\npart = 1\ninput = \"day-1-input-test.txt\"\nanswer = \"3\"\n\n\
+                     [[answer]]\nday = 2\npart = 1\nanswer = \"15\"\n",
+                ),
+            ],
+        );
+
+        let registry = registry();
+        let filter = Filter {
+            year: Some(2022),
+            day: None,
+            part: None,
+        };
+        let puzzles = filter
+            .select(puzzles(&registry))
+            .into_iter()
+            .filter(|p| p.day() <= 2)
+            .collect_vec();
+        let checks = verify(&puzzles, &Inputs::Directory(Some(root.path().into()))).unwrap();
+
+        let rows = checks
+            .iter()
+            .map(|c| {
+                (
+                    c.puzzle.day(),
+                    c.puzzle.part,
+                    c.input.as_deref(),
+                    &c.outcome,
+                )
+            })
+            .collect_vec();
+        assert_eq!(rows.len(), 5);
+        assert!(matches!(
+            rows[0],
+            (1, 1, Some("day-1-input.txt"), Outcome::Pass)
+        ));
+        assert!(matches!(
+            rows[1],
+            (1, 1, Some("day-1-input-test.txt"), Outcome::Fail { expected, actual: Answer::Integer(2) })
+                if expected == "3"
+        ));
+        assert!(matches!(rows[2], (1, 2, None, Outcome::Missing)));
+        assert!(matches!(
+            rows[3],
+            (2, 1, Some("day-2-input.txt"), Outcome::Error(_))
+        ));
+        assert!(matches!(rows[4], (2, 2, None, Outcome::Missing)));
+    }
+
+    #[test]
+    fn invalid_manifest_test() {
+        let root = tempfile::tempdir().unwrap();
+        write_year(root.path(), 2021, &[(ANSWERS_FILENAME, "answer = [")]);
+
+        let registry = registry();
+        let puzzles = puzzles(&registry);
+        let result = verify(&puzzles[..1], &Inputs::Directory(Some(root.path().into())));
+        assert!(matches!(result, Err(err) if err.kind() == io::ErrorKind::InvalidData));
+    }
+}
@@ -0,0 +1,365 @@
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    text::Line,
+    widgets::{Block, Paragraph},
+    DefaultTerminal, Frame,
+};
+use utils::simulation::Simulation;
+
+const MIN_DELAY: Duration = Duration::from_millis(10);
+const MAX_DELAY: Duration = Duration::from_secs(2);
+
+const HELP: [&str; 7] = [
+    "space  play / pause",
+    "→ l    step forward",
+    "← h    step back",
+    "g      jump to step",
+    "home   back to start",
+    "+ -    faster / slower",
+    "q      quit",
+];
+
+/**
+ * A state of the simulation, as drawn when it was reached.
+ */
+struct Snapshot {
+    lines: Vec<String>,
+    metrics: Vec<(&'static str, String)>,
+}
+
+impl Snapshot {
+    fn take(simulation: &dyn Simulation) -> Self {
+        Snapshot {
+            lines: simulation.render(),
+            metrics: simulation.metrics(),
+        }
+    }
+}
+
+/**
+ * Steps through a simulation. Every step reached is kept, so that stepping back only replays
+ * what was drawn rather than simulating again.
+ */
+pub struct Viewer {
+    title: String,
+    simulation: Box<dyn Simulation>,
+    history: Vec<Snapshot>,
+    position: usize,
+    finished: bool,
+    playing: bool,
+    delay: Duration,
+    /**
+     * The step number being typed in, while jumping to a step.
+     */
+    jump: Option<String>,
+}
+
+impl Viewer {
+    pub fn new(title: impl Into<String>, simulation: Box<dyn Simulation>) -> Self {
+        let history = vec![Snapshot::take(simulation.as_ref())];
+        Viewer {
+            title: title.into(),
+            simulation,
+            history,
+            position: 0,
+            finished: false,
+            playing: false,
+            delay: Duration::from_millis(200),
+            jump: None,
+        }
+    }
+
+    /**
+     * Gets the step being shown, where 0 is the state before any steps.
+     */
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /**
+     * Moves on a step, simulating it if it hasn't been reached before. Returns whether there
+     * was a step to move on to.
+     */
+    pub fn step_forward(&mut self) -> bool {
+        if self.position + 1 == self.history.len() {
+            if self.finished || !self.simulation.step() {
+                self.finished = true;
+                return false;
+            }
+            self.history.push(Snapshot::take(self.simulation.as_ref()));
+        }
+        self.position += 1;
+        true
+    }
+
+    pub fn step_back(&mut self) {
+        self.position = self.position.saturating_sub(1);
+    }
+
+    /**
+     * Moves to the given step, or to the last one if the simulation finishes first.
+     */
+    pub fn jump_to(&mut self, step: usize) {
+        if step < self.position {
+            self.position = step;
+        }
+        while self.position < step && self.step_forward() {}
+    }
+
+    /**
+     * Moves on a step while playing, pausing once there are no more.
+     */
+    pub fn tick(&mut self) {
+        if self.playing && !self.step_forward() {
+            self.playing = false;
+        }
+    }
+
+    /**
+     * Acts on a key press. Returns `false` once the viewer should close.
+     */
+    pub fn handle_key(&mut self, key: KeyCode) -> bool {
+        if let Some(jump) = &mut self.jump {
+            match key {
+                KeyCode::Char(c) if c.is_ascii_digit() => jump.push(c),
+                KeyCode::Backspace => {
+                    jump.pop();
+                }
+                KeyCode::Enter => {
+                    if let Ok(step) = jump.parse() {
+                        self.jump_to(step);
+                    }
+                    self.jump = None;
+                }
+                KeyCode::Esc => self.jump = None,
+                _ => {}
+            }
+            return true;
+        }
+
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char(' ') => self.playing = !self.playing,
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.playing = false;
+                self.step_forward();
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.playing = false;
+                self.step_back();
+            }
+            KeyCode::Home => self.jump_to(0),
+            KeyCode::Char('g') => {
+                self.playing = false;
+                self.jump = Some(String::new());
+            }
+            KeyCode::Char('+') => self.delay = (self.delay / 2).max(MIN_DELAY),
+            KeyCode::Char('-') => self.delay = (self.delay * 2).min(MAX_DELAY),
+            _ => {}
+        }
+        true
+    }
+
+    /**
+     * Draws the simulation, with its metrics and the controls alongside.
+     */
+    pub fn draw(&self, frame: &mut Frame) {
+        let snapshot = &self.history[self.position];
+        let [simulation_area, panel_area] =
+            Layout::horizontal([Constraint::Min(0), Constraint::Length(32)]).areas(frame.area());
+
+        let lines = snapshot
+            .lines
+            .iter()
+            .map(|line| Line::raw(line.as_str()))
+            .collect::<Vec<_>>();
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(self.title.as_str())),
+            simulation_area,
+        );
+
+        let status = if self.playing {
+            "playing"
+        } else if self.finished && self.position + 1 == self.history.len() {
+            "finished"
+        } else {
+            "paused"
+        };
+        let mut panel = vec![
+            Line::raw(format!("Step: {}", self.position)),
+            Line::raw(format!("Status: {status}")),
+            Line::raw(format!("Delay: {}ms", self.delay.as_millis())),
+            Line::raw(""),
+        ];
+        panel.extend(
+            snapshot
+                .metrics
+                .iter()
+                .map(|(name, value)| Line::raw(format!("{name}: {value}"))),
+        );
+        panel.push(Line::raw(""));
+        match &self.jump {
+            Some(jump) => panel.push(Line::raw(format!("Jump to step: {jump}_"))),
+            None => panel.extend(HELP.map(Line::raw)),
+        }
+        frame.render_widget(
+            Paragraph::new(panel).block(Block::bordered().title("Metrics")),
+            panel_area,
+        );
+    }
+}
+
+/**
+ * Shows the simulation in the terminal until the viewer is closed.
+ */
+pub fn view(title: impl Into<String>, simulation: Box<dyn Simulation>) -> io::Result<()> {
+    let mut terminal = ratatui::try_init()?;
+    let result = run(&mut terminal, Viewer::new(title, simulation));
+    ratatui::try_restore()?;
+    result
+}
+
+fn run(terminal: &mut DefaultTerminal, mut viewer: Viewer) -> io::Result<()> {
+    let mut last_tick = Instant::now();
+    loop {
+        terminal.draw(|frame| viewer.draw(frame))?;
+        if event::poll(viewer.delay.saturating_sub(last_tick.elapsed()))? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !viewer.handle_key(key.code) {
+                    return Ok(());
+                }
+            }
+        }
+        if last_tick.elapsed() >= viewer.delay {
+            viewer.tick();
+            last_tick = Instant::now();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::{cell::Cell, rc::Rc};
+
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+
+    /**
+     * Counts up to a limit, recording how many times it was stepped.
+     */
+    struct Counter {
+        count: usize,
+        limit: usize,
+        steps: Rc<Cell<usize>>,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            self.steps.set(self.steps.get() + 1);
+            if self.count == self.limit {
+                return false;
+            }
+            self.count += 1;
+            true
+        }
+
+        fn render(&self) -> Vec<String> {
+            vec!["#".repeat(self.count)]
+        }
+
+        fn metrics(&self) -> Vec<(&'static str, String)> {
+            vec![("Count", self.count.to_string())]
+        }
+    }
+
+    fn viewer(limit: usize) -> (Viewer, Rc<Cell<usize>>) {
+        let steps = Rc::new(Cell::new(0));
+        let counter = Counter {
+            count: 0,
+            limit,
+            steps: steps.clone(),
+        };
+        (Viewer::new("Counter", Box::new(counter)), steps)
+    }
+
+    #[test]
+    fn step_test() {
+        let (mut viewer, steps) = viewer(5);
+        assert!(viewer.step_forward());
+        assert!(viewer.step_forward());
+        viewer.step_back();
+        viewer.step_back();
+        viewer.step_back();
+        assert_eq!(viewer.position(), 0);
+        // Steps already reached are replayed rather than simulated again.
+        assert!(viewer.step_forward());
+        assert_eq!(steps.get(), 2);
+
+        viewer.jump_to(10);
+        assert_eq!(viewer.position(), 5);
+        assert!(!viewer.step_forward());
+        assert!(!viewer.step_forward());
+        assert_eq!(steps.get(), 6);
+
+        viewer.jump_to(3);
+        assert_eq!(viewer.position(), 3);
+    }
+
+    #[test]
+    fn key_test() {
+        let (mut viewer, _) = viewer(3);
+        assert!(viewer.handle_key(KeyCode::Char(' ')));
+        assert!(viewer.is_playing());
+        for _ in 0..5 {
+            viewer.tick();
+        }
+        assert_eq!(viewer.position(), 3);
+        assert!(!viewer.is_playing());
+
+        for key in [KeyCode::Char('g'), KeyCode::Char('1'), KeyCode::Enter] {
+            viewer.handle_key(key);
+        }
+        assert_eq!(viewer.position(), 1);
+        // Keys go to the step number while it is being typed.
+        for key in [KeyCode::Char('g'), KeyCode::Char('q'), KeyCode::Esc] {
+            assert!(viewer.handle_key(key));
+        }
+        viewer.handle_key(KeyCode::Right);
+        assert_eq!(viewer.position(), 2);
+        viewer.handle_key(KeyCode::Home);
+        assert_eq!(viewer.position(), 0);
+        assert!(!viewer.handle_key(KeyCode::Char('q')));
+    }
+
+    #[test]
+    fn draw_test() {
+        let (mut viewer, _) = viewer(3);
+        viewer.jump_to(2);
+        let mut terminal = Terminal::new(TestBackend::new(60, 20)).unwrap();
+        terminal.draw(|frame| viewer.draw(frame)).unwrap();
+
+        let text = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>();
+        assert!(text.contains("Counter"));
+        assert!(text.contains("│##  "));
+        assert!(text.contains("Step: 2"));
+        assert!(text.contains("Count: 2"));
+    }
+}
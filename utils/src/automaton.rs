@@ -0,0 +1,289 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{coordinate::Coordinate, grid::Grid};
+
+/**
+ * Which cells around a cell count as its neighbours.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// The four cells sharing an edge.
+    Orthogonal,
+    /// The eight cells sharing an edge or a corner.
+    Surrounding,
+    /// The cells at each offset from the cell.
+    Offsets(Vec<Coordinate>),
+}
+
+impl Neighbourhood {
+    pub fn of(&self, coordinate: Coordinate) -> Vec<Coordinate> {
+        match self {
+            Neighbourhood::Orthogonal => coordinate.orthogonal(),
+            Neighbourhood::Surrounding => coordinate.surround(),
+            Neighbourhood::Offsets(offsets) => {
+                offsets.iter().map(|&offset| coordinate + offset).collect()
+            }
+        }
+    }
+}
+
+/**
+ * A simulation that advances a generation at a time.
+ */
+pub trait Automaton: Sized {
+    /**
+     * Advances a generation, returning whether anything changed.
+     */
+    fn step(&mut self) -> bool;
+
+    /**
+     * Gets the number of generations that have been stepped.
+     */
+    fn generation(&self) -> usize;
+
+    /**
+     * Steps the given number of generations, passing the automaton to `snapshot` after each.
+     */
+    fn run(&mut self, steps: usize, mut snapshot: impl FnMut(&Self)) {
+        for _ in 0..steps {
+            self.step();
+            snapshot(self);
+        }
+    }
+
+    /**
+     * Steps until a generation changes nothing, passing the automaton to `snapshot` after each.
+     * Returns the number of that generation, or `None` if it is still changing after `limit`
+     * more steps.
+     */
+    fn run_until_stable(&mut self, limit: usize, mut snapshot: impl FnMut(&Self)) -> Option<usize> {
+        for _ in 0..limit {
+            let changed = self.step();
+            snapshot(self);
+            if !changed {
+                return Some(self.generation());
+            }
+        }
+        None
+    }
+}
+
+/**
+ * An automaton over every cell of a grid. Each generation, the rule is given a cell and its
+ * neighbours within the grid, and gives the cell's next value.
+ */
+pub struct Dense<T, R> {
+    grid: Grid<T>,
+    neighbourhood: Neighbourhood,
+    rule: R,
+    generation: usize,
+}
+
+impl<T, R> Dense<T, R>
+where
+    T: Clone + PartialEq,
+    R: FnMut(&T, &[&T]) -> T,
+{
+    pub fn new(grid: Grid<T>, neighbourhood: Neighbourhood, rule: R) -> Self {
+        Dense {
+            grid,
+            neighbourhood,
+            rule,
+            generation: 0,
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+}
+
+impl<T, R> Automaton for Dense<T, R>
+where
+    T: Clone + PartialEq,
+    R: FnMut(&T, &[&T]) -> T,
+{
+    fn step(&mut self) -> bool {
+        let mut next = self.grid.clone();
+        let mut changed = false;
+        for (coordinate, cell) in self.grid.iter() {
+            let neighbours = self
+                .neighbourhood
+                .of(coordinate)
+                .into_iter()
+                .filter_map(|c| self.grid.get(c))
+                .collect::<Vec<_>>();
+            let value = (self.rule)(cell, &neighbours);
+            if value != *cell {
+                changed = true;
+                next[coordinate] = value;
+            }
+        }
+        self.grid = next;
+        self.generation += 1;
+        changed
+    }
+
+    fn generation(&self) -> usize {
+        self.generation
+    }
+}
+
+/**
+ * An unbounded automaton of things that move around, such as creatures. Each generation, the
+ * rule is given a thing's position, every position taken and the generation number, and
+ * proposes where the thing goes. Things proposing the same position all stay where they are,
+ * as does anything proposing a position held by a thing that is staying put.
+ */
+pub struct Movers<R> {
+    cells: HashSet<Coordinate>,
+    rule: R,
+    generation: usize,
+}
+
+impl<R> Movers<R>
+where
+    R: FnMut(Coordinate, &HashSet<Coordinate>, usize) -> Coordinate,
+{
+    pub fn new(cells: HashSet<Coordinate>, rule: R) -> Self {
+        Movers {
+            cells,
+            rule,
+            generation: 0,
+        }
+    }
+
+    pub fn cells(&self) -> &HashSet<Coordinate> {
+        &self.cells
+    }
+}
+
+impl<R> Automaton for Movers<R>
+where
+    R: FnMut(Coordinate, &HashSet<Coordinate>, usize) -> Coordinate,
+{
+    fn step(&mut self) -> bool {
+        let mut proposals = HashMap::with_capacity(self.cells.len());
+        let mut claims: HashMap<Coordinate, usize> = HashMap::new();
+        for &cell in &self.cells {
+            let proposal = (self.rule)(cell, &self.cells, self.generation);
+            proposals.insert(cell, proposal);
+            *claims.entry(proposal).or_default() += 1;
+        }
+
+        // A thing that stays put can block a move into its position, which can make that
+        // thing stay put in turn, so this repeats until nothing else is blocked.
+        let mut staying = proposals
+            .iter()
+            .filter(|&(cell, proposal)| cell == proposal || claims[proposal] > 1)
+            .map(|(&cell, _)| cell)
+            .collect::<HashSet<_>>();
+        loop {
+            let blocked = proposals
+                .iter()
+                .filter(|&(cell, proposal)| !staying.contains(cell) && staying.contains(proposal))
+                .map(|(&cell, _)| cell)
+                .collect::<Vec<_>>();
+            if blocked.is_empty() {
+                break;
+            }
+            staying.extend(blocked);
+        }
+
+        let mut next = HashSet::with_capacity(self.cells.len());
+        let mut changed = false;
+        for (cell, proposal) in proposals {
+            if staying.contains(&cell) {
+                next.insert(cell);
+            } else {
+                changed = true;
+                next.insert(proposal);
+            }
+        }
+        debug_assert_eq!(next.len(), self.cells.len());
+        self.cells = next;
+        self.generation += 1;
+        changed
+    }
+
+    fn generation(&self) -> usize {
+        self.generation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /**
+     * Conway's Game of Life.
+     */
+    fn life(alive: bool, live_neighbours: usize) -> bool {
+        live_neighbours == 3 || (alive && live_neighbours == 2)
+    }
+
+    fn cells(coordinates: &[(i32, i32)]) -> HashSet<Coordinate> {
+        coordinates.iter().map(|&c| Coordinate::from(c)).collect()
+    }
+
+    #[test]
+    fn dense_test() {
+        let blinker = Grid::parse(".....\n..#..\n..#..\n..#..\n.....\n", |c| Ok(c == '#')).unwrap();
+        let mut automaton = Dense::new(
+            blinker.clone(),
+            Neighbourhood::Surrounding,
+            |&alive: &bool, neighbours: &[&bool]| {
+                life(alive, neighbours.iter().filter(|&&&n| n).count())
+            },
+        );
+
+        let mut snapshots = Vec::new();
+        automaton.run(2, |a| snapshots.push(a.grid().clone()));
+        assert_eq!(automaton.generation(), 2);
+        assert_eq!(snapshots[1], blinker);
+        assert!(snapshots[0][Coordinate::new(1, 2)]);
+        assert!(!snapshots[0][Coordinate::new(2, 1)]);
+        assert_eq!(automaton.run_until_stable(10, |_| {}), None);
+        assert_eq!(automaton.into_grid(), blinker);
+    }
+
+    #[test]
+    fn movers_test() {
+        // Everything heads towards x = 0. The two at (±1, 0) clash, so they stay put.
+        let start = cells(&[(-1, 0), (1, 0), (3, 5)]);
+        let mut automaton = Movers::new(start, |c: Coordinate, _: &HashSet<Coordinate>, _| {
+            Coordinate::new(c.x - c.x.signum(), c.y)
+        });
+
+        let mut sizes = Vec::new();
+        let stable = automaton.run_until_stable(10, |a| sizes.push(a.cells().len()));
+        assert_eq!(stable, Some(4));
+        assert_eq!(automaton.cells(), &cells(&[(-1, 0), (1, 0), (0, 5)]));
+        assert!(sizes.iter().all(|&size| size == 3));
+    }
+
+    #[test]
+    fn blocked_mover_test() {
+        // The two at (0, 0) and (2, 0) clash over (1, 0), so they stay put. The one at (0, 1)
+        // is the only one after (0, 0), but that is still taken, so it has to stay put too.
+        let moves = HashMap::from([
+            (Coordinate::new(0, 0), Coordinate::new(1, 0)),
+            (Coordinate::new(2, 0), Coordinate::new(1, 0)),
+            (Coordinate::new(0, 1), Coordinate::new(0, 0)),
+            (Coordinate::new(2, 1), Coordinate::new(2, 2)),
+        ]);
+        let start = moves.keys().copied().collect::<HashSet<_>>();
+        let mut automaton = Movers::new(start, |c: Coordinate, _: &HashSet<Coordinate>, _| {
+            moves.get(&c).copied().unwrap_or(c)
+        });
+
+        assert!(automaton.step());
+        assert_eq!(automaton.cells(), &cells(&[(0, 0), (2, 0), (0, 1), (2, 2)]));
+        assert!(!automaton.step());
+    }
+}
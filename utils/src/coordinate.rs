@@ -1,44 +1,103 @@
 use std::{
     collections::HashMap,
-    fmt::Display,
-    ops::{Add, Sub},
+    fmt::{self, Display},
+    hash::Hash,
+    ops::{Add, Mul, Neg, Sub},
+    str::FromStr,
 };
 
+/**
+ * A signed integer type that coordinates can be made of.
+ */
+pub trait Integer:
+    Copy
+    + Eq
+    + Ord
+    + Hash
+    + fmt::Debug
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize);
+
 /**
  * Represents cartesian coordinates. By convention, the origin (0, 0) is the top-left corner.
+ *
+ * The axes are `i32` unless another integer type is given, such as `Coordinate<i64>` for
+ * puzzles whose values don't fit.
  */
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub struct Coordinate {
-    pub x: i32,
-    pub y: i32,
+pub struct Coordinate<T = i32> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Coordinate {
-    pub fn new(x: i32, y: i32) -> Self {
+impl<T: Integer> Coordinate<T> {
+    pub fn new(x: T, y: T) -> Self {
         Coordinate { x, y }
     }
 
     pub fn origin() -> Self {
-        Coordinate { x: 0, y: 0 }
+        Coordinate {
+            x: T::ZERO,
+            y: T::ZERO,
+        }
     }
 
     pub fn orthogonal(&self) -> Vec<Self> {
+        let one = T::ONE;
         vec![
             Coordinate {
-                x: self.x - 1,
+                x: self.x - one,
                 y: self.y,
             },
             Coordinate {
-                x: self.x + 1,
+                x: self.x + one,
                 y: self.y,
             },
             Coordinate {
                 x: self.x,
-                y: self.y - 1,
+                y: self.y - one,
             },
             Coordinate {
                 x: self.x,
-                y: self.y + 1,
+                y: self.y + one,
             },
         ]
     }
@@ -47,44 +106,77 @@ impl Coordinate {
      * Gets all surrounding coordinates, including diagonals.
      */
     pub fn surround(&self) -> Vec<Self> {
-        vec![
-            Coordinate {
-                x: self.x - 1,
-                y: self.y,
-            },
+        let one = T::ONE;
+        let mut coordinates = self.orthogonal();
+        coordinates.extend([
             Coordinate {
-                x: self.x + 1,
-                y: self.y,
+                x: self.x - one,
+                y: self.y - one,
             },
             Coordinate {
-                x: self.x,
-                y: self.y - 1,
+                x: self.x + one,
+                y: self.y - one,
             },
             Coordinate {
-                x: self.x,
-                y: self.y + 1,
+                x: self.x - one,
+                y: self.y + one,
             },
             Coordinate {
-                x: self.x - 1,
-                y: self.y - 1,
+                x: self.x + one,
+                y: self.y + one,
             },
-            Coordinate {
-                x: self.x + 1,
-                y: self.y - 1,
-            },
-            Coordinate {
-                x: self.x - 1,
-                y: self.y + 1,
-            },
-            Coordinate {
-                x: self.x + 1,
-                y: self.y + 1,
-            },
-        ]
+        ]);
+        coordinates
+    }
+
+    /**
+     * Gets the coordinate `n` steps away in the given direction, which can be a [`Direction4`]
+     * or a [`Direction8`].
+     */
+    pub fn step(&self, direction: impl Into<Direction8>, n: T) -> Self {
+        let offset = direction.into().offset::<T>();
+        Coordinate::new(self.x + offset.x * n, self.y + offset.y * n)
+    }
+
+    /**
+     * Gets the number of orthogonal steps between the coordinates.
+     */
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /**
+     * Adds the coordinates, or returns `None` if either axis overflows.
+     */
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(Coordinate::new(
+            self.x.checked_add(rhs.x)?,
+            self.y.checked_add(rhs.y)?,
+        ))
+    }
+
+    /**
+     * Subtracts the coordinates, or returns `None` if either axis overflows.
+     */
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        Some(Coordinate::new(
+            self.x.checked_sub(rhs.x)?,
+            self.y.checked_sub(rhs.y)?,
+        ))
+    }
+
+    /**
+     * Converts the axes to another integer type, or returns `None` if either doesn't fit.
+     */
+    pub fn convert<U: Integer + TryFrom<T>>(&self) -> Option<Coordinate<U>> {
+        Some(Coordinate::new(
+            U::try_from(self.x).ok()?,
+            U::try_from(self.y).ok()?,
+        ))
     }
 }
 
-impl Add for Coordinate {
+impl<T: Integer> Add for Coordinate<T> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
@@ -92,7 +184,7 @@ impl Add for Coordinate {
     }
 }
 
-impl Sub for Coordinate {
+impl<T: Integer> Sub for Coordinate<T> {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
@@ -100,8 +192,8 @@ impl Sub for Coordinate {
     }
 }
 
-impl From<(i32, i32)> for Coordinate {
-    fn from(item: (i32, i32)) -> Self {
+impl<T: Integer> From<(T, T)> for Coordinate<T> {
+    fn from(item: (T, T)) -> Self {
         Coordinate {
             x: item.0,
             y: item.1,
@@ -109,10 +201,512 @@ impl From<(i32, i32)> for Coordinate {
     }
 }
 
-impl Display for Coordinate {
+impl From<Coordinate<i32>> for Coordinate<i64> {
+    fn from(item: Coordinate<i32>) -> Self {
+        Coordinate::new(item.x.into(), item.y.into())
+    }
+}
+
+impl<T: Integer> Display for Coordinate<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         format!("({x}, {y})", x = self.x, y = self.y).fmt(f)
     }
 }
 
 pub type CoordinateMap<T> = HashMap<Coordinate, T>;
+
+/**
+ * One of the four orthogonal directions. Following the convention that the origin is the
+ * top-left corner, north is towards smaller y values and east towards larger x values.
+ */
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction4 {
+    /**
+     * Every direction, clockwise from north.
+     */
+    pub const ALL: [Direction4; 4] = [
+        Direction4::North,
+        Direction4::East,
+        Direction4::South,
+        Direction4::West,
+    ];
+
+    /**
+     * Turns a quarter turn anticlockwise.
+     */
+    pub fn turn_left(self) -> Self {
+        Direction4::ALL[(self as usize + 3) % 4]
+    }
+
+    /**
+     * Turns a quarter turn clockwise.
+     */
+    pub fn turn_right(self) -> Self {
+        Direction4::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Direction4::ALL[(self as usize + 2) % 4]
+    }
+
+    /**
+     * Gets the offset of a single step in this direction.
+     */
+    pub fn offset<T: Integer>(self) -> Coordinate<T> {
+        Direction8::from(self).offset()
+    }
+}
+
+/**
+ * Reads `U`/`R`/`D`/`L`, `N`/`E`/`S`/`W` or `^`/`>`/`v`/`<`.
+ */
+impl TryFrom<char> for Direction4 {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'U' | 'N' | '^' => Ok(Direction4::North),
+            'R' | 'E' | '>' => Ok(Direction4::East),
+            'D' | 'S' | 'v' => Ok(Direction4::South),
+            'L' | 'W' | '<' => Ok(Direction4::West),
+            _ => Err(format!("unknown direction '{c}'")),
+        }
+    }
+}
+
+impl FromStr for Direction4 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction4::try_from(c),
+            _ => Err(format!("unknown direction '{s}'")),
+        }
+    }
+}
+
+/**
+ * One of the four orthogonal or four diagonal directions, with the same orientation as
+ * [`Direction4`].
+ */
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /**
+     * Every direction, clockwise from north.
+     */
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /**
+     * Turns an eighth of a turn anticlockwise.
+     */
+    pub fn turn_left(self) -> Self {
+        Direction8::ALL[(self as usize + 7) % 8]
+    }
+
+    /**
+     * Turns an eighth of a turn clockwise.
+     */
+    pub fn turn_right(self) -> Self {
+        Direction8::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Direction8::ALL[(self as usize + 4) % 8]
+    }
+
+    /**
+     * Gets the offset of a single step in this direction.
+     */
+    pub fn offset<T: Integer>(self) -> Coordinate<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        let (x, y) = match self {
+            Direction8::North => (zero, -one),
+            Direction8::NorthEast => (one, -one),
+            Direction8::East => (one, zero),
+            Direction8::SouthEast => (one, one),
+            Direction8::South => (zero, one),
+            Direction8::SouthWest => (-one, one),
+            Direction8::West => (-one, zero),
+            Direction8::NorthWest => (-one, -one),
+        };
+        Coordinate::new(x, y)
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        Direction8::ALL[direction as usize * 2]
+    }
+}
+
+/**
+ * Reads any of the forms [`Direction4`] can be read from, or a compass point such as `NE`.
+ */
+impl FromStr for Direction8 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NE" => Ok(Direction8::NorthEast),
+            "SE" => Ok(Direction8::SouthEast),
+            "SW" => Ok(Direction8::SouthWest),
+            "NW" => Ok(Direction8::NorthWest),
+            _ => s.parse::<Direction4>().map(Direction8::from),
+        }
+    }
+}
+
+/**
+ * Represents cartesian coordinates in three dimensions.
+ */
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Coordinate3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Coordinate3 {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Coordinate3 { x, y, z }
+    }
+
+    pub fn origin() -> Self {
+        Coordinate3 { x: 0, y: 0, z: 0 }
+    }
+
+    /**
+     * Gets the 6 coordinates sharing a face with this one.
+     */
+    pub fn orthogonal(&self) -> Vec<Self> {
+        vec![
+            Coordinate3::new(self.x - 1, self.y, self.z),
+            Coordinate3::new(self.x + 1, self.y, self.z),
+            Coordinate3::new(self.x, self.y - 1, self.z),
+            Coordinate3::new(self.x, self.y + 1, self.z),
+            Coordinate3::new(self.x, self.y, self.z - 1),
+            Coordinate3::new(self.x, self.y, self.z + 1),
+        ]
+    }
+
+    /**
+     * Gets all 26 surrounding coordinates, including those sharing only an edge or a corner.
+     */
+    pub fn surround(&self) -> Vec<Self> {
+        CoordN::from(*self)
+            .surround()
+            .into_iter()
+            .map(Coordinate3::from)
+            .collect()
+    }
+
+    /**
+     * Gets the number of orthogonal steps between the coordinates.
+     */
+    pub fn manhattan_distance(&self, other: &Self) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+}
+
+impl Add for Coordinate3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Coordinate3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Coordinate3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Coordinate3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl From<(i32, i32, i32)> for Coordinate3 {
+    fn from(item: (i32, i32, i32)) -> Self {
+        Coordinate3::new(item.0, item.1, item.2)
+    }
+}
+
+impl From<CoordN<3>> for Coordinate3 {
+    fn from(item: CoordN<3>) -> Self {
+        let [x, y, z] = item.0;
+        Coordinate3::new(x, y, z)
+    }
+}
+
+impl Display for Coordinate3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        format!("({x}, {y}, {z})", x = self.x, y = self.y, z = self.z).fmt(f)
+    }
+}
+
+pub type Coordinate3Map<T> = HashMap<Coordinate3, T>;
+
+/**
+ * Represents cartesian coordinates in any number of dimensions, for puzzles going beyond three.
+ */
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CoordN<const N: usize>(pub [i32; N]);
+
+impl<const N: usize> CoordN<N> {
+    pub fn new(axes: [i32; N]) -> Self {
+        CoordN(axes)
+    }
+
+    pub fn origin() -> Self {
+        CoordN([0; N])
+    }
+
+    /**
+     * Gets the 2N coordinates one step away along a single axis.
+     */
+    pub fn orthogonal(&self) -> Vec<Self> {
+        let mut coordinates = Vec::with_capacity(2 * N);
+        for axis in 0..N {
+            for step in [-1, 1] {
+                let mut coordinate = *self;
+                coordinate.0[axis] += step;
+                coordinates.push(coordinate);
+            }
+        }
+        coordinates
+    }
+
+    /**
+     * Gets all 3^N - 1 surrounding coordinates, including diagonals.
+     */
+    pub fn surround(&self) -> Vec<Self> {
+        let mut coordinates = vec![*self];
+        for axis in 0..N {
+            coordinates = coordinates
+                .into_iter()
+                .flat_map(|c| {
+                    [-1, 0, 1].map(|step| {
+                        let mut coordinate = c;
+                        coordinate.0[axis] += step;
+                        coordinate
+                    })
+                })
+                .collect();
+        }
+        coordinates.retain(|c| c != self);
+        coordinates
+    }
+
+    /**
+     * Gets the number of orthogonal steps between the coordinates.
+     */
+    pub fn manhattan_distance(&self, other: &Self) -> i32 {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .map(|(a, b)| (a - b).abs())
+            .sum()
+    }
+}
+
+impl<const N: usize> Add for CoordN<N> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a += b;
+        }
+        self
+    }
+}
+
+impl<const N: usize> Sub for CoordN<N> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a -= b;
+        }
+        self
+    }
+}
+
+impl<const N: usize> From<[i32; N]> for CoordN<N> {
+    fn from(item: [i32; N]) -> Self {
+        CoordN(item)
+    }
+}
+
+impl From<Coordinate> for CoordN<2> {
+    fn from(item: Coordinate) -> Self {
+        CoordN([item.x, item.y])
+    }
+}
+
+impl From<Coordinate3> for CoordN<3> {
+    fn from(item: Coordinate3) -> Self {
+        CoordN([item.x, item.y, item.z])
+    }
+}
+
+impl<const N: usize> Display for CoordN<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let axes = self.0.iter().map(i32::to_string).collect::<Vec<_>>();
+        format!("({axes})", axes = axes.join(", ")).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn coordinate_test() {
+        let a = Coordinate::new(1, -2);
+        let b = Coordinate::from((4, 2));
+        assert_eq!(a + b, Coordinate::new(5, 0));
+        assert_eq!(b - a, Coordinate::new(3, 4));
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(a.to_string(), "(1, -2)");
+    }
+
+    #[test]
+    fn wide_coordinate_test() {
+        let a = Coordinate::<i64>::new(4_000_000_000, -1);
+        assert_eq!(a.manhattan_distance(&Coordinate::origin()), 4_000_000_001);
+        assert_eq!(a.orthogonal()[1], Coordinate::new(4_000_000_001, -1));
+        assert_eq!(a.convert::<i32>(), None);
+        assert_eq!(
+            Coordinate::<i64>::from(Coordinate::new(1, 2)),
+            Coordinate::new(1_i64, 2)
+        );
+        assert_eq!(
+            Coordinate::new(1_i64, 2).convert(),
+            Some(Coordinate::new(1_i32, 2))
+        );
+
+        let max = Coordinate::new(i32::MAX, 0);
+        assert_eq!(max.checked_add(&Coordinate::new(1, 0)), None);
+        assert_eq!(
+            max.checked_sub(&Coordinate::new(1, 1)),
+            Some(Coordinate::new(i32::MAX - 1, -1))
+        );
+    }
+
+    #[test]
+    fn direction_test() {
+        let start = Coordinate::new(2, 2);
+        assert_eq!(start.step(Direction4::North, 2), Coordinate::new(2, 0));
+        assert_eq!(start.step(Direction4::West, 1), Coordinate::new(1, 2));
+        assert_eq!(start.step(Direction8::SouthEast, 3), Coordinate::new(5, 5));
+
+        assert_eq!(Direction4::North.turn_left(), Direction4::West);
+        assert_eq!(Direction4::West.turn_right(), Direction4::North);
+        assert_eq!(Direction4::East.opposite(), Direction4::West);
+        assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::NorthWest.turn_right(), Direction8::North);
+        assert_eq!(Direction8::SouthWest.opposite(), Direction8::NorthEast);
+
+        let sorted = |mut coordinates: Vec<Coordinate>| {
+            coordinates.sort_by_key(|c| (c.y, c.x));
+            coordinates
+        };
+        assert_eq!(
+            sorted(Direction4::ALL.map(|d| start.step(d, 1)).to_vec()),
+            sorted(start.orthogonal())
+        );
+        assert_eq!(
+            sorted(Direction8::ALL.map(|d| start.step(d, 1)).to_vec()),
+            sorted(start.surround())
+        );
+    }
+
+    #[test]
+    fn parse_direction_test() {
+        for (text, direction) in [
+            ("U", Direction4::North),
+            ("R", Direction4::East),
+            ("v", Direction4::South),
+            ("W", Direction4::West),
+        ] {
+            assert_eq!(text.parse::<Direction4>(), Ok(direction));
+        }
+        assert_eq!(Direction4::try_from('<'), Ok(Direction4::West));
+        assert!("X".parse::<Direction4>().is_err());
+        assert!("UR".parse::<Direction4>().is_err());
+
+        assert_eq!("NE".parse::<Direction8>(), Ok(Direction8::NorthEast));
+        assert_eq!("^".parse::<Direction8>(), Ok(Direction8::North));
+        assert!("NNE".parse::<Direction8>().is_err());
+    }
+
+    #[test]
+    fn coordinate_3_test() {
+        let a = Coordinate3::new(1, 2, 3);
+        let b = Coordinate3::from((-1, 0, 5));
+        assert_eq!(a + b, Coordinate3::new(0, 2, 8));
+        assert_eq!(a - b, Coordinate3::new(2, 2, -2));
+        assert_eq!(a.manhattan_distance(&b), 6);
+        assert_eq!(a.to_string(), "(1, 2, 3)");
+
+        let origin = Coordinate3::origin();
+        assert_eq!(origin.orthogonal().len(), 6);
+        assert!(origin
+            .orthogonal()
+            .iter()
+            .all(|c| c.manhattan_distance(&origin) == 1));
+        let surround = origin.surround();
+        assert_eq!(surround.len(), 26);
+        assert!(!surround.contains(&origin));
+        assert!(surround.contains(&Coordinate3::new(-1, 1, -1)));
+    }
+
+    #[test]
+    fn coord_n_test() {
+        let a = CoordN::new([1, 2, 3, 4]);
+        let b = CoordN::from([1, 1, 1, 1]);
+        assert_eq!(a + b, CoordN([2, 3, 4, 5]));
+        assert_eq!(a - b, CoordN([0, 1, 2, 3]));
+        assert_eq!(a.manhattan_distance(&b), 6);
+        assert_eq!(a.to_string(), "(1, 2, 3, 4)");
+
+        assert_eq!(a.orthogonal().len(), 8);
+        assert_eq!(a.surround().len(), 80);
+        assert_eq!(
+            CoordN::<1>::origin().surround(),
+            vec![CoordN([-1]), CoordN([1])]
+        );
+        assert_eq!(
+            CoordN::from(Coordinate::new(1, 1)).surround().len(),
+            Coordinate::new(1, 1).surround().len()
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use std::{collections::HashMap, hash::Hash};
+
+/**
+ * Where a sequence of states starts repeating: the state after `start + length` steps is the
+ * same as the one after `start` steps.
+ */
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /**
+     * Gets the earliest step whose state is the same as the state after `n` steps.
+     */
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/**
+ * Steps the state until its key is one that has been seen before, leaving the state at the
+ * first repeat. States are compared by key, so the key must capture everything that decides
+ * the states that follow.
+ *
+ * Never returns if the keys don't repeat.
+ */
+pub fn find_cycle<S, K>(state: &mut S, step: impl FnMut(&mut S), key: impl FnMut(&S) -> K) -> Cycle
+where
+    K: Hash + Eq,
+{
+    detect(state, usize::MAX, step, key, |_| {}).expect("a key repeats within usize::MAX steps")
+}
+
+/**
+ * Gets the metric of the state after `n` steps. Steps are only simulated until the states
+ * repeat, with the rest extrapolated on the basis that each time round the cycle adds the same
+ * amount to the metric, as with a height that keeps growing.
+ */
+pub fn extrapolate<S, K>(
+    mut state: S,
+    n: usize,
+    step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> K,
+    mut metric: impl FnMut(&S) -> i64,
+) -> i64
+where
+    K: Hash + Eq,
+{
+    let mut metrics = Vec::new();
+    let Some(cycle) = detect(&mut state, n, step, key, |s| metrics.push(metric(s))) else {
+        return metrics[n];
+    };
+    let per_cycle = metrics[cycle.start + cycle.length] - metrics[cycle.start];
+    let cycles = ((n - cycle.start) / cycle.length) as i64;
+    metrics[cycle.equivalent_step(n)] + per_cycle * cycles
+}
+
+/**
+ * Steps the state until a key repeats, or until `limit` steps have been taken. Every state is
+ * passed to `on_state` as it is reached, starting with the initial one.
+ */
+fn detect<S, K>(
+    state: &mut S,
+    limit: usize,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+    mut on_state: impl FnMut(&S),
+) -> Option<Cycle>
+where
+    K: Hash + Eq,
+{
+    let mut seen = HashMap::new();
+    for i in 0..=limit {
+        on_state(state);
+        if let Some(start) = seen.insert(key(state), i) {
+            return Some(Cycle {
+                start,
+                length: i - start,
+            });
+        }
+        if i < limit {
+            step(state);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /**
+     * Counts up from 0 to 9, then goes round 4 to 9 forever. The total of the values passed
+     * is kept alongside.
+     */
+    fn step(state: &mut (u32, i64)) {
+        state.0 = if state.0 == 9 { 4 } else { state.0 + 1 };
+        state.1 += state.0 as i64;
+    }
+
+    #[test]
+    fn find_cycle_test() {
+        let mut state = (0, 0);
+        let cycle = find_cycle(&mut state, step, |s| s.0);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 4,
+                length: 6
+            }
+        );
+        assert_eq!(state.0, 4);
+        assert_eq!(cycle.equivalent_step(3), 3);
+        assert_eq!(cycle.equivalent_step(10), 4);
+        assert_eq!(cycle.equivalent_step(1_000_001), 5);
+    }
+
+    #[test]
+    fn extrapolate_test() {
+        let simulate = |n: usize| {
+            let mut state = (0, 0);
+            for _ in 0..n {
+                step(&mut state);
+            }
+            state.1
+        };
+        for n in [0, 3, 4, 9, 10, 11, 25, 100] {
+            assert_eq!(extrapolate((0, 0), n, step, |s| s.0, |s| s.1), simulate(n));
+        }
+        // 4 + 5 + ... + 9 is added every 6 steps.
+        assert_eq!(
+            extrapolate((0, 0), 6_000_004, step, |s| s.0, |s| s.1),
+            simulate(4) + 39 * 1_000_000
+        );
+    }
+}
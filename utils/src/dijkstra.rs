@@ -1,72 +1,119 @@
-use std::collections::{BinaryHeap, HashSet};
+use crate::{
+    coordinate::{Coordinate, CoordinateMap},
+    search,
+};
 
-use crate::coordinate::{Coordinate, CoordinateMap};
-
-#[derive(Debug, PartialEq, Eq)]
-struct DijkstraNode {
-    pub coordinate: Coordinate,
-    /**
-     * Gets the cost of travelling to this node from the start node.
-     */
-    pub total_cost: i32,
-}
-
-impl Ord for DijkstraNode {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.total_cost.cmp(&self.total_cost)
+/**
+ * Gets the orthogonal neighbours that are on the map, with the cost of entering each one.
+ */
+fn entry_costs<'a>(
+    map: &'a CoordinateMap<i32>,
+) -> impl FnMut(&Coordinate) -> Vec<(Coordinate, i32)> + 'a {
+    |coordinate| {
+        coordinate
+            .orthogonal()
+            .into_iter()
+            .filter_map(|c| map.get(&c).map(|&cost| (c, cost)))
+            .collect()
     }
 }
 
-impl PartialOrd for DijkstraNode {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
+/**
+ * Uses Dijkstra's algorithm to find the shortest path from the start node to the end node.
+ * Entering a coordinate costs its value in the map.
+ *
+ * Returns the cost of traversing the path and the coordinates along it, including the start
+ * and the end, or `None` if no path is found.
+ */
+pub fn calc_route(
+    map: &CoordinateMap<i32>,
+    start: &Coordinate,
+    end: &Coordinate,
+) -> Option<(i32, Vec<Coordinate>)> {
+    search::dijkstra(*start, entry_costs(map), |c| c == end)
 }
 
 /**
  * Uses Dijkstra's algorithm to find the shortest path from the start node to the end node.
  *
- * The route is not returned, only the cost of traversing that path.
+ * The route is not returned, only the cost of traversing that path. See [`calc_route`] for the
+ * route.
  *
  * Returns i32::MAX if no path is found.
  */
 pub fn calc_route_cost(map: &CoordinateMap<i32>, start: &Coordinate, end: &Coordinate) -> i32 {
-    let mut unvisited = HashSet::new();
-    for c in map.keys() {
-        unvisited.insert(*c);
-    }
+    calc_route(map, start, end).map_or(i32::MAX, |(cost, _)| cost)
+}
 
-    let mut heap: BinaryHeap<DijkstraNode> = BinaryHeap::new();
-    heap.push(DijkstraNode {
-        coordinate: *start,
-        total_cost: 0,
-    });
+/**
+ * Uses Dijkstra's algorithm to find the cost of the shortest path from the start node to every
+ * coordinate that can be reached from it. The start itself costs nothing.
+ */
+pub fn calc_distances(map: &CoordinateMap<i32>, start: &Coordinate) -> CoordinateMap<i32> {
+    search::distances(*start, entry_costs(map))
+}
 
-    while let Some(current) = heap.pop() {
-        let current_risk = current.total_cost;
+#[cfg(test)]
+mod tests {
 
-        if current.coordinate == *end {
-            return current_risk;
-        }
+    use super::*;
 
-        if !unvisited.contains(&current.coordinate) {
-            continue;
+    fn load_map(rows: &[&str]) -> CoordinateMap<i32> {
+        let mut map = CoordinateMap::new();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                if let Some(cost) = c.to_digit(10) {
+                    map.insert(Coordinate::new(x as i32, y as i32), cost as i32);
+                }
+            }
         }
+        map
+    }
 
-        debug_assert!(current_risk != i32::MAX);
-        let adjacent = current.coordinate.orthogonal();
+    #[test]
+    fn calc_route_test() {
+        // The direct route along the top is blocked by expensive cells.
+        let map = load_map(&["1991", "1191", "9111"]);
+        let start = Coordinate::origin();
+        let end = Coordinate::new(3, 0);
 
-        for coordinate in adjacent {
-            if unvisited.contains(&coordinate) {
-                let new_risk = current_risk + map.get(&coordinate).unwrap();
-                heap.push(DijkstraNode {
-                    coordinate,
-                    total_cost: new_risk,
-                });
-            }
-        }
-        unvisited.remove(&current.coordinate);
+        let (cost, route) = calc_route(&map, &start, &end).unwrap();
+        assert_eq!(cost, 7);
+        assert_eq!(
+            route,
+            [
+                (0, 0),
+                (0, 1),
+                (1, 1),
+                (1, 2),
+                (2, 2),
+                (3, 2),
+                (3, 1),
+                (3, 0)
+            ]
+            .into_iter()
+            .map(Coordinate::from)
+            .collect::<Vec<_>>()
+        );
+        assert_eq!(calc_route_cost(&map, &start, &end), 7);
+
+        let gapped = load_map(&["1.1"]);
+        assert_eq!(calc_route(&gapped, &start, &Coordinate::new(2, 0)), None);
+        assert_eq!(
+            calc_route_cost(&gapped, &start, &Coordinate::new(2, 0)),
+            i32::MAX
+        );
     }
 
-    i32::MAX
+    #[test]
+    fn calc_distances_test() {
+        let map = load_map(&["123", "4.9"]);
+        let distances = calc_distances(&map, &Coordinate::origin());
+        assert_eq!(distances.len(), 5);
+        assert_eq!(distances[&Coordinate::origin()], 0);
+        assert_eq!(distances[&Coordinate::new(2, 0)], 5);
+        assert_eq!(distances[&Coordinate::new(0, 1)], 4);
+        assert_eq!(distances[&Coordinate::new(2, 1)], 14);
+        assert!(!distances.contains_key(&Coordinate::new(1, 1)));
+    }
 }
@@ -0,0 +1,139 @@
+use std::{error::Error, fmt::Display, io};
+
+pub type AocResult<T> = Result<T, AocError>;
+
+/**
+ * Errors raised while solving a puzzle.
+ */
+#[derive(Debug)]
+pub enum AocError {
+    Io(io::Error),
+    /**
+     * The input could not be parsed. Lines and columns are numbered from 1.
+     */
+    Parse {
+        line: usize,
+        column: Option<usize>,
+        message: String,
+    },
+    /**
+     * The puzzle reached a state it cannot be solved from, such as a simulation that never
+     * settles.
+     */
+    InvalidState(String),
+    /**
+     * The puzzle does not support what was asked of it, such as a part that does not exist.
+     */
+    Unsupported(String),
+}
+
+impl AocError {
+    /**
+     * Creates a parse error for the line at the given zero-based index, as produced by
+     * `lines().enumerate()`.
+     */
+    pub fn parse(index: usize, message: impl Into<String>) -> Self {
+        AocError::Parse {
+            line: index + 1,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    /**
+     * Creates a parse error for a character at the given zero-based line and column indexes.
+     */
+    pub fn parse_at(index: usize, column_index: usize, message: impl Into<String>) -> Self {
+        AocError::Parse {
+            line: index + 1,
+            column: Some(column_index + 1),
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_state(message: impl Into<String>) -> Self {
+        AocError::InvalidState(message.into())
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Io(err) => write!(f, "{err}"),
+            AocError::Parse {
+                line,
+                column: Some(column),
+                message,
+            } => write!(f, "line {line}, column {column}: {message}"),
+            AocError::Parse {
+                line,
+                column: None,
+                message,
+            } => write!(f, "line {line}: {message}"),
+            AocError::InvalidState(message) => write!(f, "invalid puzzle state: {message}"),
+            AocError::Unsupported(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(err: io::Error) -> Self {
+        AocError::Io(err)
+    }
+}
+
+/**
+ * Attaches a line to errors raised while parsing it.
+ */
+pub trait LineContext<T> {
+    /**
+     * Turns an error into a parse error for the line at the given zero-based index.
+     */
+    fn on_line(self, index: usize) -> AocResult<T>;
+}
+
+impl<T, E: Display> LineContext<T> for Result<T, E> {
+    fn on_line(self, index: usize) -> AocResult<T> {
+        self.map_err(|err| AocError::parse(index, err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn parse_display_test() {
+        assert_eq!(
+            AocError::parse(0, "bad number").to_string(),
+            "line 1: bad number"
+        );
+        assert_eq!(
+            AocError::parse_at(4, 2, "unknown tile 'x'").to_string(),
+            "line 5, column 3: unknown tile 'x'"
+        );
+    }
+
+    #[test]
+    fn on_line_test() {
+        let result = "12a".parse::<i32>().on_line(6);
+        match result {
+            Err(AocError::Parse { line, column, .. }) => {
+                assert_eq!(line, 7);
+                assert_eq!(column, None);
+            }
+            other => panic!("Unexpected result: {other:?}"),
+        }
+        assert_eq!("12".parse::<i32>().on_line(6).unwrap(), 12);
+    }
+}
@@ -0,0 +1,348 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    coordinate::{Coordinate, CoordinateMap},
+    error::{AocError, AocResult},
+};
+
+/**
+ * A dense, row-major grid of cells. Coordinates run from the origin in the top-left corner to
+ * (width - 1, height - 1).
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /**
+     * Creates a grid with every cell set to the given value.
+     */
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /**
+     * Creates a grid from its rows, which must all be the same length.
+     */
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, String> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(format!(
+                    "row {y} has {found} cells, expected {width}",
+                    found = row.len()
+                ));
+            }
+            cells.extend(row);
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /**
+     * Parses a grid with a character per cell, mapping each one with `parse_cell`. Empty lines
+     * are skipped. Errors give the line and column of the cell that could not be mapped.
+     */
+    pub fn parse(
+        input: &str,
+        mut parse_cell: impl FnMut(char) -> Result<T, String>,
+    ) -> AocResult<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            let start = cells.len();
+            for (column, c) in line.chars().enumerate() {
+                let cell =
+                    parse_cell(c).map_err(|message| AocError::parse_at(i, column, message))?;
+                cells.push(cell);
+            }
+            let row_width = cells.len() - start;
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(AocError::parse(
+                        i,
+                        format!("expected {width} cells, found {row_width}"),
+                    ))
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    /**
+     * Creates a grid covering the origin to the largest coordinates in the map, filling in cells
+     * missing from the map with the given value. Returns `None` if any coordinate is negative.
+     */
+    pub fn from_map(map: &CoordinateMap<T>, value: T) -> Option<Self>
+    where
+        T: Clone,
+    {
+        if map.keys().any(|c| c.x < 0 || c.y < 0) {
+            return None;
+        }
+        let width = map.keys().map(|c| c.x as usize + 1).max().unwrap_or(0);
+        let height = map.keys().map(|c| c.y as usize + 1).max().unwrap_or(0);
+        let mut grid = Grid::new(width, height, value);
+        for (coordinate, cell) in map {
+            grid[*coordinate] = cell.clone();
+        }
+        Some(grid)
+    }
+
+    /**
+     * Converts the grid into a map holding every cell.
+     */
+    pub fn to_map(&self) -> CoordinateMap<T>
+    where
+        T: Clone,
+    {
+        self.iter().map(|(c, cell)| (c, cell.clone())).collect()
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, coordinate: Coordinate) -> bool {
+        coordinate.x >= 0
+            && coordinate.y >= 0
+            && (coordinate.x as usize) < self.width
+            && (coordinate.y as usize) < self.height
+    }
+
+    fn index_of(&self, coordinate: Coordinate) -> Option<usize> {
+        self.contains(coordinate)
+            .then(|| coordinate.y as usize * self.width + coordinate.x as usize)
+    }
+
+    fn coordinate_of(&self, index: usize) -> Coordinate {
+        Coordinate::new((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    pub fn get(&self, coordinate: Coordinate) -> Option<&T> {
+        self.index_of(coordinate).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, coordinate: Coordinate) -> Option<&mut T> {
+        self.index_of(coordinate).map(|i| &mut self.cells[i])
+    }
+
+    /**
+     * Iterates over every cell with its coordinate, row by row.
+     */
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.coordinate_of(i), cell))
+    }
+
+    /**
+     * Iterates over every coordinate in the grid, row by row.
+     */
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinate> + '_ {
+        (0..self.cells.len()).map(|i| self.coordinate_of(i))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a size of 0, which an empty grid would otherwise ask for.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /**
+     * Iterates down a column, from the top. Empty if the column is out of bounds.
+     */
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+        (0..height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    /**
+     * Iterates over the orthogonal neighbours that are within the grid.
+     */
+    pub fn neighbours(&self, coordinate: Coordinate) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.cells_at(coordinate.orthogonal())
+    }
+
+    /**
+     * Iterates over the surrounding cells that are within the grid, including diagonals.
+     */
+    pub fn surrounding(&self, coordinate: Coordinate) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.cells_at(coordinate.surround())
+    }
+
+    fn cells_at(&self, coordinates: Vec<Coordinate>) -> impl Iterator<Item = (Coordinate, &T)> {
+        coordinates
+            .into_iter()
+            .filter_map(|c| self.get(c).map(|cell| (c, cell)))
+    }
+}
+
+impl<T> Index<Coordinate> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coordinate: Coordinate) -> &T {
+        self.get(coordinate)
+            .unwrap_or_else(|| panic!("{coordinate} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Coordinate> for Grid<T> {
+    fn index_mut(&mut self, coordinate: Coordinate) -> &mut T {
+        self.get_mut(coordinate)
+            .unwrap_or_else(|| panic!("{coordinate} is outside the grid"))
+    }
+}
+
+impl<T> From<Grid<T>> for CoordinateMap<T> {
+    fn from(grid: Grid<T>) -> Self {
+        let width = grid.width;
+        grid.cells
+            .into_iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                (
+                    Coordinate::new((i % width) as i32, (i / width) as i32),
+                    cell,
+                )
+            })
+            .collect()
+    }
+}
+
+/**
+ * Prints a row per line, with the cells of each row written one after another.
+ */
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                cell.fmt(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const INPUT: &str = "123\n456\n\n";
+
+    fn digits(input: &str) -> AocResult<Grid<u32>> {
+        Grid::parse(input, |c| {
+            c.to_digit(10).ok_or(format!("'{c}' is not a digit"))
+        })
+    }
+
+    #[test]
+    fn parse_test() {
+        let grid = digits(INPUT).unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[Coordinate::new(2, 1)], 6);
+        assert_eq!(grid.get(Coordinate::new(3, 0)), None);
+        assert_eq!(grid.get(Coordinate::new(0, -1)), None);
+        assert_eq!(grid.to_string(), "123\n456");
+
+        assert_eq!(
+            digits("12\n1x\n").unwrap_err().to_string(),
+            "line 2, column 2: 'x' is not a digit"
+        );
+        assert_eq!(
+            digits("12\n123\n").unwrap_err().to_string(),
+            "line 2: expected 2 cells, found 3"
+        );
+    }
+
+    #[test]
+    fn iterators_test() {
+        let grid = digits(INPUT).unwrap();
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(
+            grid.neighbours(Coordinate::new(0, 0))
+                .map(|(_, &cell)| cell)
+                .collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+        assert_eq!(grid.surrounding(Coordinate::new(1, 1)).count(), 5);
+        assert_eq!(grid.coordinates().last(), Some(Coordinate::new(2, 1)));
+    }
+
+    #[test]
+    fn map_test() {
+        let mut grid = digits(INPUT).unwrap();
+        *grid.get_mut(Coordinate::new(0, 1)).unwrap() = 9;
+
+        let map = grid.to_map();
+        assert_eq!(map.len(), 6);
+        assert_eq!(map[&Coordinate::new(0, 1)], 9);
+        assert_eq!(CoordinateMap::from(grid.clone()), map);
+        assert_eq!(Grid::from_map(&map, 0), Some(grid));
+
+        let mut sparse = CoordinateMap::new();
+        sparse.insert(Coordinate::new(2, 0), 7);
+        let grid = Grid::from_map(&sparse, 0).unwrap();
+        assert_eq!(grid.to_string(), "007");
+
+        sparse.insert(Coordinate::new(-1, 0), 7);
+        assert_eq!(Grid::from_map(&sparse, 0), None);
+    }
+
+    #[test]
+    fn from_rows_test() {
+        let grid = Grid::from_rows(vec![vec!['a', 'b'], vec!['c', 'd']]).unwrap();
+        assert_eq!(grid[Coordinate::new(1, 1)], 'd');
+        assert!(Grid::from_rows(vec![vec!['a', 'b'], vec!['c']]).is_err());
+
+        let empty = Grid::<char>::from_rows(Vec::new()).unwrap();
+        assert_eq!(empty.rows().count(), 0);
+        assert_eq!(empty.to_string(), "");
+    }
+}
@@ -0,0 +1,354 @@
+use crate::coordinate::Integer;
+
+/**
+ * The values from `start` up to but not including `end`. Intervals where `end` isn't after
+ * `start` are empty.
+ */
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Integer> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+
+    /**
+     * Creates the interval running from `start` to `last`, including `last`.
+     */
+    pub fn inclusive(start: T, last: T) -> Self {
+        Interval::new(start, last + T::ONE)
+    }
+
+    pub fn from_length(start: T, length: T) -> Self {
+        Interval::new(start, start + length)
+    }
+
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::ZERO
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains_value(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /**
+     * Whether every value of the other interval is also in this one.
+     */
+    pub fn contains(&self, other: &Self) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    /**
+     * Whether the intervals share at least one value.
+     */
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /**
+     * Gets the values in both intervals, or `None` if there aren't any.
+     */
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /**
+     * Moves the interval by the offset, keeping its length.
+     */
+    pub fn shift(&self, offset: T) -> Self {
+        Interval::new(self.start + offset, self.end + offset)
+    }
+}
+
+/**
+ * Moves the values in the source interval so that the start of the source lands on the
+ * destination.
+ */
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Mapping<T> {
+    pub source: Interval<T>,
+    pub destination: T,
+}
+
+impl<T: Integer> Mapping<T> {
+    pub fn new(source: Interval<T>, destination: T) -> Self {
+        Mapping {
+            source,
+            destination,
+        }
+    }
+
+    fn offset(&self) -> T {
+        self.destination - self.source.start
+    }
+
+    /**
+     * Gets where the value is moved to, or `None` if it isn't in the source interval.
+     */
+    pub fn translate(&self, value: T) -> Option<T> {
+        self.source
+            .contains_value(value)
+            .then(|| value + self.offset())
+    }
+}
+
+/**
+ * A set of values, stored as the sorted list of intervals that cover them. Intervals that overlap
+ * or touch are merged, so the intervals never share or neighbour each other's values.
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+
+    /**
+     * Gets the intervals making up the set, in order.
+     */
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /**
+     * Gets the number of values in the set.
+     */
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::ZERO, |total, interval| total + interval.len())
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.intervals
+            .iter()
+            .any(|interval| interval.contains_value(value))
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if !interval.is_empty() {
+            self.intervals.push(interval);
+            self.normalise();
+        }
+    }
+
+    pub fn remove(&mut self, removed: Interval<T>) {
+        if removed.is_empty() {
+            return;
+        }
+        self.intervals = self
+            .intervals
+            .iter()
+            .flat_map(|interval| {
+                [
+                    Interval::new(interval.start, interval.end.min(removed.start)),
+                    Interval::new(interval.start.max(removed.end), interval.end),
+                ]
+            })
+            .filter(|interval| !interval.is_empty())
+            .collect();
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.intervals
+            .iter()
+            .chain(other.intervals.iter())
+            .copied()
+            .collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.intervals
+            .iter()
+            .flat_map(|a| other.intervals.iter().filter_map(|b| a.intersection(b)))
+            .collect()
+    }
+
+    /**
+     * Gets the values in this set that aren't in the other.
+     */
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for interval in &other.intervals {
+            difference.remove(*interval);
+        }
+        difference
+    }
+
+    /**
+     * Gets the intervals between the lowest and highest values that aren't in the set.
+     */
+    pub fn gaps(&self) -> Vec<Interval<T>> {
+        self.intervals
+            .windows(2)
+            .map(|pair| Interval::new(pair[0].end, pair[1].start))
+            .collect()
+    }
+
+    /**
+     * Moves each value through the first mapping whose source contains it. Values that no
+     * mapping covers are kept as they are.
+     */
+    pub fn translate(&self, mappings: &[Mapping<T>]) -> Self {
+        let mut remaining = self.clone();
+        let mut translated = IntervalSet::new();
+        for mapping in mappings {
+            for interval in remaining.intervals() {
+                if let Some(hit) = interval.intersection(&mapping.source) {
+                    translated.intervals.push(hit.shift(mapping.offset()));
+                }
+            }
+            remaining.remove(mapping.source);
+        }
+        translated.intervals.extend(remaining.intervals);
+        translated.normalise();
+        translated
+    }
+
+    fn normalise(&mut self) {
+        self.intervals.retain(|interval| !interval.is_empty());
+        self.intervals.sort_by_key(|interval| interval.start);
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(self.intervals.len());
+        for interval in self.intervals.drain(..) {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        self.intervals = merged;
+    }
+}
+
+impl<T: Integer> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet::new()
+    }
+}
+
+impl<T: Integer> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet {
+            intervals: iter.into_iter().collect(),
+        };
+        set.normalise();
+        set
+    }
+}
+
+impl<T: Integer> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        IntervalSet::from_iter([interval])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn interval_test() {
+        let a = Interval::new(10, 20);
+        assert_eq!(a.len(), 10);
+        assert!(a.contains_value(10));
+        assert!(!a.contains_value(20));
+        assert!(a.contains(&Interval::new(12, 20)));
+        assert!(!a.contains(&Interval::new(12, 21)));
+        assert!(a.overlaps(&Interval::new(19, 30)));
+        assert!(!a.overlaps(&Interval::new(20, 30)));
+        assert_eq!(
+            a.intersection(&Interval::inclusive(15, 25)),
+            Some(Interval::new(15, 20))
+        );
+        assert_eq!(Interval::new(10, 5).len(), 0);
+        assert_eq!(Interval::from_length(3, 4), Interval::new(3, 7));
+    }
+
+    #[test]
+    fn insert_test() {
+        // Intervals that overlap or touch are merged.
+        assert_eq!(set(&[(10, 20), (20, 30), (30, 40)]), set(&[(10, 40)]));
+        assert_eq!(
+            set(&[(50, 57), (10, 20), (15, 30), (28, 40)]).intervals(),
+            [Interval::new(10, 40), Interval::new(50, 57)]
+        );
+        assert_eq!(set(&[(10, 20), (12, 15)]), set(&[(10, 20)]));
+        assert!(set(&[(10, 5)]).is_empty());
+
+        let mut s = set(&[(0, 5)]);
+        s.insert(Interval::new(7, 9));
+        assert_eq!(s.len(), 7);
+        assert_eq!(s.gaps(), vec![Interval::new(5, 7)]);
+        assert!(s.contains(8));
+        assert!(!s.contains(5));
+        assert_eq!(s.min(), Some(0));
+    }
+
+    #[test]
+    fn set_operations_test() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25)]);
+        assert_eq!(a.union(&b), set(&[(0, 30)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25)]));
+        assert_eq!(a.difference(&b), set(&[(0, 5), (25, 30)]));
+        assert_eq!(b.difference(&a), set(&[(10, 20)]));
+
+        let mut c = a.clone();
+        c.remove(Interval::new(2, 4));
+        assert_eq!(c, set(&[(0, 2), (4, 10), (20, 30)]));
+    }
+
+    #[test]
+    fn translate_test() {
+        let mappings = [
+            Mapping::new(Interval::from_length(98, 2), 50),
+            Mapping::new(Interval::from_length(50, 48), 52),
+        ];
+        assert_eq!(mappings[1].translate(79), Some(81));
+        assert_eq!(mappings[0].translate(79), None);
+
+        let seeds = set(&[(79, 93), (55, 68)]);
+        assert_eq!(seeds.translate(&mappings), set(&[(57, 70), (81, 95)]));
+
+        // Values outside every mapping stay put, and values are only moved once.
+        let spread = set(&[(40, 100)]);
+        assert_eq!(spread.translate(&mappings), set(&[(40, 100)]));
+        assert_eq!(spread.translate(&mappings).len(), 60);
+        assert_eq!(
+            set(&[(96, 100)]).translate(&mappings),
+            set(&[(50, 52), (98, 100)])
+        );
+    }
+}
@@ -1,3 +1,15 @@
+pub mod automaton;
 pub mod coordinate;
+pub mod cycle;
 pub mod dijkstra;
-pub mod file_utils;
+pub mod error;
+pub mod grid;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod ocr;
+pub mod parse;
+pub mod render;
+pub mod search;
+pub mod simulation;
+pub mod solution;
@@ -0,0 +1,467 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use crate::error::{AocError, AocResult};
+
+/**
+ * Gets the greatest common divisor of the two numbers, which is never negative.
+ */
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/**
+ * Gets the lowest common multiple of the two numbers, which is never negative. It is 0 if
+ * either number is.
+ */
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        (a / gcd(a, b) * b).abs()
+    }
+}
+
+/**
+ * Gets the lowest common multiple of the two numbers like [`lcm`], or `None` if it doesn't fit
+ * in an `i64`.
+ */
+pub fn checked_lcm(a: i64, b: i64) -> Option<i64> {
+    if a == 0 || b == 0 {
+        Some(0)
+    } else {
+        (a / gcd(a, b)).checked_mul(b).and_then(i64::checked_abs)
+    }
+}
+
+/**
+ * Gets the greatest common divisor of all the numbers, or 0 if there are none.
+ */
+pub fn gcd_all(numbers: impl IntoIterator<Item = i64>) -> i64 {
+    numbers.into_iter().fold(0, gcd)
+}
+
+/**
+ * Gets the lowest common multiple of all the numbers, or 1 if there are none, such as the
+ * period after which several cycles all line up again. It is 0 if any number is, so check
+ * before using it as a modulus.
+ */
+pub fn lcm_all(numbers: impl IntoIterator<Item = i64>) -> i64 {
+    numbers.into_iter().fold(1, lcm)
+}
+
+/**
+ * Gets the greatest common divisor of `a` and `b` along with `x` and `y` such that
+ * `a * x + b * y` is that divisor.
+ */
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/**
+ * Gets `base` to the power of `exponent`, modulo `modulus`, as a value from 0 up to the
+ * modulus. Intermediate products are widened, so any `i64` modulus is safe.
+ */
+pub fn mod_pow(base: i64, mut exponent: u64, modulus: i64) -> i64 {
+    let modulus = modulus as i128;
+    let mut base = (base as i128).rem_euclid(modulus);
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result as i64
+}
+
+/**
+ * Gets the number that `a` is multiplied by to give 1 modulo `modulus`, if there is one. There
+ * is only an inverse when `a` and the modulus have no common divisor, and never modulo 0.
+ */
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    if modulus == 0 {
+        return None;
+    }
+    let (divisor, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (divisor == 1).then(|| x.rem_euclid(modulus))
+}
+
+/**
+ * Finds the numbers that satisfy every congruence, given as a remainder and a modulus, using
+ * the Chinese remainder theorem. They are returned as a single congruence, whose modulus is
+ * the lowest common multiple of the moduli. The moduli don't need to be coprime, but it is an
+ * error if one is 0, if the congruences contradict each other or if that multiple doesn't fit
+ * in an `i64`.
+ */
+pub fn chinese_remainder(
+    congruences: impl IntoIterator<Item = (i64, i64)>,
+) -> AocResult<(i64, i64)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(remainder, modulus), (r, m)| {
+            if m == 0 {
+                return Err(AocError::invalid_state(format!(
+                    "x = {r} (mod 0) has no remainders to combine"
+                )));
+            }
+            let (divisor, x, _) = extended_gcd(modulus, m);
+            let difference = r - remainder;
+            if difference % divisor != 0 {
+                return Err(AocError::invalid_state(format!(
+                    "x = {r} (mod {m}) contradicts x = {remainder} (mod {modulus})"
+                )));
+            }
+            let combined = (modulus / divisor)
+                .checked_mul(m)
+                .map(i64::abs)
+                .ok_or_else(|| {
+                    AocError::invalid_state(format!(
+                        "the combined modulus of {modulus} and {m} is too large"
+                    ))
+                })?;
+            let step = m / divisor;
+            let multiple = (difference / divisor) as i128 * x as i128 % step as i128;
+            let remainder =
+                (remainder as i128 + modulus as i128 * multiple).rem_euclid(combined as i128);
+            Ok((remainder as i64, combined))
+        })
+}
+
+/**
+ * An integer modulo `M`, always held as a value from 0 up to `M`, so that arithmetic on it
+ * wraps rather than overflowing.
+ */
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Mod<const M: i64>(i64);
+
+impl<const M: i64> Mod<M> {
+    pub fn new(value: i64) -> Self {
+        Mod(value.rem_euclid(M))
+    }
+
+    pub fn value(self) -> i64 {
+        self.0
+    }
+
+    pub fn pow(self, exponent: u64) -> Self {
+        Mod(mod_pow(self.0, exponent, M))
+    }
+
+    /**
+     * Gets the number that this is multiplied by to give 1, if there is one.
+     */
+    pub fn inverse(self) -> Option<Self> {
+        mod_inverse(self.0, M).map(Mod)
+    }
+}
+
+impl<const M: i64> From<i64> for Mod<M> {
+    fn from(value: i64) -> Self {
+        Mod::new(value)
+    }
+}
+
+impl<const M: i64> fmt::Display for Mod<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<const M: i64> Add for Mod<M> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Mod(((self.0 as i128 + other.0 as i128) % M as i128) as i64)
+    }
+}
+
+impl<const M: i64> Sub for Mod<M> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Mod((self.0 as i128 - other.0 as i128).rem_euclid(M as i128) as i64)
+    }
+}
+
+impl<const M: i64> Mul for Mod<M> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Mod((self.0 as i128 * other.0 as i128 % M as i128) as i64)
+    }
+}
+
+impl<const M: i64> Neg for Mod<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Mod::default() - self
+    }
+}
+
+impl<const M: i64> AddAssign for Mod<M> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const M: i64> SubAssign for Mod<M> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const M: i64> MulAssign for Mod<M> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+/**
+ * An integer modulo a modulus only known at runtime, such as the length of a list being
+ * rotated. It behaves like [`Mod`], and both sides of any arithmetic must share a modulus,
+ * which is always positive.
+ */
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DynMod {
+    value: i64,
+    modulus: i64,
+}
+
+impl DynMod {
+    pub fn new(value: i64, modulus: i64) -> AocResult<Self> {
+        if modulus <= 0 {
+            return Err(AocError::invalid_state(format!(
+                "can't count modulo {modulus}, as the modulus must be positive"
+            )));
+        }
+        Ok(DynMod {
+            value: value.rem_euclid(modulus),
+            modulus,
+        })
+    }
+
+    pub fn value(self) -> i64 {
+        self.value
+    }
+
+    pub fn modulus(self) -> i64 {
+        self.modulus
+    }
+
+    pub fn pow(self, exponent: u64) -> Self {
+        self.with(mod_pow(self.value, exponent, self.modulus))
+    }
+
+    /**
+     * Gets the number that this is multiplied by to give 1, if there is one.
+     */
+    pub fn inverse(self) -> Option<Self> {
+        mod_inverse(self.value, self.modulus).map(|value| self.with(value))
+    }
+
+    fn with(self, value: i64) -> Self {
+        debug_assert!((0..self.modulus).contains(&value));
+        DynMod {
+            value,
+            modulus: self.modulus,
+        }
+    }
+
+    fn checked(self, other: Self) -> Self {
+        debug_assert_eq!(self.modulus, other.modulus, "the moduli differ");
+        other
+    }
+}
+
+impl fmt::Display for DynMod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl Add for DynMod {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let other = self.checked(other);
+        self.with(((self.value as i128 + other.value as i128) % self.modulus as i128) as i64)
+    }
+}
+
+impl Add<i64> for DynMod {
+    type Output = Self;
+
+    fn add(self, other: i64) -> Self {
+        self + self.with(other.rem_euclid(self.modulus))
+    }
+}
+
+impl Sub for DynMod {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        let other = self.checked(other);
+        self.with(
+            (self.value as i128 - other.value as i128).rem_euclid(self.modulus as i128) as i64,
+        )
+    }
+}
+
+impl Mul for DynMod {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let other = self.checked(other);
+        self.with((self.value as i128 * other.value as i128 % self.modulus as i128) as i64)
+    }
+}
+
+impl Neg for DynMod {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.with(0) - self
+    }
+}
+
+impl AddAssign for DynMod {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for DynMod {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for DynMod {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn gcd_test() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(7, 0), 7);
+        assert_eq!(lcm(4, -6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(gcd_all([24, 36, 60]), 12);
+        assert_eq!(lcm_all([23, 19, 13, 17]), 96577);
+        assert_eq!(lcm_all([]), 1);
+        assert_eq!(lcm_all([3, 0]), 0);
+        assert_eq!(checked_lcm(4, -6), Some(12));
+        assert_eq!(checked_lcm(0, 6), Some(0));
+        assert_eq!(checked_lcm(i64::MAX, i64::MAX - 1), None);
+
+        for (a, b) in [(240, 46), (-7, 3), (0, 5), (5, 0)] {
+            let (divisor, x, y) = extended_gcd(a, b);
+            assert_eq!(divisor, gcd(a, b));
+            assert_eq!(a * x + b * y, divisor);
+        }
+    }
+
+    #[test]
+    fn mod_test() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(mod_pow(3, 200, 1_000_000_007), 136_318_165);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(3, 0), None);
+    }
+
+    #[test]
+    fn chinese_remainder_test() {
+        assert_eq!(
+            chinese_remainder([(2, 3), (3, 5), (2, 7)]).unwrap(),
+            (23, 105)
+        );
+        assert_eq!(chinese_remainder([(3, 4), (1, 6)]).unwrap(), (7, 12));
+        assert!(chinese_remainder([(1, 4), (2, 6)]).is_err());
+        assert_eq!(chinese_remainder([]).unwrap(), (0, 1));
+        assert!(chinese_remainder([(2, 3), (1, 0)]).is_err());
+        assert_eq!(
+            chinese_remainder([(0, 7), (12, 13), (55, 59), (25, 31), (12, 19)]).unwrap(),
+            (1068781, 7 * 13 * 59 * 31 * 19)
+        );
+
+        // Moduli whose lowest common multiple is too big for an i64 are an error, not a wrap.
+        let primes = [1_000_000_007, 1_000_000_009, 998_244_353];
+        assert!(chinese_remainder(primes.map(|p| (1, p))).is_err());
+        assert_eq!(
+            chinese_remainder(primes[..2].iter().map(|&p| (1, p))).unwrap(),
+            (1, 1_000_000_007 * 1_000_000_009)
+        );
+    }
+
+    #[test]
+    fn mod_type_test() {
+        type Clock = Mod<12>;
+        let mut time = Clock::new(9);
+        time += Clock::new(5);
+        assert_eq!(time.value(), 2);
+        assert_eq!((time - Clock::new(5)).value(), 9);
+        assert_eq!((-time).value(), 10);
+        assert_eq!(Clock::from(-1).to_string(), "11");
+        assert_eq!(Clock::new(5).inverse(), Some(Clock::new(5)));
+        assert_eq!(Clock::new(4).inverse(), None);
+
+        // Products near the modulus don't overflow.
+        const BIG: i64 = (1 << 62) - 57;
+        let big = Mod::<BIG>::new(BIG - 1);
+        assert_eq!((big * big).value(), 1);
+        assert_eq!(big.pow(3).value(), BIG - 1);
+    }
+
+    #[test]
+    fn dyn_mod_test() {
+        let mut index = DynMod::new(3, 7).unwrap();
+        index += DynMod::new(-5, 7).unwrap();
+        assert_eq!(index.value(), 5);
+        assert_eq!(
+            (index + 811_589_153 * 10).value(),
+            (5 + 811_589_153 * 10) % 7
+        );
+        assert_eq!((-index).value(), 2);
+        assert_eq!((index * DynMod::new(3, 7).unwrap()).value(), 1);
+        assert_eq!(index.inverse(), Some(DynMod::new(3, 7).unwrap()));
+        assert_eq!(index.pow(6).value(), 1);
+        assert_eq!(index.modulus(), 7);
+        assert_eq!(index.to_string(), "5");
+        assert!(DynMod::new(1, 0).is_err());
+        assert!(DynMod::new(1, -7).is_err());
+    }
+}
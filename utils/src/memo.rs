@@ -0,0 +1,237 @@
+use std::{cell::RefCell, collections::HashMap, fmt, hash::Hash};
+
+/**
+ * How often a [`Memo`] found the value it was asked for.
+ */
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl MemoStats {
+    /**
+     * Gets the fraction of lookups that were answered from the cache, or 0 if there were none.
+     */
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+impl fmt::Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+thread_local! {
+    static REPORTS: RefCell<Vec<(&'static str, MemoStats)>> = const { RefCell::new(Vec::new()) };
+}
+
+/**
+ * Takes the statistics reported by the named memos dropped on this thread since the last call,
+ * in the order they were dropped. The runner uses this to show how much caching helped a
+ * solution.
+ */
+pub fn take_reports() -> Vec<(&'static str, MemoStats)> {
+    REPORTS.with(|reports| reports.take())
+}
+
+/**
+ * A cache of the values of a function, keyed on the state it was called with. Recursive
+ * solvers wrap their body in [`Memo::cached`] so that each state is only solved once, with
+ * the statistics showing how much the caching saved.
+ */
+#[derive(Debug)]
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+    stats: MemoStats,
+    name: Option<&'static str>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            values: HashMap::new(),
+            stats: MemoStats::default(),
+            name: None,
+        }
+    }
+}
+
+/**
+ * Clones the cached values and statistics but not the name, so that only the original reports
+ * when dropped.
+ */
+impl<K: Clone, V: Clone> Clone for Memo<K, V> {
+    fn clone(&self) -> Self {
+        Memo {
+            values: self.values.clone(),
+            stats: self.stats,
+            name: None,
+        }
+    }
+}
+
+impl<K, V> Drop for Memo<K, V> {
+    fn drop(&mut self) {
+        if let Some(name) = self.name {
+            REPORTS.with(|reports| reports.borrow_mut().push((name, self.stats)));
+        }
+    }
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+     * Creates a memo that reports its statistics under the name when it is dropped, to be
+     * picked up by [`take_reports`].
+     */
+    pub fn named(name: &'static str) -> Self {
+        let mut memo = Self::default();
+        memo.name = Some(name);
+        memo
+    }
+
+    /**
+     * Gets the cached value for the key, counting the lookup as a hit or a miss.
+     */
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let value = self.values.get(key).cloned();
+        if value.is_some() {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+        }
+        value
+    }
+
+    /**
+     * Caches the value for the key, returning it for convenience.
+     */
+    pub fn insert(&mut self, key: K, value: V) -> V {
+        self.values.insert(key, value.clone());
+        value
+    }
+
+    /**
+     * Gets the cached value for the key, or computes and caches it if there isn't one. The
+     * computation is given the memo back, so that it can recurse into other states.
+     */
+    pub fn cached(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = compute(self);
+        self.insert(key, value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+
+    /**
+     * Forgets every cached value, along with the statistics.
+     */
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.stats = MemoStats::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /**
+     * Counts the paths from the top left of a grid to the bottom right, moving only right and
+     * down.
+     */
+    fn paths(memo: &mut Memo<(u32, u32), u64>, x: u32, y: u32) -> u64 {
+        memo.cached((x, y), |memo| {
+            if x == 0 || y == 0 {
+                1
+            } else {
+                paths(memo, x - 1, y) + paths(memo, x, y - 1)
+            }
+        })
+    }
+
+    #[test]
+    fn memo_test() {
+        let mut memo = Memo::new();
+        assert_eq!(paths(&mut memo, 16, 16), 601_080_390);
+        assert_eq!(memo.len(), 17 * 17 - 1);
+        let stats = memo.stats();
+        assert_eq!(stats.misses, memo.len());
+        assert!(stats.hits > 0);
+
+        // Asking again is answered straight from the cache.
+        assert_eq!(paths(&mut memo, 16, 16), 601_080_390);
+        assert_eq!(memo.stats().hits, stats.hits + 1);
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats(), MemoStats::default());
+        assert_eq!(memo.stats().hit_rate(), 0.0);
+    }
+
+    #[test]
+    fn report_test() {
+        take_reports();
+        {
+            let mut memo = Memo::named("paths");
+            paths(&mut memo, 2, 2);
+            paths(&mut Memo::new(), 2, 2);
+        }
+        let reports = take_reports();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].0, "paths");
+        assert_eq!(reports[0].1.misses, 8);
+        assert!(take_reports().is_empty());
+
+        // A clone keeps the cache but doesn't report a second time.
+        {
+            let mut memo = Memo::named("paths");
+            paths(&mut memo, 2, 2);
+            let mut copy = memo.clone();
+            assert_eq!(copy.len(), memo.len());
+            paths(&mut copy, 2, 2);
+        }
+        assert_eq!(take_reports().len(), 1);
+    }
+
+    #[test]
+    fn stats_test() {
+        let stats = MemoStats { hits: 3, misses: 1 };
+        assert_eq!(stats.hit_rate(), 0.75);
+        assert_eq!(stats.to_string(), "3 hits, 1 misses (75.0% hit rate)");
+    }
+}
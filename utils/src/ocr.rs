@@ -0,0 +1,202 @@
+use std::collections::HashSet;
+
+use crate::{
+    coordinate::Coordinate,
+    error::{AocError, AocResult},
+};
+
+/**
+ * The glyphs that are six rows tall, each four columns wide apart from `I` and `Y`.
+ */
+const SMALL_GLYPHS: &[(char, &[&str])] = &[
+    ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', &["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/**
+ * The glyphs that are ten rows tall, each six columns wide apart from `J`.
+ */
+#[rustfmt::skip]
+const LARGE_GLYPHS: &[(char, &[&str])] = &[
+    ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#",
+            "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', &["#####.", "#....#", "#....#", "#....#", "#####.",
+            "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', &[".####.", "#....#", "#.....", "#.....", "#.....",
+            "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', &["######", "#.....", "#.....", "#.....", "#####.",
+            "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', &["######", "#.....", "#.....", "#.....", "#####.",
+            "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', &[".####.", "#....#", "#.....", "#.....", "#.....",
+            "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', &["#....#", "#....#", "#....#", "#....#", "######",
+            "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', &["..###", "...#.", "...#.", "...#.", "...#.",
+            "...#.", "...#.", "#..#.", "#..#.", ".##.."]),
+    ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....",
+            "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', &["#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#",
+            "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', &["#####.", "#....#", "#....#", "#....#", "#####.",
+            "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', &["#####.", "#....#", "#....#", "#....#", "#####.",
+            "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..",
+            "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', &["######", ".....#", ".....#", "....#.", "...#..",
+            "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/**
+ * Reads the letters spelt out by dot-matrix art, where `#` is lit and `.` is unlit, as drawn
+ * in puzzles such as 2022 day 10. Rows are separated by new lines.
+ */
+pub fn recognise(art: &str) -> AocResult<String> {
+    let mut points = HashSet::new();
+    for (y, line) in art.lines().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            match c {
+                '#' => {
+                    points.insert(Coordinate::new(x as i32, y as i32));
+                }
+                '.' => {}
+                _ => return Err(AocError::parse_at(y, x, format!("unknown art cell '{c}'"))),
+            }
+        }
+    }
+    recognise_points(&points)
+}
+
+/**
+ * Reads the letters spelt out by the lit points. The letters can be either six or ten rows
+ * tall, and are read left to right, split by the columns that have nothing lit.
+ */
+pub fn recognise_points(points: &HashSet<Coordinate>) -> AocResult<String> {
+    let (Some(min), Some(max)) = (
+        points
+            .iter()
+            .copied()
+            .reduce(|a, b| Coordinate::new(a.x.min(b.x), a.y.min(b.y))),
+        points
+            .iter()
+            .copied()
+            .reduce(|a, b| Coordinate::new(a.x.max(b.x), a.y.max(b.y))),
+    ) else {
+        return Err(AocError::invalid_state("there are no letters to recognise"));
+    };
+    let glyphs = match max.y - min.y + 1 {
+        6 => SMALL_GLYPHS,
+        10 => LARGE_GLYPHS,
+        height => {
+            return Err(AocError::invalid_state(format!(
+                "letters are 6 or 10 rows tall, not {height}"
+            )))
+        }
+    };
+
+    let columns = (min.x..=max.x)
+        .map(|x| {
+            (min.y..=max.y)
+                .map(|y| points.contains(&Coordinate::new(x, y)))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut letters = String::new();
+    let mut start = 0;
+    while start < columns.len() {
+        let end = (start..columns.len())
+            .find(|&x| !columns[x].contains(&true))
+            .unwrap_or(columns.len());
+        let rows = (0..columns[start].len())
+            .map(|y| {
+                columns[start..end]
+                    .iter()
+                    .map(|column| if column[y] { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        let letter = glyphs
+            .iter()
+            .find(|(_, glyph)| *glyph == rows)
+            .map(|&(letter, _)| letter)
+            .ok_or_else(|| {
+                AocError::invalid_state(format!(
+                    "unknown letter at column {column}:\n{rows}",
+                    column = min.x + start as i32,
+                    rows = rows.join("\n")
+                ))
+            })?;
+        letters.push(letter);
+        start = (end..columns.len())
+            .find(|&x| columns[x].contains(&true))
+            .unwrap_or(columns.len());
+    }
+    Ok(letters)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn recognise_test() {
+        assert_eq!(
+            recognise(
+                ".##..###..####.#.....##..#...#\n\
+                 #..#.#..#.#....#....#..#.#...#\n\
+                 #..#.###..###..#....#..#..#.#.\n\
+                 ####.#..#.#....#....#..#...#..\n\
+                 #..#.#..#.#....#....#..#...#..\n\
+                 #..#.###..####.####..##....#.."
+            )
+            .unwrap(),
+            "ABELOY"
+        );
+
+        // Blank rows and columns around the letters are ignored.
+        let points = LARGE_GLYPHS
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (_, glyph))| {
+                glyph.iter().enumerate().flat_map(move |(y, row)| {
+                    row.chars()
+                        .enumerate()
+                        .filter(|&(_, c)| c == '#')
+                        .map(move |(x, _)| {
+                            Coordinate::new(3 + 8 * i as i32 + x as i32, y as i32 - 4)
+                        })
+                })
+            })
+            .collect::<HashSet<_>>();
+        assert_eq!(recognise_points(&points).unwrap(), "ABCEFGHJKLNPRXZ");
+    }
+
+    #[test]
+    fn unknown_test() {
+        assert!(recognise("").is_err());
+        assert!(recognise("#\n#\n#").is_err());
+        assert!(recognise("####\n#..#\n#..#\n#..#\n#..#\n####").is_err());
+        assert!(recognise("####\n#..x\n#..#\n#..#\n#..#\n####").is_err());
+    }
+}
@@ -0,0 +1,250 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    error::{AocError, AocResult, LineContext},
+    grid::Grid,
+};
+
+/**
+ * A group of lines separated from the rest of the input by blank lines, such as one monkey's
+ * notes. Each line is kept with its zero-based index in the whole input, so that errors can
+ * name the right line.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block<'a> {
+    lines: Vec<(usize, &'a str)>,
+}
+
+impl<'a> Block<'a> {
+    /**
+     * Gets the lines of the block, each with its index in the input.
+     */
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        self.lines.iter().copied()
+    }
+
+    /**
+     * Gets the index in the input of the first line of the block.
+     */
+    pub fn first_index(&self) -> usize {
+        self.lines[0].0
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /**
+     * Gets the line at the given position within the block, or a parse error on the block's
+     * last line if it is too short.
+     */
+    pub fn line(&self, position: usize) -> AocResult<(usize, &'a str)> {
+        self.lines.get(position).copied().ok_or_else(|| {
+            let (last, _) = self.lines[self.lines.len() - 1];
+            AocError::parse(last, format!("expected at least {} lines", position + 1))
+        })
+    }
+}
+
+/**
+ * Splits the input into the groups of lines between blank lines. Lines holding only whitespace
+ * count as blank.
+ */
+pub fn blocks(input: &str) -> Vec<Block<'_>> {
+    let mut blocks = Vec::new();
+    let mut lines = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            if !lines.is_empty() {
+                blocks.push(Block {
+                    lines: std::mem::take(&mut lines),
+                });
+            }
+        } else {
+            lines.push((i, line));
+        }
+    }
+    if !lines.is_empty() {
+        blocks.push(Block { lines });
+    }
+    blocks
+}
+
+/**
+ * Parses the values in a line split by the separator, such as `3, 4, 5` split by `,`.
+ * Whitespace around each value is ignored, as are empty values, so runs of spaces can be used
+ * as a separator.
+ */
+pub fn list<T>(line: &str, separator: &str, index: usize) -> AocResult<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    line.split(separator)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| parse_value(value, index))
+        .collect()
+}
+
+/**
+ * Parses every integer found in the text, ignoring anything else, such as the `-2` and `15` in
+ * `x=-2, y=15`. A `-` is only taken as a sign when it isn't straight after a digit, so ranges
+ * like `2-4` give `2` and `4`.
+ */
+pub fn signed_ints<T>(line: &str, index: usize) -> AocResult<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let bytes = line.as_bytes();
+    let mut values = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let signed = bytes[i] == b'-'
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            && (i == 0 || !bytes[i - 1].is_ascii_digit());
+        if signed || bytes[i].is_ascii_digit() {
+            let start = i;
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            values.push(parse_value(&line[start..i], index)?);
+        } else {
+            i += 1;
+        }
+    }
+    Ok(values)
+}
+
+/**
+ * Splits a `key: value` line at its first colon, trimming whitespace from both halves.
+ */
+pub fn record(line: &str, index: usize) -> AocResult<(&str, &str)> {
+    line.split_once(':')
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .ok_or_else(|| AocError::parse(index, format!("expected 'key: value', found '{line}'")))
+}
+
+/**
+ * Gets the rest of a line after the given text, ignoring leading whitespace, such as the
+ * items after `Starting items: `.
+ */
+pub fn field<'a>(line: &'a str, prefix: &str, index: usize) -> AocResult<&'a str> {
+    line.trim_start()
+        .strip_prefix(prefix)
+        .ok_or_else(|| AocError::parse(index, format!("expected '{prefix}', found '{line}'")))
+}
+
+/**
+ * Splits a line into runs of digits and runs of everything else, such as `10R5` into `10`,
+ * `R` and `5`.
+ */
+pub fn tokens(line: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut previous: Option<char> = None;
+    for (i, c) in line.char_indices() {
+        if previous.is_some_and(|p| p.is_ascii_digit() != c.is_ascii_digit()) {
+            tokens.push(&line[start..i]);
+            start = i;
+        }
+        previous = Some(c);
+    }
+    if start < line.len() {
+        tokens.push(&line[start..]);
+    }
+    tokens
+}
+
+/**
+ * Parses a grid with a character per cell, as with [`Grid::parse`].
+ */
+pub fn char_grid(input: &str) -> AocResult<Grid<char>> {
+    Grid::parse(input, Ok)
+}
+
+fn parse_value<T>(value: &str, index: usize) -> AocResult<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|err| format!("invalid value '{value}': {err}"))
+        .on_line(index)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn line_of<T>(result: AocResult<T>) -> usize {
+        match result {
+            Err(AocError::Parse { line, .. }) => line,
+            _ => panic!("Expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn blocks_test() {
+        let input = "a\nb\n\n\nc\n  \nd\ne\n";
+        let blocks = blocks(input);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].lines().collect::<Vec<_>>(), [(0, "a"), (1, "b")]);
+        assert_eq!(blocks[1].first_index(), 4);
+        assert_eq!(blocks[2].line(1).unwrap(), (7, "e"));
+        assert_eq!(line_of(blocks[2].line(2)), 8);
+    }
+
+    #[test]
+    fn list_test() {
+        assert_eq!(list::<i32>("3, 4,5", ",", 0).unwrap(), [3, 4, 5]);
+        assert_eq!(list::<u64>("  7   8 9 ", " ", 0).unwrap(), [7, 8, 9]);
+        assert_eq!(list::<i32>("1 -> -2", "->", 0).unwrap(), [1, -2]);
+        assert_eq!(line_of(list::<i32>("1,x", ",", 4)), 5);
+    }
+
+    #[test]
+    fn signed_ints_test() {
+        assert_eq!(
+            signed_ints::<i32>("target area: x=20..30, y=-10..-5", 0).unwrap(),
+            [20, 30, -10, -5]
+        );
+        assert_eq!(signed_ints::<i32>("2-4,6-8", 0).unwrap(), [2, 4, 6, 8]);
+        assert!(signed_ints::<i32>("no numbers - here", 0)
+            .unwrap()
+            .is_empty());
+        assert_eq!(line_of(signed_ints::<u8>("x=300", 2)), 3);
+    }
+
+    #[test]
+    fn record_test() {
+        assert_eq!(
+            record("root: pppw + sjmn", 0).unwrap(),
+            ("root", "pppw + sjmn")
+        );
+        assert_eq!(
+            field("  Starting items: 79, 98", "Starting items: ", 0).unwrap(),
+            "79, 98"
+        );
+        assert_eq!(line_of(record("root", 1)), 2);
+        assert_eq!(line_of(field("Test: 3", "Operation: ", 1)), 2);
+    }
+
+    #[test]
+    fn tokens_test() {
+        assert_eq!(tokens("10R5L5"), ["10", "R", "5", "L", "5"]);
+        assert_eq!(tokens("RL12"), ["RL", "12"]);
+        assert!(tokens("").is_empty());
+
+        let grid = char_grid("#.\n.#\n").unwrap();
+        assert_eq!(grid.row(1), Some(&['.', '#'][..]));
+    }
+}
@@ -0,0 +1,345 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::{
+    coordinate::{Coordinate, CoordinateMap},
+    grid::Grid,
+};
+
+/**
+ * A red, green and blue colour.
+ */
+pub type Colour = [u8; 3];
+
+pub const BLACK: Colour = [0, 0, 0];
+pub const WHITE: Colour = [255, 255, 255];
+
+/**
+ * An image of a rectangle of cells, with a colour for each cell. Each cell is drawn as a square
+ * of pixels, `scale` pixels across, when the picture is written out.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picture {
+    /**
+     * The cell in the top left corner.
+     */
+    origin: Coordinate,
+    width: usize,
+    height: usize,
+    background: Colour,
+    pixels: Vec<Colour>,
+}
+
+impl Picture {
+    /**
+     * Creates a picture of the cells from `origin` that is filled with the background colour.
+     */
+    pub fn new(origin: Coordinate, width: usize, height: usize, background: Colour) -> Self {
+        Picture {
+            origin,
+            width,
+            height,
+            background,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /**
+     * Creates a picture just big enough to show every cell in the map. Cells that aren't in the
+     * map are left as the background colour.
+     */
+    pub fn from_map<T>(
+        map: &CoordinateMap<T>,
+        background: Colour,
+        mut colour: impl FnMut(Coordinate, &T) -> Colour,
+    ) -> Self {
+        let Some((min, max)) = bounds(map.keys().copied()) else {
+            return Picture::new(Coordinate::origin(), 0, 0, background);
+        };
+        let mut picture = Picture::new(
+            min,
+            (max.x - min.x + 1) as usize,
+            (max.y - min.y + 1) as usize,
+            background,
+        );
+        for (&coordinate, value) in map {
+            picture.set(coordinate, colour(coordinate, value));
+        }
+        picture
+    }
+
+    /**
+     * Creates a picture of every cell in the grid.
+     */
+    pub fn from_grid<T>(grid: &Grid<T>, mut colour: impl FnMut(Coordinate, &T) -> Colour) -> Self {
+        Picture {
+            origin: Coordinate::origin(),
+            width: grid.width(),
+            height: grid.height(),
+            background: BLACK,
+            pixels: grid.iter().map(|(c, value)| colour(c, value)).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, coordinate: Coordinate) -> Option<Colour> {
+        self.index(coordinate).map(|index| self.pixels[index])
+    }
+
+    /**
+     * Colours the cell. Cells outside the picture are ignored.
+     */
+    pub fn set(&mut self, coordinate: Coordinate, colour: Colour) {
+        if let Some(index) = self.index(coordinate) {
+            self.pixels[index] = colour;
+        }
+    }
+
+    /**
+     * Colours every cell along the path, such as a route found by a search.
+     */
+    pub fn draw_path(&mut self, path: &[Coordinate], colour: Colour) {
+        for &coordinate in path {
+            self.set(coordinate, colour);
+        }
+    }
+
+    /**
+     * Writes the picture as a PNG.
+     */
+    pub fn write_png(&self, writer: impl Write, scale: usize) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(
+            writer,
+            (self.width * scale) as u32,
+            (self.height * scale) as u32,
+        );
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.scaled(scale).concat())
+            .map_err(io::Error::other)
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>, scale: usize) -> io::Result<()> {
+        self.write_png(BufWriter::new(File::create(path)?), scale)
+    }
+
+    /**
+     * Gets the colour of each pixel, row by row, with each cell drawn `scale` pixels across.
+     */
+    fn scaled(&self, scale: usize) -> Vec<Colour> {
+        let mut pixels = Vec::with_capacity(self.pixels.len() * scale * scale);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            let scaled_row = row
+                .iter()
+                .flat_map(|&colour| std::iter::repeat_n(colour, scale))
+                .collect::<Vec<_>>();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&scaled_row);
+            }
+        }
+        pixels
+    }
+
+    /**
+     * Copies the picture onto a bigger area, filling the rest with the background colour.
+     */
+    fn expand(&self, origin: Coordinate, width: usize, height: usize) -> Picture {
+        let mut expanded = Picture::new(origin, width, height, self.background);
+        for (index, &colour) in self.pixels.iter().enumerate() {
+            let offset = Coordinate::new((index % self.width) as i32, (index / self.width) as i32);
+            expanded.set(self.origin + offset, colour);
+        }
+        expanded
+    }
+
+    fn index(&self, coordinate: Coordinate) -> Option<usize> {
+        let Coordinate { x, y } = coordinate - self.origin;
+        (x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height)
+            .then(|| y as usize * self.width + x as usize)
+    }
+}
+
+/**
+ * Writes the pictures as the frames of a looping animated GIF, showing each for `delay`
+ * hundredths of a second. Frames covering different cells are lined up by coordinate, so a
+ * map can grow as the animation goes on.
+ *
+ * GIFs have a palette of 256 colours, which the frames must fit between them.
+ */
+pub fn write_gif(
+    frames: &[Picture],
+    writer: impl Write,
+    scale: usize,
+    delay: u16,
+) -> io::Result<()> {
+    let corners = frames
+        .iter()
+        .filter(|frame| !frame.pixels.is_empty())
+        .flat_map(|frame| {
+            let far = Coordinate::new(frame.width as i32 - 1, frame.height as i32 - 1);
+            [frame.origin, frame.origin + far]
+        });
+    let Some((min, max)) = bounds(corners) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "there are no frames to animate",
+        ));
+    };
+    let (width, height) = ((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize);
+    let too_big = || io::Error::new(io::ErrorKind::InvalidInput, "the animation is too big");
+    let gif_width = u16::try_from(width * scale).map_err(|_| too_big())?;
+    let gif_height = u16::try_from(height * scale).map_err(|_| too_big())?;
+
+    let frames = frames
+        .iter()
+        .map(|frame| frame.expand(min, width, height))
+        .collect::<Vec<_>>();
+    let mut palette: HashMap<Colour, u8> = HashMap::new();
+    for &colour in frames.iter().flat_map(|frame| &frame.pixels) {
+        if !palette.contains_key(&colour) {
+            let index = u8::try_from(palette.len()).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "the frames use over 256 colours",
+                )
+            })?;
+            palette.insert(colour, index);
+        }
+    }
+    let mut global_palette = vec![0; palette.len() * 3];
+    for (colour, &index) in &palette {
+        global_palette[index as usize * 3..][..3].copy_from_slice(colour);
+    }
+
+    let mut encoder = gif::Encoder::new(writer, gif_width, gif_height, &global_palette)
+        .map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+    for frame in &frames {
+        let indices = frame
+            .scaled(scale)
+            .iter()
+            .map(|colour| palette[colour])
+            .collect::<Vec<_>>();
+        let mut gif_frame = gif::Frame::from_indexed_pixels(gif_width, gif_height, indices, None);
+        gif_frame.delay = delay;
+        encoder.write_frame(&gif_frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+pub fn save_gif(
+    frames: &[Picture],
+    path: impl AsRef<Path>,
+    scale: usize,
+    delay: u16,
+) -> io::Result<()> {
+    write_gif(frames, BufWriter::new(File::create(path)?), scale, delay)
+}
+
+/**
+ * Gets the top left and bottom right corners of the rectangle holding every coordinate.
+ */
+fn bounds(coordinates: impl IntoIterator<Item = Coordinate>) -> Option<(Coordinate, Coordinate)> {
+    coordinates
+        .into_iter()
+        .fold(None, |bounds, c| match bounds {
+            None => Some((c, c)),
+            Some((min, max)) => Some((
+                Coordinate::new(min.x.min(c.x), min.y.min(c.y)),
+                Coordinate::new(max.x.max(c.x), max.y.max(c.y)),
+            )),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const RED: Colour = [255, 0, 0];
+
+    #[test]
+    fn picture_test() {
+        let map = CoordinateMap::from([
+            (Coordinate::new(-1, 2), 1),
+            (Coordinate::new(2, 3), 2),
+            (Coordinate::new(0, 2), 0),
+        ]);
+        let mut picture =
+            Picture::from_map(&map, BLACK, |_, &value| if value > 0 { WHITE } else { RED });
+        assert_eq!((picture.width(), picture.height()), (4, 2));
+        assert_eq!(picture.get(Coordinate::new(-1, 2)), Some(WHITE));
+        assert_eq!(picture.get(Coordinate::new(0, 2)), Some(RED));
+        assert_eq!(picture.get(Coordinate::new(0, 3)), Some(BLACK));
+        assert_eq!(picture.get(Coordinate::new(0, 0)), None);
+
+        picture.draw_path(&[Coordinate::new(0, 3), Coordinate::new(0, 4)], RED);
+        assert_eq!(picture.get(Coordinate::new(0, 3)), Some(RED));
+
+        let grid = Grid::parse("#.\n.#\n", |c| Ok(c == '#')).unwrap();
+        let picture = Picture::from_grid(&grid, |_, &lit| if lit { WHITE } else { BLACK });
+        assert_eq!(picture.scaled(2)[..4], [WHITE, WHITE, BLACK, BLACK]);
+        assert_eq!(picture.scaled(2)[12..], [BLACK, BLACK, WHITE, WHITE]);
+    }
+
+    #[test]
+    fn png_test() {
+        let picture = Picture::new(Coordinate::origin(), 3, 2, RED);
+        let mut bytes = Vec::new();
+        picture.write_png(&mut bytes, 2).unwrap();
+
+        let mut reader = png::Decoder::new(&bytes[..]).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (6, 4));
+        assert_eq!(pixels[..6], [255, 0, 0, 255, 0, 0]);
+    }
+
+    #[test]
+    fn gif_test() {
+        // The second frame is bigger than the first, which is padded to match.
+        let small = Picture::new(Coordinate::new(1, 1), 1, 1, WHITE);
+        let mut big = Picture::new(Coordinate::origin(), 2, 2, BLACK);
+        big.set(Coordinate::new(1, 1), RED);
+        let mut bytes = Vec::new();
+        write_gif(&[small, big], &mut bytes, 3, 10).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(&bytes[..]).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (6, 6));
+        let mut corners = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 10);
+            let last = frame.buffer.len() - 4;
+            corners.push((
+                frame.buffer[..3].to_vec(),
+                frame.buffer[last..last + 3].to_vec(),
+            ));
+        }
+        assert_eq!(
+            corners,
+            [
+                (WHITE.to_vec(), WHITE.to_vec()),
+                (BLACK.to_vec(), RED.to_vec())
+            ]
+        );
+
+        assert!(write_gif(&[], &mut Vec::new(), 1, 10).is_err());
+    }
+}
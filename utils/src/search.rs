@@ -0,0 +1,308 @@
+use std::{
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/**
+ * Uses a breadth-first search to find the fewest steps from the start node to a node
+ * satisfying `is_goal`, where every step to a neighbour costs the same.
+ *
+ * Returns the number of steps and the path taken, including both the start and the goal, or
+ * `None` if no goal can be reached.
+ */
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(usize, Vec<N>)>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut nodes = Nodes::new(start);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(index) = queue.pop_front() {
+        if is_goal(&nodes.nodes[index]) {
+            let path = nodes.path(index);
+            return Some((path.len() - 1, path));
+        }
+        for next in neighbours(&nodes.nodes[index]) {
+            if !nodes.indices.contains_key(&next) {
+                queue.push_back(nodes.insert(next, index));
+            }
+        }
+    }
+    None
+}
+
+/**
+ * Uses Dijkstra's algorithm to find the cheapest route from the start node to a node
+ * satisfying `is_goal`. `neighbours` gives each node reachable from a node along with the cost
+ * of stepping to it, which must not be negative.
+ *
+ * Returns the total cost and the path taken, including both the start and the goal, or `None`
+ * if no goal can be reached.
+ */
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Hash + Eq + Clone,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbours, is_goal, |_| C::default())
+}
+
+/**
+ * Uses A* to find the cheapest route from the start node to a node satisfying `is_goal`, as
+ * with [`dijkstra`]. `heuristic` estimates the remaining cost from a node to the nearest goal,
+ * and must never overestimate it for the route found to be the cheapest.
+ */
+pub fn astar<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+    heuristic: impl FnMut(&N) -> C,
+) -> Option<(C, Vec<N>)>
+where
+    N: Hash + Eq + Clone,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (nodes, costs, goal) = explore(start, neighbours, is_goal, heuristic);
+    goal.map(|index| (costs[index], nodes.path(index)))
+}
+
+/**
+ * Uses Dijkstra's algorithm to find the cheapest cost of reaching every node that can be
+ * reached from the start node, including the start itself at no cost.
+ */
+pub fn distances<N, C, I>(start: N, neighbours: impl FnMut(&N) -> I) -> HashMap<N, C>
+where
+    N: Hash + Eq + Clone,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (nodes, costs, _) = explore(start, neighbours, |_| false, |_| C::default());
+    nodes.nodes.into_iter().zip(costs).collect()
+}
+
+/**
+ * Searches outwards from the start node, cheapest estimate first, until a goal is found or there
+ * is nowhere left to go. Gives every node reached with its cost so far, and the goal's index.
+ */
+fn explore<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+    mut heuristic: impl FnMut(&N) -> C,
+) -> (Nodes<N>, Vec<C>, Option<usize>)
+where
+    N: Hash + Eq + Clone,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut heap = BinaryHeap::new();
+    heap.push(Candidate {
+        estimate: heuristic(&start),
+        cost: C::default(),
+        index: 0,
+    });
+    let mut nodes = Nodes::new(start);
+    let mut costs = vec![C::default()];
+
+    while let Some(Candidate { cost, index, .. }) = heap.pop() {
+        // A cheaper way here was found after this candidate was queued.
+        if cost > costs[index] {
+            continue;
+        }
+        if is_goal(&nodes.nodes[index]) {
+            return (nodes, costs, Some(index));
+        }
+        for (next, step_cost) in neighbours(&nodes.nodes[index]) {
+            let next_cost = cost + step_cost;
+            let next_index = match nodes.indices.get(&next) {
+                Some(&i) if next_cost >= costs[i] => continue,
+                Some(&i) => {
+                    costs[i] = next_cost;
+                    nodes.parents[i] = Some(index);
+                    i
+                }
+                None => {
+                    costs.push(next_cost);
+                    nodes.insert(next, index)
+                }
+            };
+            heap.push(Candidate {
+                estimate: next_cost + heuristic(&nodes.nodes[next_index]),
+                cost: next_cost,
+                index: next_index,
+            });
+        }
+    }
+    (nodes, costs, None)
+}
+
+/**
+ * Every node reached so far, each with the node it was reached from, so that paths can be
+ * followed back to the start.
+ */
+struct Nodes<N> {
+    nodes: Vec<N>,
+    parents: Vec<Option<usize>>,
+    indices: HashMap<N, usize>,
+}
+
+impl<N: Hash + Eq + Clone> Nodes<N> {
+    fn new(start: N) -> Self {
+        Nodes {
+            nodes: vec![start.clone()],
+            parents: vec![None],
+            indices: HashMap::from([(start, 0)]),
+        }
+    }
+
+    /**
+     * Adds a node reached from the parent, returning its index.
+     */
+    fn insert(&mut self, node: N, parent: usize) -> usize {
+        let index = self.nodes.len();
+        self.indices.insert(node.clone(), index);
+        self.nodes.push(node);
+        self.parents.push(Some(parent));
+        index
+    }
+
+    fn path(&self, mut index: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[index].clone()];
+        while let Some(parent) = self.parents[index] {
+            path.push(self.nodes[parent].clone());
+            index = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Candidate<C> {
+    /**
+     * Cost so far plus the heuristic's estimate of the cost still to come.
+     */
+    estimate: C,
+    cost: C,
+    index: usize,
+}
+
+impl<C: Ord> Ord for Candidate<C> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl<C: Ord> PartialOrd for Candidate<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::coordinate::Coordinate;
+
+    use super::*;
+
+    const MAZE: [&str; 5] = ["S..#....", ".#.#.##.", ".#...#..", ".####.#.", "......#E"];
+
+    fn open(c: Coordinate) -> bool {
+        c.x >= 0
+            && c.y >= 0
+            && MAZE
+                .get(c.y as usize)
+                .and_then(|row| row.as_bytes().get(c.x as usize))
+                .is_some_and(|&cell| cell != b'#')
+    }
+
+    fn maze_neighbours(c: &Coordinate) -> Vec<Coordinate> {
+        c.orthogonal().into_iter().filter(|&n| open(n)).collect()
+    }
+
+    #[test]
+    fn bfs_test() {
+        let end = Coordinate::new(7, 4);
+        let (steps, path) = bfs(Coordinate::origin(), maze_neighbours, |c| *c == end).unwrap();
+        assert_eq!(steps, 15);
+        assert_eq!(path.len(), 16);
+        assert_eq!(path.first(), Some(&Coordinate::origin()));
+        assert_eq!(path.last(), Some(&end));
+        assert!(path
+            .windows(2)
+            .all(|w| maze_neighbours(&w[0]).contains(&w[1])));
+
+        assert_eq!(
+            bfs(Coordinate::origin(), maze_neighbours, |c| c.x > 7),
+            None
+        );
+    }
+
+    #[test]
+    fn dijkstra_test() {
+        // Going round through `b` and `c` is cheaper than the direct edge to `d`.
+        let edges = HashMap::from([
+            ('a', vec![('b', 1), ('d', 10)]),
+            ('b', vec![('c', 2)]),
+            ('c', vec![('d', 3)]),
+            ('d', vec![]),
+        ]);
+        let neighbours = |n: &char| edges[n].clone();
+
+        assert_eq!(
+            dijkstra('a', neighbours, |&n| n == 'd'),
+            Some((6, vec!['a', 'b', 'c', 'd']))
+        );
+        assert_eq!(
+            dijkstra('a', neighbours, |&n| n == 'a'),
+            Some((0, vec!['a']))
+        );
+        assert_eq!(dijkstra('d', neighbours, |&n| n == 'a'), None);
+
+        assert_eq!(
+            distances('a', neighbours),
+            HashMap::from([('a', 0), ('b', 1), ('c', 3), ('d', 6)])
+        );
+        assert_eq!(
+            distances('c', neighbours),
+            HashMap::from([('c', 0), ('d', 3)])
+        );
+    }
+
+    #[test]
+    fn astar_test() {
+        let end = Coordinate::new(7, 4);
+        let weighted = |c: &Coordinate| {
+            maze_neighbours(c)
+                .into_iter()
+                .map(|n| (n, 1))
+                .collect::<Vec<_>>()
+        };
+        let manhattan = |c: &Coordinate| (end.x - c.x).abs() + (end.y - c.y).abs();
+
+        let (cost, path) = astar(Coordinate::origin(), weighted, |c| *c == end, manhattan).unwrap();
+        assert_eq!(cost, 15);
+        assert_eq!(path.len(), 16);
+        assert_eq!(
+            dijkstra(Coordinate::origin(), weighted, |c| *c == end).map(|(cost, _)| cost),
+            Some(cost)
+        );
+    }
+}
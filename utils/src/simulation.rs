@@ -0,0 +1,22 @@
+/**
+ * A puzzle's simulation, exposed a step at a time so that it can be watched, such as by the
+ * runner's `--visualise` viewer.
+ */
+pub trait Simulation {
+    /**
+     * Advances a step, returning whether there was anything left to simulate. Once this has
+     * returned `false` it should not be called again. Anything that can go wrong should be found
+     * when the simulation is created, or shown in its metrics, rather than just stopping here.
+     */
+    fn step(&mut self) -> bool;
+
+    /**
+     * Draws the current state as lines of text.
+     */
+    fn render(&self) -> Vec<String>;
+
+    /**
+     * Gets named values describing the current state, such as a count of things so far.
+     */
+    fn metrics(&self) -> Vec<(&'static str, String)>;
+}
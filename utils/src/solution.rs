@@ -0,0 +1,425 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    error::{AocError, AocResult},
+    simulation::Simulation,
+};
+
+/**
+ * The answer to one part of a puzzle.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(u128),
+    Text(String),
+    /**
+     * Multi-line dot-matrix output, which spells out the answer when printed.
+     */
+    Art(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => value.fmt(f),
+            Answer::BigInteger(value) => value.fmt(f),
+            Answer::Text(value) | Answer::Art(value) => value.fmt(f),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Integer(value.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Integer(value)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        i64::try_from(value).map_or(Answer::BigInteger(value.into()), Answer::Integer)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        (value as u64).into()
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        Answer::BigInteger(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+/**
+ * Input parsed ahead of solving by [`Solution::parse`].
+ */
+pub type ParsedInput = Box<dyn Any + Send + Sync>;
+
+/**
+ * A day's puzzle solution.
+ */
+pub trait Solution: Send + Sync {
+    fn year(&self) -> u32;
+
+    fn day(&self) -> u32;
+
+    /**
+     * Solves part 1 using the given puzzle input.
+     */
+    fn part_1(&self, input: &str) -> AocResult<Answer>;
+
+    /**
+     * Solves part 2 using the given puzzle input.
+     */
+    fn part_2(&self, _input: &str) -> AocResult<Answer> {
+        Err(no_part_2(self.year(), self.day()))
+    }
+
+    /**
+     * Whether there is a part 2. The last day of each year only has one part.
+     */
+    fn has_part_2(&self) -> bool {
+        true
+    }
+
+    /**
+     * Solves the given part, which must be 1 or 2. Empty input is rejected before it reaches
+     * the part.
+     */
+    fn solve(&self, part: u32, input: &str) -> AocResult<Answer> {
+        check_not_empty(input)?;
+        match part {
+            1 => self.part_1(input),
+            2 => self.part_2(input),
+            _ => Err(no_such_part(part)),
+        }
+    }
+
+    /**
+     * Parses the input so that parsing can be timed apart from solving. Solutions that don't
+     * parse separately keep the text as it is and parse it in each part.
+     */
+    fn parse(&self, input: &str) -> AocResult<ParsedInput> {
+        Ok(Box::new(input.to_string()))
+    }
+
+    /**
+     * Solves the given part using input from [`Solution::parse`].
+     */
+    fn solve_parsed(&self, part: u32, parsed: &ParsedInput) -> AocResult<Answer> {
+        let input = parsed
+            .downcast_ref::<String>()
+            .ok_or_else(|| AocError::invalid_state("parsed input has the wrong type"))?;
+        self.solve(part, input)
+    }
+
+    /**
+     * Whether [`Solution::parse`] does the parsing, rather than each part.
+     */
+    fn parses_separately(&self) -> bool {
+        false
+    }
+
+    /**
+     * Sets up a simulation of the given part that can be stepped through and watched. Only
+     * days that simulate something step by step support this.
+     */
+    fn simulate(&self, _part: u32, _input: &str) -> AocResult<Box<dyn Simulation>> {
+        Err(no_simulation(self.year(), self.day()))
+    }
+}
+
+/**
+ * A solution whose parts share input that is parsed up front, so parse and solve time can be
+ * measured separately. Register it wrapped in [`Parsed`].
+ */
+pub trait ParsedSolution: Send + Sync {
+    type Input: Send + Sync + 'static;
+
+    fn year(&self) -> u32;
+
+    fn day(&self) -> u32;
+
+    fn parse(&self, input: &str) -> AocResult<Self::Input>;
+
+    fn part_1(&self, input: &Self::Input) -> AocResult<Answer>;
+
+    fn part_2(&self, _input: &Self::Input) -> AocResult<Answer> {
+        Err(no_part_2(self.year(), self.day()))
+    }
+
+    fn has_part_2(&self) -> bool {
+        true
+    }
+
+    fn simulate(&self, _part: u32, _input: &Self::Input) -> AocResult<Box<dyn Simulation>> {
+        Err(no_simulation(self.year(), self.day()))
+    }
+}
+
+/**
+ * Adapts a [`ParsedSolution`] into a [`Solution`].
+ */
+pub struct Parsed<S>(pub S);
+
+impl<S: ParsedSolution> Solution for Parsed<S> {
+    fn year(&self) -> u32 {
+        self.0.year()
+    }
+
+    fn day(&self) -> u32 {
+        self.0.day()
+    }
+
+    fn part_1(&self, input: &str) -> AocResult<Answer> {
+        self.0.part_1(&self.0.parse(input)?)
+    }
+
+    fn part_2(&self, input: &str) -> AocResult<Answer> {
+        self.0.part_2(&self.0.parse(input)?)
+    }
+
+    fn has_part_2(&self) -> bool {
+        self.0.has_part_2()
+    }
+
+    fn parse(&self, input: &str) -> AocResult<ParsedInput> {
+        check_not_empty(input)?;
+        Ok(Box::new(self.0.parse(input)?))
+    }
+
+    fn solve_parsed(&self, part: u32, parsed: &ParsedInput) -> AocResult<Answer> {
+        let input = parsed
+            .downcast_ref::<S::Input>()
+            .ok_or_else(|| AocError::invalid_state("parsed input has the wrong type"))?;
+        match part {
+            1 => self.0.part_1(input),
+            2 => self.0.part_2(input),
+            _ => Err(no_such_part(part)),
+        }
+    }
+
+    fn parses_separately(&self) -> bool {
+        true
+    }
+
+    fn simulate(&self, part: u32, input: &str) -> AocResult<Box<dyn Simulation>> {
+        check_not_empty(input)?;
+        self.0.simulate(part, &self.0.parse(input)?)
+    }
+}
+
+/**
+ * Rejects input with nothing in it, as no puzzle can be solved from it.
+ */
+fn check_not_empty(input: &str) -> AocResult<()> {
+    if input.trim().is_empty() {
+        Err(AocError::invalid_state("the input is empty"))
+    } else {
+        Ok(())
+    }
+}
+
+fn no_part_2(year: u32, day: u32) -> AocError {
+    AocError::Unsupported(format!("{year} day {day} has no part 2"))
+}
+
+fn no_such_part(part: u32) -> AocError {
+    AocError::Unsupported(format!("there is no part {part}"))
+}
+
+fn no_simulation(year: u32, day: u32) -> AocError {
+    AocError::Unsupported(format!("{year} day {day} has no simulation to visualise"))
+}
+
+/**
+ * Collection of solutions, kept ordered by year and day.
+ */
+#[derive(Default)]
+pub struct Registry {
+    solutions: Vec<Box<dyn Solution>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Registry::default()
+    }
+
+    pub fn register<S: Solution + 'static>(&mut self, solution: S) {
+        self.solutions.push(Box::new(solution));
+        self.solutions.sort_by_key(|s| (s.year(), s.day()));
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Option<&dyn Solution> {
+        self.iter().find(|s| s.year() == year && s.day() == day)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Solution> {
+        self.solutions.iter().map(|s| s.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.solutions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.solutions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::error::LineContext;
+
+    use super::*;
+
+    struct TestSolution {
+        year: u32,
+        day: u32,
+    }
+
+    impl Solution for TestSolution {
+        fn year(&self) -> u32 {
+            self.year
+        }
+
+        fn day(&self) -> u32 {
+            self.day
+        }
+
+        fn part_1(&self, input: &str) -> AocResult<Answer> {
+            Ok(input.to_string().into())
+        }
+
+        fn has_part_2(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn registry_order_test() {
+        let mut registry = Registry::new();
+        registry.register(TestSolution { year: 2022, day: 2 });
+        registry.register(TestSolution { year: 2021, day: 5 });
+        registry.register(TestSolution { year: 2022, day: 1 });
+
+        let order = registry
+            .iter()
+            .map(|s| (s.year(), s.day()))
+            .collect::<Vec<_>>();
+        assert_eq!(order, vec![(2021, 5), (2022, 1), (2022, 2)]);
+    }
+
+    #[test]
+    fn registry_get_test() {
+        let mut registry = Registry::new();
+        registry.register(TestSolution { year: 2022, day: 2 });
+
+        assert!(registry.get(2022, 2).is_some());
+        assert!(registry.get(2022, 3).is_none());
+    }
+
+    #[test]
+    fn solve_test() {
+        let solution = TestSolution { year: 2022, day: 2 };
+        assert_eq!(
+            solution.solve(1, "input").unwrap(),
+            Answer::Text("input".to_string())
+        );
+        assert!(matches!(
+            solution.solve(2, "input"),
+            Err(AocError::Unsupported(_))
+        ));
+        assert!(matches!(
+            solution.solve(3, "input"),
+            Err(AocError::Unsupported(_))
+        ));
+        assert!(matches!(
+            solution.simulate(1, "input"),
+            Err(AocError::Unsupported(_))
+        ));
+        assert!(matches!(
+            solution.solve(1, " \n\n"),
+            Err(AocError::InvalidState(_))
+        ));
+    }
+
+    struct TestParsedSolution;
+
+    impl ParsedSolution for TestParsedSolution {
+        type Input = Vec<i64>;
+
+        fn year(&self) -> u32 {
+            2021
+        }
+
+        fn day(&self) -> u32 {
+            1
+        }
+
+        fn parse(&self, input: &str) -> AocResult<Vec<i64>> {
+            input
+                .lines()
+                .enumerate()
+                .map(|(i, line)| line.parse::<i64>().on_line(i))
+                .collect()
+        }
+
+        fn part_1(&self, input: &Vec<i64>) -> AocResult<Answer> {
+            Ok(Answer::from(input.iter().sum::<i64>()))
+        }
+    }
+
+    #[test]
+    fn parsed_test() {
+        let solution = Parsed(TestParsedSolution);
+        assert!(solution.parses_separately());
+        assert_eq!(solution.solve(1, "1\n2\n").unwrap(), Answer::Integer(3));
+
+        let parsed = solution.parse("1\n2\n").unwrap();
+        assert_eq!(
+            solution.solve_parsed(1, &parsed).unwrap(),
+            Answer::Integer(3)
+        );
+        assert!(matches!(
+            solution.solve_parsed(2, &parsed),
+            Err(AocError::Unsupported(_))
+        ));
+        assert!(solution.parse("x").is_err());
+        assert!(solution.parse("").is_err());
+
+        let text = TestSolution { year: 2022, day: 2 };
+        assert!(!text.parses_separately());
+        assert!(matches!(
+            text.solve_parsed(1, &parsed),
+            Err(AocError::InvalidState(_))
+        ));
+    }
+
+    #[test]
+    fn answer_test() {
+        assert_eq!(Answer::from(-5_i32), Answer::Integer(-5));
+        assert_eq!(Answer::from(5_usize), Answer::Integer(5));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInteger(u64::MAX.into()));
+        assert_eq!(
+            Answer::from(444356092776315_u128).to_string(),
+            "444356092776315"
+        );
+    }
+}